@@ -0,0 +1,294 @@
+//! Built-in load-generation mode for tuning `batch_size`/`flush_interval`.
+//!
+//! Picking `EDGE_COLLECTOR_BATCH_SIZE`/`EDGE_COLLECTOR_FLUSH_INTERVAL_SECS`
+//! for a given device and network normally means standing up an external
+//! load-testing harness. [`run_benchmark`] instead drives [`LogGenerator`]
+//! and [`LogClient::send_batch`] directly, in-process, sweeping a list of
+//! batch sizes and reporting the achieved throughput and per-batch send
+//! latency percentiles for each — the same real HTTP path
+//! [`crate::buffer::buffer_task`]'s flush callback exercises, just without
+//! the buffering in between.
+
+use std::time::{Duration, Instant};
+
+use crate::client::LogClient;
+use crate::log_generator::{LogBatch, LogGenerator};
+
+/// Batch sizes swept by default if `EDGE_COLLECTOR_BENCHMARK_BATCH_SIZES` is
+/// unset.
+pub const DEFAULT_BATCH_SIZES: &[usize] = &[1, 2, 4, 8, 16, 32, 100];
+
+/// Default number of warmup sends per batch size, discarded before timing
+/// starts so connection setup and circuit breaker/AIMD warmup don't skew the
+/// reported percentiles.
+pub const DEFAULT_WARMUP_ITERATIONS: usize = 5;
+
+/// Default number of timed sends per batch size.
+pub const DEFAULT_ITERATIONS: usize = 20;
+
+/// Configuration for a benchmark run, normally built from environment
+/// variables via [`BenchmarkConfig::from_env`].
+#[derive(Debug, Clone)]
+pub struct BenchmarkConfig {
+    /// Batch sizes to sweep, in the order they're reported.
+    pub batch_sizes: Vec<usize>,
+
+    /// Warmup sends per batch size, not included in the reported stats.
+    pub warmup_iterations: usize,
+
+    /// Timed sends per batch size.
+    pub iterations: usize,
+}
+
+impl Default for BenchmarkConfig {
+    fn default() -> Self {
+        Self {
+            batch_sizes: DEFAULT_BATCH_SIZES.to_vec(),
+            warmup_iterations: DEFAULT_WARMUP_ITERATIONS,
+            iterations: DEFAULT_ITERATIONS,
+        }
+    }
+}
+
+impl BenchmarkConfig {
+    /// Read `EDGE_COLLECTOR_BENCHMARK_BATCH_SIZES` (comma-separated),
+    /// `EDGE_COLLECTOR_BENCHMARK_WARMUP`, and
+    /// `EDGE_COLLECTOR_BENCHMARK_ITERATIONS`, falling back to defaults for
+    /// anything unset or unparseable.
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+
+        let batch_sizes = std::env::var("EDGE_COLLECTOR_BENCHMARK_BATCH_SIZES")
+            .ok()
+            .map(|raw| {
+                raw.split(',')
+                    .filter_map(|s| s.trim().parse::<usize>().ok())
+                    .filter(|n| *n > 0)
+                    .collect::<Vec<usize>>()
+            })
+            .filter(|sizes| !sizes.is_empty())
+            .unwrap_or(defaults.batch_sizes);
+
+        let warmup_iterations = std::env::var("EDGE_COLLECTOR_BENCHMARK_WARMUP")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(defaults.warmup_iterations);
+
+        let iterations = std::env::var("EDGE_COLLECTOR_BENCHMARK_ITERATIONS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(defaults.iterations);
+
+        Self { batch_sizes, warmup_iterations, iterations }
+    }
+}
+
+/// Throughput and latency stats for one swept batch size.
+#[derive(Debug, Clone)]
+pub struct BenchmarkResult {
+    /// The batch size this result was measured at.
+    pub batch_size: usize,
+
+    /// Logs sent across all timed iterations at this batch size.
+    pub logs_sent: u64,
+
+    /// Wall-clock time spent sending all timed iterations.
+    pub total_duration: Duration,
+
+    /// Achieved rate in logs/sec across the timed iterations.
+    pub logs_per_sec: f64,
+
+    /// 50th percentile per-batch send latency.
+    pub p50: Duration,
+
+    /// 90th percentile per-batch send latency.
+    pub p90: Duration,
+
+    /// 99th percentile per-batch send latency.
+    pub p99: Duration,
+
+    /// Timed sends that failed (after the client's own retries); excluded
+    /// from the latency percentiles since a failed send's "latency" isn't
+    /// comparable to a successful one.
+    pub failures: u64,
+}
+
+/// Sweep `config.batch_sizes`, sending `config.iterations` batches of each
+/// size through `client` (after `config.warmup_iterations` untimed warmup
+/// sends), and return one [`BenchmarkResult`] per batch size in sweep order.
+pub async fn run_benchmark(client: &LogClient, config: &BenchmarkConfig) -> Vec<BenchmarkResult> {
+    let generator = LogGenerator::with_defaults();
+    let mut results = Vec::with_capacity(config.batch_sizes.len());
+
+    for &batch_size in &config.batch_sizes {
+        for _ in 0..config.warmup_iterations {
+            let batch = LogBatch::new(generator.generate_batch(batch_size));
+            let _ = client.send_batch(batch).await;
+        }
+
+        let mut latencies = Vec::with_capacity(config.iterations);
+        let mut failures = 0u64;
+        let sweep_start = Instant::now();
+
+        for _ in 0..config.iterations {
+            let batch = LogBatch::new(generator.generate_batch(batch_size));
+            let send_start = Instant::now();
+            let outcome = client.send_batch(batch).await;
+            let elapsed = send_start.elapsed();
+
+            match outcome {
+                Ok(_) => latencies.push(elapsed),
+                Err(_) => failures += 1,
+            }
+        }
+
+        let total_duration = sweep_start.elapsed();
+        let logs_sent = latencies.len() as u64 * batch_size as u64;
+        let logs_per_sec = if total_duration.as_secs_f64() > 0.0 {
+            logs_sent as f64 / total_duration.as_secs_f64()
+        } else {
+            0.0
+        };
+
+        latencies.sort();
+        results.push(BenchmarkResult {
+            batch_size,
+            logs_sent,
+            total_duration,
+            logs_per_sec,
+            p50: percentile(&latencies, 0.50),
+            p90: percentile(&latencies, 0.90),
+            p99: percentile(&latencies, 0.99),
+            failures,
+        });
+    }
+
+    results
+}
+
+/// The value at `pct` (0.0-1.0) in `sorted`, which must already be sorted
+/// ascending. Returns `Duration::ZERO` for an empty slice (all iterations
+/// failed).
+fn percentile(sorted: &[Duration], pct: f64) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+
+    let rank = ((sorted.len() - 1) as f64 * pct).round() as usize;
+    sorted[rank.min(sorted.len() - 1)]
+}
+
+/// Render `results` as a small aligned table for an operator tuning
+/// `EDGE_COLLECTOR_BATCH_SIZE`/`EDGE_COLLECTOR_FLUSH_INTERVAL_SECS`.
+pub fn format_report(results: &[BenchmarkResult]) -> String {
+    let mut out = String::new();
+    out.push_str(&format!(
+        "{:>10} {:>12} {:>10} {:>10} {:>10} {:>10} {:>9}\n",
+        "batch_size", "logs/sec", "p50", "p90", "p99", "logs_sent", "failures"
+    ));
+
+    for r in results {
+        out.push_str(&format!(
+            "{:>10} {:>12.1} {:>10} {:>10} {:>10} {:>10} {:>9}\n",
+            r.batch_size,
+            r.logs_per_sec,
+            format_duration(r.p50),
+            format_duration(r.p90),
+            format_duration(r.p99),
+            r.logs_sent,
+            r.failures,
+        ));
+    }
+
+    out
+}
+
+fn format_duration(d: Duration) -> String {
+    format!("{:.1}ms", d.as_secs_f64() * 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_benchmark_config_default() {
+        let config = BenchmarkConfig::default();
+        assert_eq!(config.batch_sizes, DEFAULT_BATCH_SIZES.to_vec());
+        assert_eq!(config.warmup_iterations, DEFAULT_WARMUP_ITERATIONS);
+        assert_eq!(config.iterations, DEFAULT_ITERATIONS);
+    }
+
+    #[test]
+    fn test_benchmark_config_from_env() {
+        std::env::set_var("EDGE_COLLECTOR_BENCHMARK_BATCH_SIZES", "1, 10,  50");
+        std::env::set_var("EDGE_COLLECTOR_BENCHMARK_WARMUP", "2");
+        std::env::set_var("EDGE_COLLECTOR_BENCHMARK_ITERATIONS", "7");
+
+        let config = BenchmarkConfig::from_env();
+        assert_eq!(config.batch_sizes, vec![1, 10, 50]);
+        assert_eq!(config.warmup_iterations, 2);
+        assert_eq!(config.iterations, 7);
+
+        std::env::remove_var("EDGE_COLLECTOR_BENCHMARK_BATCH_SIZES");
+        std::env::remove_var("EDGE_COLLECTOR_BENCHMARK_WARMUP");
+        std::env::remove_var("EDGE_COLLECTOR_BENCHMARK_ITERATIONS");
+    }
+
+    #[test]
+    fn test_benchmark_config_from_env_falls_back_on_unparseable_sizes() {
+        std::env::set_var("EDGE_COLLECTOR_BENCHMARK_BATCH_SIZES", "not,a,number");
+        let config = BenchmarkConfig::from_env();
+        assert_eq!(config.batch_sizes, DEFAULT_BATCH_SIZES.to_vec());
+        std::env::remove_var("EDGE_COLLECTOR_BENCHMARK_BATCH_SIZES");
+    }
+
+    #[test]
+    fn test_percentile_of_empty_slice_is_zero() {
+        assert_eq!(percentile(&[], 0.50), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_percentile_matches_known_values() {
+        let sorted: Vec<Duration> = (1..=100).map(Duration::from_millis).collect();
+        assert_eq!(percentile(&sorted, 0.50), Duration::from_millis(51));
+        assert_eq!(percentile(&sorted, 0.99), Duration::from_millis(99));
+        assert_eq!(percentile(&sorted, 0.0), Duration::from_millis(1));
+        assert_eq!(percentile(&sorted, 1.0), Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn test_run_benchmark_reports_failures_against_unreachable_backend() {
+        // Port 1 is reserved and refuses connections immediately.
+        let client =
+            LogClient::with_settings("http://127.0.0.1:1/ingest", Duration::from_millis(50), 0).unwrap();
+        let config = BenchmarkConfig { batch_sizes: vec![1], warmup_iterations: 0, iterations: 3 };
+
+        let results = run_benchmark(&client, &config).await;
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].batch_size, 1);
+        assert_eq!(results[0].failures, 3);
+        assert_eq!(results[0].logs_sent, 0);
+    }
+
+    #[test]
+    fn test_format_report_includes_header_and_row() {
+        let results = vec![BenchmarkResult {
+            batch_size: 10,
+            logs_sent: 200,
+            total_duration: Duration::from_secs(1),
+            logs_per_sec: 200.0,
+            p50: Duration::from_millis(5),
+            p90: Duration::from_millis(9),
+            p99: Duration::from_millis(12),
+            failures: 0,
+        }];
+
+        let report = format_report(&results);
+        assert!(report.contains("batch_size"));
+        assert!(report.contains("logs/sec"));
+        assert!(report.contains("10"));
+        assert!(report.contains("200.0"));
+    }
+}
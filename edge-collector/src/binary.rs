@@ -0,0 +1,484 @@
+//! Compact binary wire format for `LogEntry`/`LogBatch`.
+//!
+//! This module implements a length-delimited datagram encoding as an
+//! alternative to JSON for constrained edge links (cellular, LoRa). The
+//! layout is a fixed little-endian header followed by variable-length
+//! fields:
+//!
+//! ```text
+//! i64  timestamp_nanos
+//! u8   level
+//! u32  metadata_count
+//! u16  dropped_bytes
+//! u16  source_id_len | source_id bytes
+//! metadata_count * { u16 key_len | key bytes | u8 value_tag | value }
+//! u16  message_len | message bytes
+//! ```
+//!
+//! Metadata value tags: `0` = null, `1` = bool, `2` = i64, `3` = f64, `4` = string.
+
+use std::collections::HashMap;
+
+use crate::log_generator::{LogBatch, LogEntry, LogLevel};
+
+/// Maximum size of a single encoded datagram, in bytes.
+///
+/// When an entry would exceed this budget, the message is truncated to fit,
+/// a `truncated = true` metadata flag is added, and the number of dropped
+/// message bytes is recorded in the header rather than failing the encode.
+pub const MAX_DATAGRAM_LEN: usize = 2032;
+
+const TAG_NULL: u8 = 0;
+const TAG_BOOL: u8 = 1;
+const TAG_I64: u8 = 2;
+const TAG_F64: u8 = 3;
+const TAG_STR: u8 = 4;
+
+/// Errors that can occur while decoding a datagram.
+#[derive(Debug)]
+pub enum DecodeError {
+    /// The buffer ended before a length-prefixed field could be fully read.
+    UnexpectedEof,
+    /// The `level` byte did not match a known `LogLevel` variant.
+    InvalidLevel(u8),
+    /// A metadata value tag did not match a known encoding.
+    InvalidValueTag(u8),
+    /// A length-prefixed string was not valid UTF-8.
+    InvalidUtf8,
+}
+
+impl std::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodeError::UnexpectedEof => write!(f, "datagram ended before expected field"),
+            DecodeError::InvalidLevel(b) => write!(f, "invalid log level byte: {}", b),
+            DecodeError::InvalidValueTag(b) => write!(f, "invalid metadata value tag: {}", b),
+            DecodeError::InvalidUtf8 => write!(f, "string field was not valid UTF-8"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Cursor over a byte slice used while decoding a datagram.
+struct Reader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    fn remaining(&self) -> usize {
+        self.buf.len() - self.pos
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8], DecodeError> {
+        if self.remaining() < n {
+            return Err(DecodeError::UnexpectedEof);
+        }
+        let slice = &self.buf[self.pos..self.pos + n];
+        self.pos += n;
+        Ok(slice)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, DecodeError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16, DecodeError> {
+        let b = self.take(2)?;
+        Ok(u16::from_le_bytes([b[0], b[1]]))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, DecodeError> {
+        let b = self.take(4)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn read_i64(&mut self) -> Result<i64, DecodeError> {
+        let b = self.take(8)?;
+        Ok(i64::from_le_bytes(b.try_into().unwrap()))
+    }
+
+    fn read_f64(&mut self) -> Result<f64, DecodeError> {
+        let b = self.take(8)?;
+        Ok(f64::from_le_bytes(b.try_into().unwrap()))
+    }
+
+    fn read_string16(&mut self) -> Result<String, DecodeError> {
+        let len = self.read_u16()? as usize;
+        let bytes = self.take(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| DecodeError::InvalidUtf8)
+    }
+}
+
+fn level_to_byte(level: LogLevel) -> u8 {
+    match level {
+        LogLevel::Trace => 0,
+        LogLevel::Debug => 1,
+        LogLevel::Info => 2,
+        LogLevel::Warn => 3,
+        LogLevel::Error => 4,
+        LogLevel::Fatal => 5,
+    }
+}
+
+fn byte_to_level(b: u8) -> Result<LogLevel, DecodeError> {
+    match b {
+        0 => Ok(LogLevel::Trace),
+        1 => Ok(LogLevel::Debug),
+        2 => Ok(LogLevel::Info),
+        3 => Ok(LogLevel::Warn),
+        4 => Ok(LogLevel::Error),
+        5 => Ok(LogLevel::Fatal),
+        other => Err(DecodeError::InvalidLevel(other)),
+    }
+}
+
+fn write_string16(buf: &mut Vec<u8>, s: &str) {
+    let bytes = s.as_bytes();
+    let len = bytes.len().min(u16::MAX as usize) as u16;
+    buf.extend_from_slice(&len.to_le_bytes());
+    buf.extend_from_slice(&bytes[..len as usize]);
+}
+
+fn write_metadata_value(buf: &mut Vec<u8>, value: &serde_json::Value) {
+    match value {
+        serde_json::Value::Null => buf.push(TAG_NULL),
+        serde_json::Value::Bool(b) => {
+            buf.push(TAG_BOOL);
+            buf.push(*b as u8);
+        }
+        serde_json::Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                buf.push(TAG_I64);
+                buf.extend_from_slice(&i.to_le_bytes());
+            } else {
+                buf.push(TAG_F64);
+                buf.extend_from_slice(&n.as_f64().unwrap_or(0.0).to_le_bytes());
+            }
+        }
+        serde_json::Value::String(s) => {
+            buf.push(TAG_STR);
+            write_string16(buf, s);
+        }
+        // Arrays/objects have no compact representation; fall back to their
+        // JSON text so round-tripping degrades gracefully instead of failing.
+        other => {
+            buf.push(TAG_STR);
+            write_string16(buf, &other.to_string());
+        }
+    }
+}
+
+fn read_metadata_value(r: &mut Reader<'_>) -> Result<serde_json::Value, DecodeError> {
+    match r.read_u8()? {
+        TAG_NULL => Ok(serde_json::Value::Null),
+        TAG_BOOL => Ok(serde_json::Value::Bool(r.read_u8()? != 0)),
+        TAG_I64 => Ok(serde_json::Value::Number(r.read_i64()?.into())),
+        TAG_F64 => Ok(serde_json::Number::from_f64(r.read_f64()?)
+            .map(serde_json::Value::Number)
+            .unwrap_or(serde_json::Value::Null)),
+        TAG_STR => Ok(serde_json::Value::String(r.read_string16()?)),
+        other => Err(DecodeError::InvalidValueTag(other)),
+    }
+}
+
+/// Fixed header size: timestamp(8) + level(1) + metadata_count(4) + dropped_bytes(2).
+const HEADER_LEN: usize = 8 + 1 + 4 + 2;
+
+impl LogEntry {
+    /// Encode this entry as a compact binary datagram, appending to `buf`.
+    ///
+    /// If the fully-encoded entry would exceed [`MAX_DATAGRAM_LEN`], the
+    /// message is truncated to fit, a `truncated = true` metadata flag is
+    /// appended, and the number of dropped message bytes is recorded in the
+    /// header instead of failing the encode.
+    pub fn encode_datagram(&self, buf: &mut Vec<u8>) {
+        self.encode_datagram_within(buf, MAX_DATAGRAM_LEN);
+    }
+
+    /// Like [`LogEntry::encode_datagram`], but truncating the message to fit
+    /// `max_len` bytes instead of [`MAX_DATAGRAM_LEN`].
+    fn encode_datagram_within(&self, buf: &mut Vec<u8>, max_len: usize) {
+        let empty = HashMap::new();
+        let metadata = self.metadata.as_ref().unwrap_or(&empty);
+
+        let mut body = Vec::new();
+        for (key, value) in metadata.iter() {
+            write_string16(&mut body, key);
+            write_metadata_value(&mut body, value);
+        }
+
+        let mut source = Vec::new();
+        write_string16(&mut source, &self.source_id);
+
+        let truncated_flag_len = 2 + "truncated".len() + 1 + 1; // key_len+key + tag + bool
+        let message_bytes = self.message.as_bytes();
+        let non_message_len = HEADER_LEN + source.len() + body.len() + 2; // +2 for message len prefix
+
+        let mut dropped_bytes: u16 = 0;
+        let mut truncated = false;
+        let mut message_cut = message_bytes.len();
+
+        if non_message_len + message_bytes.len() > max_len {
+            let budget = max_len
+                .saturating_sub(non_message_len)
+                .saturating_sub(truncated_flag_len);
+            let mut cut = budget.min(message_bytes.len());
+            while cut > 0 && !self.message.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            dropped_bytes = (message_bytes.len() - cut).min(u16::MAX as usize) as u16;
+            message_cut = cut;
+            truncated = true;
+        }
+
+        let metadata_count = metadata.len() as u32 + truncated as u32;
+
+        buf.extend_from_slice(
+            &self
+                .timestamp
+                .timestamp_nanos_opt()
+                .unwrap_or(0)
+                .to_le_bytes(),
+        );
+        buf.push(level_to_byte(self.level));
+        buf.extend_from_slice(&metadata_count.to_le_bytes());
+        buf.extend_from_slice(&dropped_bytes.to_le_bytes());
+        buf.extend_from_slice(&source);
+        buf.extend_from_slice(&body);
+        if truncated {
+            write_string16(buf, "truncated");
+            buf.push(TAG_BOOL);
+            buf.push(1);
+        }
+
+        let msg_len = message_cut.min(u16::MAX as usize) as u16;
+        buf.extend_from_slice(&msg_len.to_le_bytes());
+        buf.extend_from_slice(&message_bytes[..msg_len as usize]);
+    }
+
+    /// Decode a single entry previously written by [`LogEntry::encode_datagram`].
+    ///
+    /// The client-generated `id` is not part of the wire format and is
+    /// always `None` on the decoded entry.
+    pub fn decode_datagram(data: &[u8]) -> Result<LogEntry, DecodeError> {
+        let mut r = Reader::new(data);
+
+        let timestamp_nanos = r.read_i64()?;
+        let level = byte_to_level(r.read_u8()?)?;
+        let metadata_count = r.read_u32()?;
+        let _dropped_bytes = r.read_u16()?;
+        let source_id = r.read_string16()?;
+
+        let mut metadata = HashMap::with_capacity(metadata_count as usize);
+        for _ in 0..metadata_count {
+            let key = r.read_string16()?;
+            let value = read_metadata_value(&mut r)?;
+            metadata.insert(key, value);
+        }
+
+        let message = r.read_string16()?;
+
+        let timestamp = chrono::DateTime::from_timestamp(
+            timestamp_nanos.div_euclid(1_000_000_000),
+            timestamp_nanos.rem_euclid(1_000_000_000) as u32,
+        )
+        .unwrap_or_else(chrono::Utc::now);
+
+        Ok(LogEntry {
+            id: None,
+            timestamp,
+            source_id,
+            level,
+            message,
+            metadata: if metadata.is_empty() {
+                None
+            } else {
+                Some(metadata)
+            },
+        })
+    }
+}
+
+impl LogBatch {
+    /// Split and encode this batch into one or more length-delimited
+    /// datagrams, each no larger than [`MAX_DATAGRAM_LEN`].
+    ///
+    /// Each datagram is a sequence of `u16 length | entry bytes` records,
+    /// packing as many entries as fit before starting a new datagram.
+    pub fn encode_datagrams(&self) -> Vec<Vec<u8>> {
+        let mut datagrams = Vec::new();
+        let mut current = Vec::new();
+
+        for entry in &self.logs {
+            let mut entry_buf = Vec::new();
+            // Leave room for the record's u16 length prefix.
+            entry.encode_datagram_within(&mut entry_buf, MAX_DATAGRAM_LEN - 2);
+
+            let record_len = 2 + entry_buf.len();
+            if !current.is_empty() && current.len() + record_len > MAX_DATAGRAM_LEN {
+                datagrams.push(std::mem::take(&mut current));
+            }
+
+            let len = entry_buf.len().min(u16::MAX as usize) as u16;
+            current.extend_from_slice(&len.to_le_bytes());
+            current.extend_from_slice(&entry_buf);
+        }
+
+        if !current.is_empty() {
+            datagrams.push(current);
+        }
+
+        datagrams
+    }
+
+    /// Decode a single datagram produced by [`LogBatch::encode_datagrams`]
+    /// back into its constituent entries.
+    pub fn decode_datagram(data: &[u8]) -> Result<Vec<LogEntry>, DecodeError> {
+        let mut r = Reader::new(data);
+        let mut entries = Vec::new();
+
+        while r.remaining() > 0 {
+            let len = r.read_u16()? as usize;
+            let entry_bytes = r.take(len)?;
+            entries.push(LogEntry::decode_datagram(entry_bytes)?);
+        }
+
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_roundtrip_entry_without_metadata() {
+        let entry = LogEntry::new("sensor-1", LogLevel::Info, "hello world");
+        let mut buf = Vec::new();
+        entry.encode_datagram(&mut buf);
+
+        let decoded = LogEntry::decode_datagram(&buf).expect("decode should succeed");
+        assert_eq!(decoded.source_id, entry.source_id);
+        assert_eq!(decoded.level, entry.level);
+        assert_eq!(decoded.message, entry.message);
+        assert!(decoded.metadata.is_none());
+    }
+
+    #[test]
+    fn test_roundtrip_entry_with_metadata() {
+        let mut metadata = HashMap::new();
+        metadata.insert("reading".to_string(), serde_json::json!(42.5));
+        metadata.insert("ok".to_string(), serde_json::json!(true));
+        metadata.insert("sequence".to_string(), serde_json::json!(7));
+        metadata.insert("unit".to_string(), serde_json::json!("celsius"));
+
+        let entry = LogEntry::new("sensor-1", LogLevel::Warn, "reading out of range")
+            .with_metadata(metadata);
+
+        let mut buf = Vec::new();
+        entry.encode_datagram(&mut buf);
+        let decoded = LogEntry::decode_datagram(&buf).expect("decode should succeed");
+
+        let meta = decoded.metadata.expect("metadata should survive round trip");
+        assert_eq!(meta.get("reading").unwrap().as_f64().unwrap(), 42.5);
+        assert_eq!(meta.get("ok").unwrap().as_bool().unwrap(), true);
+        assert_eq!(meta.get("sequence").unwrap().as_i64().unwrap(), 7);
+        assert_eq!(meta.get("unit").unwrap().as_str().unwrap(), "celsius");
+    }
+
+    #[test]
+    fn test_oversized_message_is_truncated_not_rejected() {
+        let huge_message = "x".repeat(MAX_DATAGRAM_LEN * 2);
+        let entry = LogEntry::new("sensor-1", LogLevel::Error, huge_message.clone());
+
+        let mut buf = Vec::new();
+        entry.encode_datagram(&mut buf);
+        assert!(buf.len() <= MAX_DATAGRAM_LEN);
+
+        let decoded = LogEntry::decode_datagram(&buf).expect("decode should succeed");
+        assert!(decoded.message.len() < huge_message.len());
+        let meta = decoded.metadata.expect("truncated flag should be set");
+        assert_eq!(meta.get("truncated").unwrap().as_bool().unwrap(), true);
+    }
+
+    #[test]
+    fn test_decode_rejects_invalid_level() {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&0i64.to_le_bytes()); // timestamp
+        buf.push(99); // invalid level
+        buf.extend_from_slice(&0u32.to_le_bytes()); // metadata_count
+        buf.extend_from_slice(&0u16.to_le_bytes()); // dropped_bytes
+        buf.extend_from_slice(&0u16.to_le_bytes()); // source_id len
+        buf.extend_from_slice(&0u16.to_le_bytes()); // message len
+
+        let result = LogEntry::decode_datagram(&buf);
+        assert!(matches!(result, Err(DecodeError::InvalidLevel(99))));
+    }
+
+    #[test]
+    fn test_decode_rejects_truncated_buffer() {
+        let result = LogEntry::decode_datagram(&[0, 1, 2]);
+        assert!(matches!(result, Err(DecodeError::UnexpectedEof)));
+    }
+
+    #[test]
+    fn test_batch_roundtrip_single_datagram() {
+        let entries: Vec<LogEntry> = (0..5)
+            .map(|i| LogEntry::new(format!("sensor-{}", i), LogLevel::Info, "short message"))
+            .collect();
+        let batch = LogBatch::new(entries);
+
+        let datagrams = batch.encode_datagrams();
+        assert_eq!(datagrams.len(), 1);
+
+        let decoded = LogBatch::decode_datagram(&datagrams[0]).unwrap();
+        assert_eq!(decoded.len(), 5);
+    }
+
+    #[test]
+    fn test_batch_splits_across_multiple_datagrams() {
+        let big_message = "m".repeat(900);
+        let entries: Vec<LogEntry> = (0..10)
+            .map(|i| LogEntry::new(format!("sensor-{}", i), LogLevel::Info, big_message.clone()))
+            .collect();
+        let batch = LogBatch::new(entries);
+
+        let datagrams = batch.encode_datagrams();
+        assert!(datagrams.len() > 1, "large entries should split into multiple datagrams");
+
+        for datagram in &datagrams {
+            assert!(datagram.len() <= MAX_DATAGRAM_LEN);
+        }
+
+        let mut total = 0;
+        for datagram in &datagrams {
+            total += LogBatch::decode_datagram(datagram).unwrap().len();
+        }
+        assert_eq!(total, 10);
+    }
+
+    #[test]
+    fn test_batch_encode_empty() {
+        let batch = LogBatch::new(Vec::new());
+        assert!(batch.encode_datagrams().is_empty());
+    }
+
+    #[test]
+    fn test_decode_error_display() {
+        assert_eq!(
+            format!("{}", DecodeError::InvalidLevel(9)),
+            "invalid log level byte: 9"
+        );
+        assert_eq!(
+            format!("{}", DecodeError::UnexpectedEof),
+            "datagram ended before expected field"
+        );
+    }
+}
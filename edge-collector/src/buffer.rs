@@ -3,12 +3,15 @@
 //! This module provides async buffering with size-based and time-based flush triggers
 //! using tokio mpsc channels and select! for concurrent event handling.
 
-use std::time::Duration;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 use tokio::time::interval;
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, info, warn};
 
-use crate::log_generator::{LogBatch, LogEntry};
+use crate::log_generator::{LogBatch, LogEntry, LogLevel};
+use crate::tuner::{AdaptiveTuner, AdaptiveTuningConfig};
 
 /// Maximum buffer capacity to prevent memory issues.
 /// If buffer exceeds this, oldest logs will be dropped.
@@ -17,6 +20,43 @@ const MAX_BUFFER_CAPACITY: usize = 10_000;
 /// Default channel capacity for the mpsc sender/receiver.
 const DEFAULT_CHANNEL_CAPACITY: usize = 1_000;
 
+/// Relative severity of a [`LogLevel`], lowest first, for
+/// [`OverflowPolicy::PriorityDrop`] to rank eviction candidates.
+fn severity_rank(level: LogLevel) -> u8 {
+    match level {
+        LogLevel::Trace => 0,
+        LogLevel::Debug => 1,
+        LogLevel::Info => 2,
+        LogLevel::Warn => 3,
+        LogLevel::Error => 4,
+        LogLevel::Fatal => 5,
+    }
+}
+
+/// What to do with incoming logs once the buffer reaches `max_capacity`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// Drop the oldest 10% of buffered entries to make room (the original,
+    /// and still default, behavior).
+    #[default]
+    DropOldest,
+
+    /// Reject the incoming entry, leaving the buffer untouched.
+    DropNewest,
+
+    /// Never drop: let the buffer grow past `max_capacity`. This trusts the
+    /// bounded mpsc channel (`channel_capacity`) to be the real backpressure
+    /// mechanism — once producers can't enqueue any faster than the buffer
+    /// drains, `BufferSender::send` naturally blocks instead of logs being
+    /// silently discarded.
+    Block,
+
+    /// Evict the lowest-[`LogLevel`] entries first (e.g. `Debug`/`Info`
+    /// before `Warn`/`Error`), so audit-critical logs survive overflow even
+    /// if they arrived earlier than lower-priority ones.
+    PriorityDrop,
+}
+
 /// Configuration for the log buffer.
 #[derive(Debug, Clone)]
 pub struct BufferConfig {
@@ -31,6 +71,18 @@ pub struct BufferConfig {
 
     /// Capacity of the mpsc channel
     pub channel_capacity: usize,
+
+    /// Flush once the buffer's estimated JSON size reaches this many bytes,
+    /// even if `batch_size` hasn't been reached. `None` disables the trigger.
+    pub max_batch_bytes: Option<usize>,
+
+    /// Auto-tune `batch_size`/`flush_interval` toward observed flush latency
+    /// (see [`crate::tuner::AdaptiveTuner`]). `None` disables tuning and keeps
+    /// `batch_size`/`flush_interval` fixed, as before.
+    pub adaptive: Option<AdaptiveTuningConfig>,
+
+    /// What to do with incoming logs once the buffer reaches `max_capacity`.
+    pub overflow_policy: OverflowPolicy,
 }
 
 impl Default for BufferConfig {
@@ -40,6 +92,9 @@ impl Default for BufferConfig {
             flush_interval: Duration::from_secs(5),
             max_capacity: MAX_BUFFER_CAPACITY,
             channel_capacity: DEFAULT_CHANNEL_CAPACITY,
+            max_batch_bytes: None,
+            adaptive: None,
+            overflow_policy: OverflowPolicy::default(),
         }
     }
 }
@@ -52,8 +107,31 @@ impl BufferConfig {
             flush_interval,
             max_capacity: MAX_BUFFER_CAPACITY,
             channel_capacity: DEFAULT_CHANNEL_CAPACITY,
+            max_batch_bytes: None,
+            adaptive: None,
+            overflow_policy: OverflowPolicy::default(),
         }
     }
+
+    /// Enable the byte-budget flush trigger alongside the count-based one.
+    pub fn with_max_batch_bytes(mut self, max_batch_bytes: usize) -> Self {
+        self.max_batch_bytes = Some(max_batch_bytes);
+        self
+    }
+
+    /// Enable adaptive batch-size/flush-interval tuning, starting from this
+    /// config's `batch_size`/`flush_interval` and adjusting them toward
+    /// `tuning`'s target flush latency as flushes are reported.
+    pub fn with_adaptive_tuning(mut self, tuning: AdaptiveTuningConfig) -> Self {
+        self.adaptive = Some(tuning);
+        self
+    }
+
+    /// Override the default drop-oldest overflow policy.
+    pub fn with_overflow_policy(mut self, overflow_policy: OverflowPolicy) -> Self {
+        self.overflow_policy = overflow_policy;
+        self
+    }
 }
 
 /// Statistics about buffer operations.
@@ -65,14 +143,27 @@ pub struct BufferStats {
     /// Total number of logs flushed (sent in batches)
     pub logs_flushed: u64,
 
-    /// Total number of logs dropped due to buffer overflow
+    /// Total number of logs dropped due to buffer overflow, across all
+    /// policies
     pub logs_dropped: u64,
 
+    /// Number of logs dropped by [`OverflowPolicy::DropOldest`]
+    pub logs_dropped_oldest: u64,
+
+    /// Number of logs rejected by [`OverflowPolicy::DropNewest`]
+    pub logs_dropped_newest: u64,
+
+    /// Number of logs evicted by [`OverflowPolicy::PriorityDrop`]
+    pub logs_dropped_priority: u64,
+
     /// Number of flush operations triggered by size threshold
     pub size_flushes: u64,
 
     /// Number of flush operations triggered by time interval
     pub time_flushes: u64,
+
+    /// Number of flush operations triggered by the byte-budget threshold
+    pub byte_flushes: u64,
 }
 
 /// Result of a flush operation.
@@ -94,6 +185,15 @@ pub struct BufferSender {
 }
 
 impl BufferSender {
+    /// Wrap a raw channel sender as a `BufferSender`.
+    ///
+    /// This lets other consumers of a plain `LogEntry` channel (e.g.
+    /// [`crate::multi_stream::MultiStreamBuffer`]) reuse the same sender type
+    /// and error handling as [`LogBuffer`].
+    pub(crate) fn from_mpsc_sender(tx: mpsc::Sender<LogEntry>) -> Self {
+        Self { tx }
+    }
+
     /// Send a log entry to the buffer.
     ///
     /// This is an async operation that will wait if the channel is full.
@@ -178,11 +278,22 @@ pub struct LogBuffer {
     /// Internal buffer for accumulating logs
     buffer: Vec<LogEntry>,
 
+    /// Estimated total JSON size (in bytes) of the entries currently buffered
+    buffer_bytes: usize,
+
+    /// Entries that arrived while the buffer was already at
+    /// `config.max_batch_bytes`, held here to start the *next* batch instead
+    /// of growing the current one past its byte budget.
+    carry: VecDeque<LogEntry>,
+
     /// Configuration for the buffer
     config: BufferConfig,
 
     /// Statistics about buffer operations
     stats: BufferStats,
+
+    /// Adaptive batch-size/flush-interval tuner, if enabled via `config.adaptive`
+    tuner: Option<AdaptiveTuner>,
 }
 
 impl LogBuffer {
@@ -193,11 +304,19 @@ impl LogBuffer {
     pub fn new(config: BufferConfig) -> (BufferSender, Self) {
         let (tx, rx) = mpsc::channel(config.channel_capacity);
 
+        let tuner = config
+            .adaptive
+            .clone()
+            .map(|tuning| AdaptiveTuner::new(tuning, config.batch_size, config.flush_interval));
+
         let buffer = Self {
             rx,
             buffer: Vec::with_capacity(config.batch_size),
+            buffer_bytes: 0,
+            carry: VecDeque::new(),
             config,
             stats: BufferStats::default(),
+            tuner,
         };
 
         let sender = BufferSender { tx };
@@ -224,33 +343,43 @@ impl LogBuffer {
 
         loop {
             tokio::select! {
-                // Handle incoming log entries
-                maybe_entry = self.rx.recv() => {
-                    match maybe_entry {
-                        Some(entry) => {
-                            self.add_entry(entry);
-
-                            // Check if we've reached batch size
-                            if self.buffer.len() >= self.config.batch_size {
-                                self.stats.size_flushes += 1;
-                                debug!(
-                                    batch_size = self.buffer.len(),
-                                    "Flushing buffer: batch size threshold reached"
-                                );
-                                return Some(self.create_batch());
-                            }
+                // Drain every entry already queued on the channel in one
+                // call, rather than waking up once per entry.
+                drained = self.drain_channel() => {
+                    if drained == 0 {
+                        // Channel closed, flush remaining buffer
+                        if !self.buffer.is_empty() {
+                            info!(
+                                remaining = self.buffer.len(),
+                                "Channel closed, flushing remaining logs"
+                            );
+                            return Some(self.create_batch());
                         }
-                        None => {
-                            // Channel closed, flush remaining buffer
-                            if !self.buffer.is_empty() {
-                                info!(
-                                    remaining = self.buffer.len(),
-                                    "Channel closed, flushing remaining logs"
-                                );
-                                return Some(self.create_batch());
-                            }
-                            return None;
+                        return None;
+                    }
+
+                    // Check if we've reached batch size, or the latest entry
+                    // was cut before pushing because it would have blown the
+                    // byte budget (and is now waiting in `self.carry`).
+                    let size_reached = self.buffer.len() >= self.config.batch_size;
+                    let bytes_reached = !self.carry.is_empty();
+
+                    if size_reached || bytes_reached {
+                        if size_reached {
+                            self.stats.size_flushes += 1;
+                            debug!(
+                                batch_size = self.buffer.len(),
+                                "Flushing buffer: batch size threshold reached"
+                            );
+                        } else {
+                            self.stats.byte_flushes += 1;
+                            debug!(
+                                batch_size = self.buffer.len(),
+                                buffer_bytes = self.buffer_bytes,
+                                "Flushing buffer: byte budget threshold reached"
+                            );
                         }
+                        return Some(self.create_batch());
                     }
                 }
 
@@ -270,27 +399,214 @@ impl LogBuffer {
         }
     }
 
-    /// Add a log entry to the buffer, handling overflow if necessary.
+    /// Drain every entry currently queued on the channel into `self.buffer`
+    /// in one call, bounded so the buffer never overshoots `batch_size`.
+    ///
+    /// If a previous call deferred entries into `self.carry` because they
+    /// would have pushed the batch past `max_batch_bytes`, those are admitted
+    /// first (in order) before anything new is pulled off the channel.
+    ///
+    /// Waits for at least one entry to arrive (or the channel to close)
+    /// before returning, the same as a single `rx.recv()` would, but then
+    /// takes everything else already queued without an extra wake-up per
+    /// entry. Returns the number of entries drained, or `0` only when the
+    /// channel is closed and empty.
+    async fn drain_channel(&mut self) -> usize {
+        let limit = self
+            .config
+            .batch_size
+            .saturating_sub(self.buffer.len())
+            .max(1);
+
+        let mut admitted = 0;
+        while admitted < limit {
+            let Some(entry) = self.carry.pop_front() else {
+                break;
+            };
+            let carried = self.carry.len();
+            self.add_entry(entry);
+            if self.carry.len() > carried {
+                // Deferred again: this batch is already at its byte budget.
+                break;
+            }
+            admitted += 1;
+        }
+        if admitted > 0 {
+            return admitted;
+        }
+
+        let mut drained = Vec::with_capacity(limit);
+        let n = self.rx.recv_many(&mut drained, limit).await;
+
+        for entry in drained {
+            self.add_entry(entry);
+        }
+
+        n
+    }
+
+    /// Like [`LogBuffer::next_batch`], but also watches `token` for
+    /// cancellation.
+    ///
+    /// When `token` is cancelled, the buffer stops waiting for new entries,
+    /// takes whatever is already queued on the channel, flushes everything
+    /// that remains as one final batch (or returns `None` if there was
+    /// nothing), and returns — so a caller driving this in a shutdown path
+    /// never drops in-flight logs the way aborting the task would.
+    pub async fn next_batch_until(&mut self, token: CancellationToken) -> Option<LogBatch> {
+        let mut ticker = interval(self.config.flush_interval);
+        // Skip the first immediate tick
+        ticker.tick().await;
+
+        loop {
+            tokio::select! {
+                drained = self.drain_channel() => {
+                    if drained == 0 {
+                        // Channel closed, flush remaining buffer
+                        if !self.buffer.is_empty() {
+                            info!(
+                                remaining = self.buffer.len(),
+                                "Channel closed, flushing remaining logs"
+                            );
+                            return Some(self.create_batch());
+                        }
+                        return None;
+                    }
+
+                    let size_reached = self.buffer.len() >= self.config.batch_size;
+                    let bytes_reached = !self.carry.is_empty();
+
+                    if size_reached || bytes_reached {
+                        if size_reached {
+                            self.stats.size_flushes += 1;
+                            debug!(
+                                batch_size = self.buffer.len(),
+                                "Flushing buffer: batch size threshold reached"
+                            );
+                        } else {
+                            self.stats.byte_flushes += 1;
+                            debug!(
+                                batch_size = self.buffer.len(),
+                                buffer_bytes = self.buffer_bytes,
+                                "Flushing buffer: byte budget threshold reached"
+                            );
+                        }
+                        return Some(self.create_batch());
+                    }
+                }
+
+                _ = ticker.tick() => {
+                    if !self.buffer.is_empty() {
+                        self.stats.time_flushes += 1;
+                        debug!(
+                            batch_size = self.buffer.len(),
+                            interval_secs = self.config.flush_interval.as_secs(),
+                            "Flushing buffer: time interval elapsed"
+                        );
+                        return Some(self.create_batch());
+                    }
+                }
+
+                _ = token.cancelled() => {
+                    info!("Shutdown requested, flushing remaining logs");
+                    // Anything still waiting in `carry` was only deferred to
+                    // respect the byte budget; on a final flush there's no
+                    // next batch for it to start, so fold it in directly.
+                    while let Some(entry) = self.carry.pop_front() {
+                        self.buffer_bytes += entry.estimated_size();
+                        self.buffer.push(entry);
+                    }
+                    // Entries already queued on the channel were sent before
+                    // shutdown, so they belong in the final batch too.
+                    while let Ok(entry) = self.rx.try_recv() {
+                        self.stats.logs_received += 1;
+                        self.buffer_bytes += entry.estimated_size();
+                        self.buffer.push(entry);
+                    }
+                    return self.flush();
+                }
+            }
+        }
+    }
+
+    /// Add a log entry to the buffer, applying `config.overflow_policy` if
+    /// the buffer is already at `max_capacity`.
+    ///
+    /// If `config.max_batch_bytes` is set and adding `entry` would push the
+    /// buffer's estimated size past it, `entry` is held in `self.carry`
+    /// instead, so it starts the *next* batch rather than growing this one
+    /// past its byte budget.
     fn add_entry(&mut self, entry: LogEntry) {
+        if let Some(max) = self.config.max_batch_bytes {
+            if !self.buffer.is_empty() && self.buffer_bytes + entry.estimated_size() > max {
+                self.carry.push_back(entry);
+                return;
+            }
+        }
+
         self.stats.logs_received += 1;
 
-        // Check for buffer overflow
         if self.buffer.len() >= self.config.max_capacity {
-            // Drop oldest entries to make room
-            let drop_count = self.buffer.len() / 10; // Drop 10% to avoid frequent drops
-            let drop_count = drop_count.max(1);
-
-            warn!(
-                buffer_size = self.buffer.len(),
-                drop_count = drop_count,
-                max_capacity = self.config.max_capacity,
-                "Buffer overflow: dropping oldest logs"
-            );
+            match self.config.overflow_policy {
+                OverflowPolicy::DropOldest => {
+                    let drop_count = (self.buffer.len() / 10).max(1);
+                    warn!(
+                        buffer_size = self.buffer.len(),
+                        drop_count = drop_count,
+                        max_capacity = self.config.max_capacity,
+                        "Buffer overflow: dropping oldest logs"
+                    );
+
+                    let dropped_bytes: usize = self.buffer[0..drop_count]
+                        .iter()
+                        .map(LogEntry::estimated_size)
+                        .sum();
+                    self.buffer.drain(0..drop_count);
+                    self.buffer_bytes = self.buffer_bytes.saturating_sub(dropped_bytes);
+                    self.stats.logs_dropped += drop_count as u64;
+                    self.stats.logs_dropped_oldest += drop_count as u64;
+                }
+                OverflowPolicy::DropNewest => {
+                    warn!(
+                        buffer_size = self.buffer.len(),
+                        max_capacity = self.config.max_capacity,
+                        "Buffer overflow: rejecting incoming log"
+                    );
+                    self.stats.logs_dropped += 1;
+                    self.stats.logs_dropped_newest += 1;
+                    return;
+                }
+                OverflowPolicy::Block => {
+                    // No internal drop: the bounded mpsc channel is the real
+                    // backpressure mechanism, so just let the buffer grow.
+                }
+                OverflowPolicy::PriorityDrop => {
+                    let drop_count = (self.buffer.len() / 10).max(1);
+                    let mut indices: Vec<usize> = (0..self.buffer.len()).collect();
+                    indices.sort_by_key(|&i| severity_rank(self.buffer[i].level));
+
+                    let mut to_drop: Vec<usize> = indices.into_iter().take(drop_count).collect();
+                    to_drop.sort_unstable_by(|a, b| b.cmp(a)); // descending, so removal doesn't shift earlier indices
 
-            self.buffer.drain(0..drop_count);
-            self.stats.logs_dropped += drop_count as u64;
+                    warn!(
+                        buffer_size = self.buffer.len(),
+                        drop_count = drop_count,
+                        max_capacity = self.config.max_capacity,
+                        "Buffer overflow: dropping lowest-severity logs"
+                    );
+
+                    let mut dropped_bytes = 0usize;
+                    for idx in to_drop {
+                        dropped_bytes += self.buffer.remove(idx).estimated_size();
+                    }
+                    self.buffer_bytes = self.buffer_bytes.saturating_sub(dropped_bytes);
+                    self.stats.logs_dropped += drop_count as u64;
+                    self.stats.logs_dropped_priority += drop_count as u64;
+                }
+            }
         }
 
+        self.buffer_bytes += entry.estimated_size();
         self.buffer.push(entry);
     }
 
@@ -301,6 +617,7 @@ impl LogBuffer {
 
         // Re-allocate with capacity for efficiency
         self.buffer = Vec::with_capacity(self.config.batch_size);
+        self.buffer_bytes = 0;
 
         LogBatch::new(logs)
     }
@@ -315,6 +632,11 @@ impl LogBuffer {
         self.buffer.is_empty()
     }
 
+    /// Get the estimated JSON size (in bytes) of the currently buffered logs.
+    pub fn buffer_bytes(&self) -> usize {
+        self.buffer_bytes
+    }
+
     /// Get current buffer statistics.
     pub fn stats(&self) -> &BufferStats {
         &self.stats
@@ -339,6 +661,20 @@ impl LogBuffer {
             Some(self.create_batch())
         }
     }
+
+    /// Report how long the most recently flushed batch took to send.
+    ///
+    /// If adaptive tuning is enabled (via `BufferConfig::with_adaptive_tuning`),
+    /// this nudges `batch_size`/`flush_interval` toward the configured target
+    /// latency; the new values take effect starting with the next
+    /// `next_batch` call. A no-op if adaptive tuning isn't enabled.
+    pub fn record_flush_outcome(&mut self, elapsed: Duration) {
+        if let Some(tuner) = &mut self.tuner {
+            tuner.record_flush(elapsed);
+            self.config.batch_size = tuner.batch_size();
+            self.config.flush_interval = tuner.flush_interval();
+        }
+    }
 }
 
 /// A standalone buffer task that can be spawned as a tokio task.
@@ -352,11 +688,14 @@ impl LogBuffer {
 /// * `batch_size` - Number of logs to accumulate before flushing
 /// * `flush_interval` - Duration to wait before flushing even if batch size not reached
 /// * `on_flush` - Async callback function to handle flushed batches
+/// * `overflow_policy` - What to do once the buffer reaches `MAX_BUFFER_CAPACITY`
+/// * `max_batch_bytes` - Flush once the buffer's estimated JSON size reaches this
+///   many bytes, even if `batch_size` hasn't been reached. `None` disables the trigger.
 ///
 /// # Example
 ///
 /// ```no_run
-/// use edge_collector::buffer::{buffer_task, BufferConfig};
+/// use edge_collector::buffer::{buffer_task, BufferConfig, OverflowPolicy};
 /// use edge_collector::log_generator::LogBatch;
 /// use tokio::sync::mpsc;
 ///
@@ -375,6 +714,8 @@ impl LogBuffer {
 ///                 println!("Flushing {} logs", batch.len());
 ///                 Ok(())
 ///             },
+///             OverflowPolicy::default(),
+///             config.max_batch_bytes,
 ///         ).await;
 ///     });
 /// }
@@ -384,11 +725,18 @@ pub async fn buffer_task<F, Fut>(
     batch_size: usize,
     flush_interval: Duration,
     on_flush: F,
+    overflow_policy: OverflowPolicy,
+    max_batch_bytes: Option<usize>,
 ) where
     F: Fn(LogBatch) -> Fut,
     Fut: std::future::Future<Output = Result<(), Box<dyn std::error::Error + Send + Sync>>>,
 {
     let mut buffer: Vec<LogEntry> = Vec::with_capacity(batch_size);
+    let mut buffer_bytes: usize = 0;
+    // Entries deferred because they would have pushed the buffer past
+    // `max_batch_bytes`; replayed into the buffer right after the flush they
+    // triggered, so they start the next batch instead of growing this one.
+    let mut carry: VecDeque<LogEntry> = VecDeque::new();
     let mut ticker = interval(flush_interval);
     let mut logs_dropped: u64 = 0;
 
@@ -398,31 +746,296 @@ pub async fn buffer_task<F, Fut>(
     loop {
         tokio::select! {
             Some(log) = rx.recv() => {
-                // Handle buffer overflow
-                if buffer.len() >= MAX_BUFFER_CAPACITY {
-                    let drop_count = buffer.len() / 10;
-                    let drop_count = drop_count.max(1);
-                    warn!(
-                        buffer_size = buffer.len(),
-                        drop_count = drop_count,
-                        "Buffer overflow: dropping oldest logs"
-                    );
-                    buffer.drain(0..drop_count);
-                    logs_dropped += drop_count as u64;
+                let mut deferred = false;
+                if let Some(max) = max_batch_bytes {
+                    if !buffer.is_empty() && buffer_bytes + log.estimated_size() > max {
+                        carry.push_back(log);
+                        deferred = true;
+                    }
+                }
+
+                if !deferred {
+                    // Handle buffer overflow
+                    if buffer.len() >= MAX_BUFFER_CAPACITY {
+                        match overflow_policy {
+                            OverflowPolicy::DropOldest => {
+                                let drop_count = (buffer.len() / 10).max(1);
+                                warn!(
+                                    buffer_size = buffer.len(),
+                                    drop_count = drop_count,
+                                    "Buffer overflow: dropping oldest logs"
+                                );
+                                let dropped_bytes: usize = buffer[0..drop_count]
+                                    .iter()
+                                    .map(LogEntry::estimated_size)
+                                    .sum();
+                                buffer.drain(0..drop_count);
+                                buffer_bytes = buffer_bytes.saturating_sub(dropped_bytes);
+                                logs_dropped += drop_count as u64;
+                            }
+                            OverflowPolicy::DropNewest => {
+                                warn!(buffer_size = buffer.len(), "Buffer overflow: rejecting incoming log");
+                                logs_dropped += 1;
+                                continue;
+                            }
+                            OverflowPolicy::Block => {
+                                // No internal drop: trust the bounded channel for backpressure.
+                            }
+                            OverflowPolicy::PriorityDrop => {
+                                let drop_count = (buffer.len() / 10).max(1);
+                                let mut indices: Vec<usize> = (0..buffer.len()).collect();
+                                indices.sort_by_key(|&i| severity_rank(buffer[i].level));
+                                let mut to_drop: Vec<usize> = indices.into_iter().take(drop_count).collect();
+                                to_drop.sort_unstable_by(|a, b| b.cmp(a));
+                                warn!(
+                                    buffer_size = buffer.len(),
+                                    drop_count = drop_count,
+                                    "Buffer overflow: dropping lowest-severity logs"
+                                );
+                                let mut dropped_bytes = 0usize;
+                                for idx in to_drop {
+                                    dropped_bytes += buffer.remove(idx).estimated_size();
+                                }
+                                buffer_bytes = buffer_bytes.saturating_sub(dropped_bytes);
+                                logs_dropped += drop_count as u64;
+                            }
+                        }
+                    }
+
+                    buffer_bytes += log.estimated_size();
+                    buffer.push(log);
+                }
+
+                // Flush if batch size reached, or an entry had to be deferred
+                // to respect the byte budget
+                let size_reached = buffer.len() >= batch_size;
+                let bytes_reached = !carry.is_empty();
+                if size_reached || bytes_reached {
+                    if size_reached {
+                        debug!(batch_size = buffer.len(), "Size-based flush triggered");
+                    } else {
+                        debug!(batch_size = buffer.len(), buffer_bytes = buffer_bytes, "Byte-budget flush triggered");
+                    }
+                    let logs = std::mem::take(&mut buffer);
+                    buffer = Vec::with_capacity(batch_size);
+                    buffer_bytes = 0;
+                    let batch = LogBatch::new(logs);
+
+                    if let Err(e) = on_flush(batch).await {
+                        warn!(error = %e, "Failed to flush batch");
+                    }
+
+                    // Admit carried-over entries into the now-empty buffer so
+                    // they start the next batch, without waiting for another
+                    // channel wake-up.
+                    while buffer.len() < batch_size {
+                        let Some(entry) = carry.pop_front() else { break };
+                        if let Some(max) = max_batch_bytes {
+                            if !buffer.is_empty() && buffer_bytes + entry.estimated_size() > max {
+                                carry.push_front(entry);
+                                break;
+                            }
+                        }
+                        buffer_bytes += entry.estimated_size();
+                        buffer.push(entry);
+                    }
+                }
+            }
+
+            _ = ticker.tick() => {
+                // Time-based flush if buffer not empty
+                if !buffer.is_empty() {
+                    debug!(batch_size = buffer.len(), "Time-based flush triggered");
+                    let logs = std::mem::take(&mut buffer);
+                    buffer = Vec::with_capacity(batch_size);
+                    buffer_bytes = 0;
+                    let batch = LogBatch::new(logs);
+
+                    if let Err(e) = on_flush(batch).await {
+                        warn!(error = %e, "Failed to flush batch");
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Like [`buffer_task`], but stops cleanly and flushes any remaining logs
+/// (including those already queued on the channel) when `token` is
+/// cancelled, instead of relying on the caller aborting the task.
+///
+/// `adaptive`, if set, re-tunes `batch_size`/`flush_interval` toward the
+/// configured target flush latency after every flush (see
+/// [`crate::tuner::AdaptiveTuner`]); `None` keeps both fixed, as before.
+///
+/// # Example
+///
+/// ```no_run
+/// use edge_collector::buffer::{buffer_task_with_shutdown, BufferConfig, OverflowPolicy};
+/// use edge_collector::log_generator::LogBatch;
+/// use tokio::sync::mpsc;
+/// use tokio_util::sync::CancellationToken;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let config = BufferConfig::default();
+///     let (tx, rx) = mpsc::channel(1000);
+///     let token = CancellationToken::new();
+///
+///     let shutdown = token.clone();
+///     tokio::spawn(async move {
+///         buffer_task_with_shutdown(
+///             rx,
+///             config.batch_size,
+///             config.flush_interval,
+///             |batch: LogBatch| async move {
+///                 println!("Flushing {} logs", batch.len());
+///                 Ok(())
+///             },
+///             shutdown,
+///             OverflowPolicy::default(),
+///             config.max_batch_bytes,
+///             config.adaptive,
+///         ).await;
+///     });
+///
+///     // On SIGTERM:
+///     token.cancel();
+/// }
+/// ```
+pub async fn buffer_task_with_shutdown<F, Fut>(
+    mut rx: mpsc::Receiver<LogEntry>,
+    mut batch_size: usize,
+    mut flush_interval: Duration,
+    on_flush: F,
+    token: CancellationToken,
+    overflow_policy: OverflowPolicy,
+    max_batch_bytes: Option<usize>,
+    adaptive: Option<AdaptiveTuningConfig>,
+) where
+    F: Fn(LogBatch) -> Fut,
+    Fut: std::future::Future<Output = Result<(), Box<dyn std::error::Error + Send + Sync>>>,
+{
+    let mut buffer: Vec<LogEntry> = Vec::with_capacity(batch_size);
+    let mut buffer_bytes: usize = 0;
+    // Entries deferred because they would have pushed the buffer past
+    // `max_batch_bytes`; replayed into the buffer right after the flush they
+    // triggered, so they start the next batch instead of growing this one.
+    let mut carry: VecDeque<LogEntry> = VecDeque::new();
+    let mut ticker = interval(flush_interval);
+    let mut logs_dropped: u64 = 0;
+    let mut tuner = adaptive.map(|cfg| AdaptiveTuner::new(cfg, batch_size, flush_interval));
+
+    // Skip the first immediate tick
+    ticker.tick().await;
+
+    loop {
+        tokio::select! {
+            Some(log) = rx.recv() => {
+                let mut deferred = false;
+                if let Some(max) = max_batch_bytes {
+                    if !buffer.is_empty() && buffer_bytes + log.estimated_size() > max {
+                        carry.push_back(log);
+                        deferred = true;
+                    }
                 }
 
-                buffer.push(log);
+                if !deferred {
+                    // Handle buffer overflow
+                    if buffer.len() >= MAX_BUFFER_CAPACITY {
+                        match overflow_policy {
+                            OverflowPolicy::DropOldest => {
+                                let drop_count = (buffer.len() / 10).max(1);
+                                warn!(
+                                    buffer_size = buffer.len(),
+                                    drop_count = drop_count,
+                                    "Buffer overflow: dropping oldest logs"
+                                );
+                                let dropped_bytes: usize = buffer[0..drop_count]
+                                    .iter()
+                                    .map(LogEntry::estimated_size)
+                                    .sum();
+                                buffer.drain(0..drop_count);
+                                buffer_bytes = buffer_bytes.saturating_sub(dropped_bytes);
+                                logs_dropped += drop_count as u64;
+                            }
+                            OverflowPolicy::DropNewest => {
+                                warn!(buffer_size = buffer.len(), "Buffer overflow: rejecting incoming log");
+                                logs_dropped += 1;
+                                continue;
+                            }
+                            OverflowPolicy::Block => {
+                                // No internal drop: trust the bounded channel for backpressure.
+                            }
+                            OverflowPolicy::PriorityDrop => {
+                                let drop_count = (buffer.len() / 10).max(1);
+                                let mut indices: Vec<usize> = (0..buffer.len()).collect();
+                                indices.sort_by_key(|&i| severity_rank(buffer[i].level));
+                                let mut to_drop: Vec<usize> = indices.into_iter().take(drop_count).collect();
+                                to_drop.sort_unstable_by(|a, b| b.cmp(a));
+                                warn!(
+                                    buffer_size = buffer.len(),
+                                    drop_count = drop_count,
+                                    "Buffer overflow: dropping lowest-severity logs"
+                                );
+                                let mut dropped_bytes = 0usize;
+                                for idx in to_drop {
+                                    dropped_bytes += buffer.remove(idx).estimated_size();
+                                }
+                                buffer_bytes = buffer_bytes.saturating_sub(dropped_bytes);
+                                logs_dropped += drop_count as u64;
+                            }
+                        }
+                    }
 
-                // Flush if batch size reached
-                if buffer.len() >= batch_size {
-                    debug!(batch_size = buffer.len(), "Size-based flush triggered");
+                    buffer_bytes += log.estimated_size();
+                    buffer.push(log);
+                }
+
+                // Flush if batch size reached, or an entry had to be deferred
+                // to respect the byte budget
+                let size_reached = buffer.len() >= batch_size;
+                let bytes_reached = !carry.is_empty();
+                if size_reached || bytes_reached {
+                    if size_reached {
+                        debug!(batch_size = buffer.len(), "Size-based flush triggered");
+                    } else {
+                        debug!(batch_size = buffer.len(), buffer_bytes = buffer_bytes, "Byte-budget flush triggered");
+                    }
                     let logs = std::mem::take(&mut buffer);
                     buffer = Vec::with_capacity(batch_size);
+                    buffer_bytes = 0;
                     let batch = LogBatch::new(logs);
 
+                    let flush_start = Instant::now();
                     if let Err(e) = on_flush(batch).await {
                         warn!(error = %e, "Failed to flush batch");
                     }
+                    if let Some(t) = tuner.as_mut() {
+                        t.record_flush(flush_start.elapsed());
+                        batch_size = t.batch_size();
+                        let new_interval = t.flush_interval();
+                        if new_interval != flush_interval {
+                            flush_interval = new_interval;
+                            ticker = interval(flush_interval);
+                            ticker.tick().await;
+                        }
+                    }
+
+                    // Admit carried-over entries into the now-empty buffer so
+                    // they start the next batch, without waiting for another
+                    // channel wake-up.
+                    while buffer.len() < batch_size {
+                        let Some(entry) = carry.pop_front() else { break };
+                        if let Some(max) = max_batch_bytes {
+                            if !buffer.is_empty() && buffer_bytes + entry.estimated_size() > max {
+                                carry.push_front(entry);
+                                break;
+                            }
+                        }
+                        buffer_bytes += entry.estimated_size();
+                        buffer.push(entry);
+                    }
                 }
             }
 
@@ -432,12 +1045,46 @@ pub async fn buffer_task<F, Fut>(
                     debug!(batch_size = buffer.len(), "Time-based flush triggered");
                     let logs = std::mem::take(&mut buffer);
                     buffer = Vec::with_capacity(batch_size);
+                    buffer_bytes = 0;
                     let batch = LogBatch::new(logs);
 
+                    let flush_start = Instant::now();
                     if let Err(e) = on_flush(batch).await {
                         warn!(error = %e, "Failed to flush batch");
                     }
+                    if let Some(t) = tuner.as_mut() {
+                        t.record_flush(flush_start.elapsed());
+                        batch_size = t.batch_size();
+                        let new_interval = t.flush_interval();
+                        if new_interval != flush_interval {
+                            flush_interval = new_interval;
+                            ticker = interval(flush_interval);
+                            ticker.tick().await;
+                        }
+                    }
+                }
+            }
+
+            _ = token.cancelled() => {
+                info!(
+                    logs_dropped = logs_dropped,
+                    "Shutdown requested, flushing remaining logs"
+                );
+                // Fold anything still deferred in `carry` into the final
+                // batch — there's no next batch for it to start.
+                buffer.extend(carry.drain(..));
+                // Entries already queued on the channel were sent before
+                // shutdown, so they belong in the final batch too.
+                while let Ok(log) = rx.try_recv() {
+                    buffer.push(log);
+                }
+                if !buffer.is_empty() {
+                    let batch = LogBatch::new(buffer);
+                    if let Err(e) = on_flush(batch).await {
+                        warn!(error = %e, "Failed to flush batch on shutdown");
+                    }
                 }
+                return;
             }
         }
     }
@@ -461,6 +1108,70 @@ mod tests {
         assert_eq!(config.batch_size, 100);
         assert_eq!(config.flush_interval, Duration::from_secs(5));
         assert_eq!(config.max_capacity, MAX_BUFFER_CAPACITY);
+        assert_eq!(config.max_batch_bytes, None);
+        assert!(config.adaptive.is_none());
+        assert_eq!(config.overflow_policy, OverflowPolicy::DropOldest);
+    }
+
+    #[tokio::test]
+    async fn test_buffer_config_with_max_batch_bytes() {
+        let config = BufferConfig::new(100, Duration::from_secs(5)).with_max_batch_bytes(4096);
+        assert_eq!(config.max_batch_bytes, Some(4096));
+    }
+
+    #[tokio::test]
+    async fn test_buffer_byte_budget_flush() {
+        // A single test entry serializes to well over 100 bytes; cap the
+        // budget low enough that a couple of entries trips it long before
+        // the (very high) count threshold would.
+        let config = BufferConfig::new(1_000, Duration::from_secs(60)).with_max_batch_bytes(200);
+        let (sender, mut buffer) = LogBuffer::new(config);
+
+        sender.send(create_test_entry()).await.unwrap();
+        sender.send(create_test_entry()).await.unwrap();
+
+        let result = timeout(Duration::from_millis(100), buffer.next_batch()).await;
+        let batch = result.expect("should complete quickly").expect("should get batch");
+
+        assert!(batch.len() <= 2);
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.buffer_bytes(), 0);
+        assert_eq!(buffer.stats().byte_flushes, 1);
+        assert_eq!(buffer.stats().size_flushes, 0);
+    }
+
+    #[tokio::test]
+    async fn test_buffer_byte_budget_carries_cut_entry_to_next_batch() {
+        // Same budget as above: each entry is over half the budget, so every
+        // batch holds one entry and each cut entry should start the next
+        // batch rather than being dropped or silently double-counted.
+        let config = BufferConfig::new(1_000, Duration::from_secs(60)).with_max_batch_bytes(200);
+        let (sender, mut buffer) = LogBuffer::new(config);
+
+        sender.send(create_test_entry()).await.unwrap();
+        sender.send(create_test_entry()).await.unwrap();
+        sender.send(create_test_entry()).await.unwrap();
+        drop(sender); // lets the last carried entry flush on channel close
+
+        let first = timeout(Duration::from_millis(100), buffer.next_batch())
+            .await
+            .expect("should complete quickly")
+            .expect("should get a batch");
+        assert_eq!(buffer.stats().byte_flushes, 1);
+
+        let second = timeout(Duration::from_millis(100), buffer.next_batch())
+            .await
+            .expect("should complete quickly")
+            .expect("should get a batch");
+        assert_eq!(buffer.stats().byte_flushes, 2);
+
+        let third = timeout(Duration::from_millis(100), buffer.next_batch())
+            .await
+            .expect("should complete quickly")
+            .expect("should get a batch");
+
+        assert_eq!(first.len() + second.len() + third.len(), 3);
+        assert_eq!(buffer.stats().logs_received, 3);
     }
 
     #[tokio::test]
@@ -625,6 +1336,35 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_adaptive_tuning_disabled_by_default() {
+        let config = BufferConfig::new(100, Duration::from_secs(5));
+        let (_sender, mut buffer) = LogBuffer::new(config);
+
+        buffer.record_flush_outcome(Duration::from_secs(10));
+
+        // No tuner configured: batch_size/flush_interval are untouched.
+        assert_eq!(buffer.config().batch_size, 100);
+        assert_eq!(buffer.config().flush_interval, Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_adaptive_tuning_shrinks_batch_on_slow_flush() {
+        use crate::tuner::AdaptiveTuningConfig;
+
+        let config = BufferConfig::new(100, Duration::from_secs(5)).with_adaptive_tuning(
+            AdaptiveTuningConfig {
+                target_flush_latency: Duration::from_millis(100),
+                ..AdaptiveTuningConfig::default()
+            },
+        );
+        let (_sender, mut buffer) = LogBuffer::new(config);
+
+        buffer.record_flush_outcome(Duration::from_secs(1)); // well over target
+
+        assert_eq!(buffer.config().batch_size, 90);
+    }
+
     #[tokio::test]
     async fn test_buffer_task_size_flush() {
         let (tx, rx) = mpsc::channel::<LogEntry>(100);
@@ -644,6 +1384,8 @@ mod tests {
                         Ok(())
                     }
                 },
+                OverflowPolicy::default(),
+                None,
             )
             .await;
         });
@@ -683,6 +1425,8 @@ mod tests {
                         Ok(())
                     }
                 },
+                OverflowPolicy::default(),
+                None,
             )
             .await;
         });
@@ -701,4 +1445,397 @@ mod tests {
         drop(tx);
         handle.abort();
     }
+
+    #[tokio::test]
+    async fn test_buffer_task_byte_budget_flush() {
+        let (tx, rx) = mpsc::channel::<LogEntry>(100);
+        let flush_count = Arc::new(AtomicUsize::new(0));
+        let flush_events = Arc::new(AtomicUsize::new(0));
+        let flush_count_clone = flush_count.clone();
+        let flush_events_clone = flush_events.clone();
+
+        // High batch size and flush interval so only the byte budget can
+        // explain a flush happening at all.
+        let handle = tokio::spawn(async move {
+            buffer_task(
+                rx,
+                1_000,
+                Duration::from_secs(60),
+                |batch| {
+                    let count = flush_count_clone.clone();
+                    let events = flush_events_clone.clone();
+                    async move {
+                        count.fetch_add(batch.len(), Ordering::SeqCst);
+                        events.fetch_add(1, Ordering::SeqCst);
+                        Ok(())
+                    }
+                },
+                OverflowPolicy::default(),
+                Some(200),
+            )
+            .await;
+        });
+
+        for _ in 0..3 {
+            tx.send(create_test_entry()).await.unwrap();
+        }
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        // Each entry is over half the budget, so each flush carries one entry
+        // and the third entry starts the next batch, waiting for a later
+        // trigger.
+        assert_eq!(flush_count.load(Ordering::SeqCst), 2);
+        assert!(
+            flush_events.load(Ordering::SeqCst) >= 2,
+            "byte budget should have split the entries across more than one flush"
+        );
+
+        drop(tx);
+        handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_next_batch_until_flushes_remainder_on_cancel() {
+        let config = BufferConfig::new(100, Duration::from_secs(60)); // never hits size/time
+        let (sender, mut buffer) = LogBuffer::new(config);
+        let token = CancellationToken::new();
+
+        sender.send(create_test_entry()).await.unwrap();
+        sender.send(create_test_entry()).await.unwrap();
+        token.cancel();
+
+        let result = timeout(Duration::from_millis(100), buffer.next_batch_until(token)).await;
+        let batch = result.expect("should complete quickly").expect("should get batch");
+
+        assert_eq!(batch.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_next_batch_until_returns_none_when_empty_on_cancel() {
+        let config = BufferConfig::new(100, Duration::from_secs(60));
+        let (_sender, mut buffer) = LogBuffer::new(config);
+        let token = CancellationToken::new();
+        token.cancel();
+
+        let result = timeout(Duration::from_millis(100), buffer.next_batch_until(token)).await;
+        let batch = result.expect("should complete quickly");
+
+        assert!(batch.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_buffer_task_with_shutdown_flushes_remainder_on_cancel() {
+        let (tx, rx) = mpsc::channel::<LogEntry>(100);
+        let flush_count = Arc::new(AtomicUsize::new(0));
+        let flush_count_clone = flush_count.clone();
+        let token = CancellationToken::new();
+        let shutdown = token.clone();
+
+        let handle = tokio::spawn(async move {
+            buffer_task_with_shutdown(
+                rx,
+                100, // High batch size, never reached
+                Duration::from_secs(60),
+                |batch| {
+                    let count = flush_count_clone.clone();
+                    async move {
+                        count.fetch_add(batch.len(), Ordering::SeqCst);
+                        Ok(())
+                    }
+                },
+                shutdown,
+                OverflowPolicy::default(),
+                None,
+                None,
+            )
+            .await;
+        });
+
+        tx.send(create_test_entry()).await.unwrap();
+        tx.send(create_test_entry()).await.unwrap();
+
+        token.cancel();
+        timeout(Duration::from_millis(100), handle)
+            .await
+            .expect("task should complete quickly")
+            .expect("task should not panic");
+
+        assert_eq!(flush_count.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_overflow_policy_drop_newest_rejects_incoming() {
+        let config = BufferConfig {
+            max_capacity: 3,
+            channel_capacity: 10,
+            overflow_policy: OverflowPolicy::DropNewest,
+            ..BufferConfig::new(100, Duration::from_secs(60))
+        };
+        let (sender, mut buffer) = LogBuffer::new(config);
+
+        for _ in 0..5 {
+            sender.send(create_test_entry()).await.unwrap();
+        }
+        drop(sender);
+
+        let batch = timeout(Duration::from_millis(100), buffer.next_batch())
+            .await
+            .expect("should complete quickly")
+            .expect("should get batch");
+
+        assert_eq!(batch.len(), 3); // the 2 newest were rejected
+        assert_eq!(buffer.stats().logs_dropped_newest, 2);
+        assert_eq!(buffer.stats().logs_dropped, 2);
+    }
+
+    #[tokio::test]
+    async fn test_overflow_policy_priority_drop_evicts_lowest_severity_first() {
+        let config = BufferConfig {
+            max_capacity: 3,
+            channel_capacity: 10,
+            overflow_policy: OverflowPolicy::PriorityDrop,
+            ..BufferConfig::new(100, Duration::from_secs(60))
+        };
+        let (sender, mut buffer) = LogBuffer::new(config);
+
+        sender
+            .send(LogEntry::new("s", LogLevel::Error, "keep me"))
+            .await
+            .unwrap();
+        sender
+            .send(LogEntry::new("s", LogLevel::Debug, "drop me"))
+            .await
+            .unwrap();
+        sender
+            .send(LogEntry::new("s", LogLevel::Info, "keep me too"))
+            .await
+            .unwrap();
+        // Fourth entry overflows max_capacity=3, evicting the lowest-severity one.
+        sender
+            .send(LogEntry::new("s", LogLevel::Warn, "also keep"))
+            .await
+            .unwrap();
+        drop(sender);
+
+        let batch = timeout(Duration::from_millis(100), buffer.next_batch())
+            .await
+            .expect("should complete quickly")
+            .expect("should get batch");
+
+        assert_eq!(batch.len(), 3);
+        assert!(batch.logs.iter().all(|e| e.level != LogLevel::Debug));
+        assert_eq!(buffer.stats().logs_dropped_priority, 1);
+    }
+
+    #[tokio::test]
+    async fn test_overflow_policy_block_does_not_drop() {
+        let config = BufferConfig {
+            max_capacity: 3,
+            channel_capacity: 10,
+            overflow_policy: OverflowPolicy::Block,
+            ..BufferConfig::new(100, Duration::from_secs(60))
+        };
+        let (sender, mut buffer) = LogBuffer::new(config);
+
+        for _ in 0..5 {
+            sender.send(create_test_entry()).await.unwrap();
+        }
+        drop(sender);
+
+        let batch = timeout(Duration::from_millis(100), buffer.next_batch())
+            .await
+            .expect("should complete quickly")
+            .expect("should get batch");
+
+        assert_eq!(batch.len(), 5); // nothing dropped, buffer grew past max_capacity
+        assert_eq!(buffer.stats().logs_dropped, 0);
+    }
+
+    /// How many of a [`MockSink`]'s sends should fail before it starts
+    /// succeeding, for deterministic retry-path tests that don't depend on a
+    /// live remote endpoint.
+    #[derive(Debug, Clone, Copy)]
+    enum MockFailurePolicy {
+        /// Fail every send.
+        AlwaysFail,
+        /// Fail the first `n` sends across the sink's lifetime, then succeed.
+        FailFirst(usize),
+        /// Succeed every send.
+        AlwaysSucceed,
+    }
+
+    /// A test-only `on_flush` target for [`buffer_task`]/[`buffer_task_with_shutdown`]
+    /// that can be configured to fail a set number of times before succeeding,
+    /// and that records every batch it receives (in the order received) for
+    /// the caller to assert against.
+    #[derive(Clone)]
+    struct MockSink {
+        policy: MockFailurePolicy,
+        attempts: Arc<AtomicUsize>,
+        received: Arc<std::sync::Mutex<Vec<LogBatch>>>,
+    }
+
+    impl MockSink {
+        fn new(policy: MockFailurePolicy) -> Self {
+            Self {
+                policy,
+                attempts: Arc::new(AtomicUsize::new(0)),
+                received: Arc::new(std::sync::Mutex::new(Vec::new())),
+            }
+        }
+
+        fn received_batches(&self) -> Vec<LogBatch> {
+            self.received.lock().unwrap().clone()
+        }
+
+        async fn send(&self, batch: LogBatch) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            let attempt = self.attempts.fetch_add(1, Ordering::SeqCst);
+            let should_fail = match self.policy {
+                MockFailurePolicy::AlwaysFail => true,
+                MockFailurePolicy::FailFirst(n) => attempt < n,
+                MockFailurePolicy::AlwaysSucceed => false,
+            };
+
+            if should_fail {
+                return Err("mock sink configured to fail this send".into());
+            }
+
+            self.received.lock().unwrap().push(batch);
+            Ok(())
+        }
+    }
+
+    /// Resend `batch` through `sink` up to `max_retries` times after the
+    /// first attempt, mirroring `LogClient::send_batch`'s "keep trying up to
+    /// `max_retries` times" contract so `MockSink` can stand in for it in
+    /// tests that don't depend on a live remote endpoint.
+    async fn send_with_retries(
+        sink: &MockSink,
+        batch: LogBatch,
+        max_retries: u32,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut last_err = None;
+
+        for _ in 0..=max_retries {
+            match sink.send(batch.clone()).await {
+                Ok(()) => return Ok(()),
+                Err(e) => last_err = Some(e),
+            }
+        }
+
+        Err(last_err.expect("loop always attempts at least once"))
+    }
+
+    #[tokio::test]
+    async fn test_send_with_retries_succeeds_within_max_retries() {
+        // Fails the first 2 sends, succeeds on the 3rd (2 retries).
+        let sink = MockSink::new(MockFailurePolicy::FailFirst(2));
+
+        let result = send_with_retries(&sink, LogBatch::new(vec![create_test_entry()]), 2).await;
+
+        assert!(result.is_ok());
+        assert_eq!(sink.received_batches().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_send_with_retries_gives_up_after_max_retries() {
+        // Fails the first 5 sends (succeeding only on the 6th), but only 3
+        // total attempts (max_retries = 2) are allowed.
+        let sink = MockSink::new(MockFailurePolicy::FailFirst(5));
+
+        let result = send_with_retries(&sink, LogBatch::new(vec![create_test_entry()]), 2).await;
+
+        assert!(result.is_err());
+        assert_eq!(sink.received_batches().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_buffer_task_flushes_batches_in_order_via_mock_sink() {
+        let (tx, rx) = mpsc::channel::<LogEntry>(100);
+        let sink = MockSink::new(MockFailurePolicy::AlwaysSucceed);
+        let sink_clone = sink.clone();
+
+        let handle = tokio::spawn(async move {
+            buffer_task(
+                rx,
+                3,
+                Duration::from_secs(60),
+                move |batch| {
+                    let sink = sink_clone.clone();
+                    async move { sink.send(batch).await }
+                },
+                OverflowPolicy::default(),
+                None,
+            )
+            .await;
+        });
+
+        for i in 0..9 {
+            tx.send(LogEntry::new("test-source", LogLevel::Info, format!("message-{i}")))
+                .await
+                .unwrap();
+        }
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        // buffer_task keeps running after the channel closes, so stop it here.
+        drop(tx);
+        handle.abort();
+
+        let received_messages: Vec<String> = sink
+            .received_batches()
+            .into_iter()
+            .flat_map(|batch| batch.logs.into_iter().map(|e| e.message))
+            .collect();
+
+        let expected: Vec<String> = (0..9).map(|i| format!("message-{i}")).collect();
+        assert_eq!(received_messages, expected);
+    }
+
+    #[tokio::test]
+    async fn test_buffer_task_with_shutdown_resends_remaining_batch_via_mock_sink() {
+        let (tx, rx) = mpsc::channel::<LogEntry>(100);
+        let sink = MockSink::new(MockFailurePolicy::AlwaysSucceed);
+        let sink_clone = sink.clone();
+        let token = CancellationToken::new();
+        let shutdown = token.clone();
+
+        let handle = tokio::spawn(async move {
+            buffer_task_with_shutdown(
+                rx,
+                100, // High batch size, never reached on its own
+                Duration::from_secs(60),
+                move |batch| {
+                    let sink = sink_clone.clone();
+                    async move { sink.send(batch).await }
+                },
+                shutdown,
+                OverflowPolicy::default(),
+                None,
+                None,
+            )
+            .await;
+        });
+
+        tx.send(LogEntry::new("test-source", LogLevel::Info, "drained-1"))
+            .await
+            .unwrap();
+        tx.send(LogEntry::new("test-source", LogLevel::Info, "drained-2"))
+            .await
+            .unwrap();
+
+        token.cancel();
+        timeout(Duration::from_millis(100), handle)
+            .await
+            .expect("task should complete quickly")
+            .expect("task should not panic");
+
+        let received_messages: Vec<String> = sink
+            .received_batches()
+            .into_iter()
+            .flat_map(|batch| batch.logs.into_iter().map(|e| e.message))
+            .collect();
+
+        assert_eq!(received_messages, vec!["drained-1", "drained-2"]);
+    }
 }
@@ -0,0 +1,300 @@
+//! Circuit breaker guarding the ingest HTTP call.
+//!
+//! Blind retries (see [`crate::client::LogClient`]) are fine for transient
+//! blips, but when the backend is fully down they waste the whole request
+//! timeout budget on every single batch. [`CircuitBreaker`] sits in front of
+//! the HTTP call and short-circuits sends once failures pile up, so a dead
+//! backend fails fast instead of slow.
+//!
+//! Three states, matching the classic circuit-breaker pattern:
+//! - **Closed**: requests flow normally; consecutive failures are counted,
+//!   and hitting the configured threshold trips the breaker to Open.
+//! - **Open**: every request is rejected immediately, without touching the
+//!   network, until `open_duration` has elapsed since it tripped.
+//! - **Half-Open**: after the cooldown, up to `half_open_max_probes` trial
+//!   requests are let through. A single success closes the breaker and
+//!   resets the failure count; any failure re-opens it and restarts the
+//!   cooldown.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::config::Config;
+
+/// Which of the three states the breaker is currently in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CircuitState {
+    #[default]
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+/// Error returned when [`CircuitBreaker::before_request`] rejects a call.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CircuitOpenError {
+    /// How much longer the breaker is expected to stay Open.
+    pub retry_after: Duration,
+}
+
+impl std::fmt::Display for CircuitOpenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "circuit breaker is open, retry after {:.1}s",
+            self.retry_after.as_secs_f64()
+        )
+    }
+}
+
+impl std::error::Error for CircuitOpenError {}
+
+struct Inner {
+    state: CircuitState,
+    consecutive_failures: u32,
+    /// When the breaker most recently tripped to Open; `None` while Closed.
+    opened_at: Option<Instant>,
+    /// Trial requests already let through during the current Half-Open window.
+    half_open_probes_issued: u32,
+    consecutive_failure_threshold: u32,
+    open_duration: Duration,
+    half_open_max_probes: u32,
+}
+
+/// A shared, clonable circuit breaker handle.
+///
+/// Cloning shares the same underlying state (via an internal `Arc<Mutex<_>>`),
+/// so a single breaker can be handed to every task that sends batches.
+#[derive(Clone)]
+pub struct CircuitBreaker {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl Default for CircuitBreaker {
+    /// Create a breaker using the default `cb_*` settings from [`Config::default`].
+    fn default() -> Self {
+        Self::new(&Config::default())
+    }
+}
+
+impl CircuitBreaker {
+    /// Create a breaker using the `cb_*` settings from `config`.
+    pub fn new(config: &Config) -> Self {
+        Self::with_settings(
+            config.cb_consecutive_failures,
+            config.cb_open_duration,
+            config.cb_half_open_max_probes,
+        )
+    }
+
+    /// Create a breaker with explicit settings, bypassing `Config`.
+    pub fn with_settings(
+        consecutive_failure_threshold: u32,
+        open_duration: Duration,
+        half_open_max_probes: u32,
+    ) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Inner {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                opened_at: None,
+                half_open_probes_issued: 0,
+                consecutive_failure_threshold,
+                open_duration,
+                half_open_max_probes,
+            })),
+        }
+    }
+
+    /// Check whether a request may proceed, transitioning Open to Half-Open
+    /// once the cooldown has elapsed.
+    ///
+    /// Call this before each HTTP call; on `Ok(())` send the request and
+    /// report the outcome via [`CircuitBreaker::record_success`] or
+    /// [`CircuitBreaker::record_failure`]. On `Err`, skip the network call
+    /// entirely.
+    pub fn before_request(&self) -> Result<(), CircuitOpenError> {
+        let mut inner = self.inner.lock().expect("circuit breaker mutex poisoned");
+
+        match inner.state {
+            CircuitState::Closed => Ok(()),
+            CircuitState::Open => {
+                let opened_at = inner.opened_at.expect("Open state always has opened_at set");
+                let elapsed = opened_at.elapsed();
+
+                if elapsed >= inner.open_duration {
+                    inner.state = CircuitState::HalfOpen;
+                    inner.half_open_probes_issued = 1;
+                    Ok(())
+                } else {
+                    Err(CircuitOpenError {
+                        retry_after: inner.open_duration - elapsed,
+                    })
+                }
+            }
+            CircuitState::HalfOpen => {
+                if inner.half_open_probes_issued < inner.half_open_max_probes {
+                    inner.half_open_probes_issued += 1;
+                    Ok(())
+                } else {
+                    Err(CircuitOpenError {
+                        retry_after: inner.open_duration,
+                    })
+                }
+            }
+        }
+    }
+
+    /// Report that a guarded request succeeded.
+    ///
+    /// Fully resets the breaker: consecutive failures drop to zero and the
+    /// state (if Open or Half-Open) returns to Closed.
+    pub fn record_success(&self) {
+        let mut inner = self.inner.lock().expect("circuit breaker mutex poisoned");
+        inner.consecutive_failures = 0;
+        inner.state = CircuitState::Closed;
+        inner.opened_at = None;
+        inner.half_open_probes_issued = 0;
+    }
+
+    /// Report that a guarded request failed.
+    ///
+    /// From Closed, increments the consecutive-failure count and trips to
+    /// Open once the configured threshold is reached. From Half-Open, a
+    /// single failure immediately re-opens the breaker and restarts the
+    /// cooldown.
+    pub fn record_failure(&self) {
+        let mut inner = self.inner.lock().expect("circuit breaker mutex poisoned");
+
+        match inner.state {
+            CircuitState::Closed => {
+                inner.consecutive_failures += 1;
+                if inner.consecutive_failures >= inner.consecutive_failure_threshold {
+                    inner.state = CircuitState::Open;
+                    inner.opened_at = Some(Instant::now());
+                }
+            }
+            CircuitState::HalfOpen => {
+                inner.state = CircuitState::Open;
+                inner.opened_at = Some(Instant::now());
+                inner.half_open_probes_issued = 0;
+            }
+            CircuitState::Open => {
+                // Defensive: before_request() should prevent calls while Open.
+            }
+        }
+    }
+
+    /// Get the breaker's current state, mainly for tests and observability.
+    pub fn state(&self) -> CircuitState {
+        self.inner.lock().expect("circuit breaker mutex poisoned").state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker(threshold: u32, open_duration: Duration, half_open_max_probes: u32) -> CircuitBreaker {
+        CircuitBreaker::with_settings(threshold, open_duration, half_open_max_probes)
+    }
+
+    #[test]
+    fn test_starts_closed() {
+        let cb = breaker(3, Duration::from_secs(10), 1);
+        assert_eq!(cb.state(), CircuitState::Closed);
+        assert!(cb.before_request().is_ok());
+    }
+
+    #[test]
+    fn test_trips_open_after_threshold_failures() {
+        let cb = breaker(3, Duration::from_secs(10), 1);
+
+        cb.record_failure();
+        cb.record_failure();
+        assert_eq!(cb.state(), CircuitState::Closed);
+
+        cb.record_failure();
+        assert_eq!(cb.state(), CircuitState::Open);
+        assert!(cb.before_request().is_err());
+    }
+
+    #[test]
+    fn test_success_resets_consecutive_failures() {
+        let cb = breaker(3, Duration::from_secs(10), 1);
+
+        cb.record_failure();
+        cb.record_failure();
+        cb.record_success();
+        cb.record_failure();
+        cb.record_failure();
+
+        // Two more failures after the reset shouldn't be enough to trip.
+        assert_eq!(cb.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_open_rejects_until_cooldown_elapses() {
+        let cb = breaker(1, Duration::from_millis(50), 1);
+        cb.record_failure();
+        assert_eq!(cb.state(), CircuitState::Open);
+
+        let err = cb.before_request().expect_err("should reject while Open");
+        assert!(err.retry_after <= Duration::from_millis(50));
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(cb.before_request().is_ok());
+        assert_eq!(cb.state(), CircuitState::HalfOpen);
+    }
+
+    #[test]
+    fn test_half_open_success_closes_breaker() {
+        let cb = breaker(1, Duration::from_millis(10), 1);
+        cb.record_failure();
+        std::thread::sleep(Duration::from_millis(20));
+
+        cb.before_request().expect("cooldown elapsed, probe allowed");
+        assert_eq!(cb.state(), CircuitState::HalfOpen);
+
+        cb.record_success();
+        assert_eq!(cb.state(), CircuitState::Closed);
+        assert!(cb.before_request().is_ok());
+    }
+
+    #[test]
+    fn test_half_open_failure_reopens_and_restarts_cooldown() {
+        let cb = breaker(1, Duration::from_millis(30), 1);
+        cb.record_failure();
+        std::thread::sleep(Duration::from_millis(40));
+
+        cb.before_request().expect("cooldown elapsed, probe allowed");
+        assert_eq!(cb.state(), CircuitState::HalfOpen);
+
+        cb.record_failure();
+        assert_eq!(cb.state(), CircuitState::Open);
+        // Fresh cooldown: immediately rejecting again confirms it restarted.
+        assert!(cb.before_request().is_err());
+    }
+
+    #[test]
+    fn test_half_open_limits_concurrent_probes() {
+        let cb = breaker(1, Duration::from_millis(10), 2);
+        cb.record_failure();
+        std::thread::sleep(Duration::from_millis(20));
+
+        assert!(cb.before_request().is_ok()); // probe 1
+        assert!(cb.before_request().is_ok()); // probe 2
+        assert!(cb.before_request().is_err()); // exceeds half_open_max_probes
+    }
+
+    #[test]
+    fn test_circuit_open_error_display() {
+        let err = CircuitOpenError {
+            retry_after: Duration::from_millis(1500),
+        };
+        assert_eq!(
+            format!("{}", err),
+            "circuit breaker is open, retry after 1.5s"
+        );
+    }
+}
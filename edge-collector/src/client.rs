@@ -3,12 +3,20 @@
 //! This module provides an async HTTP client with connection pooling,
 //! retry logic with exponential backoff, and proper error handling.
 
-use std::time::Duration;
+use std::io::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+use flate2::write::GzEncoder;
+use flate2::Compression;
 use reqwest::{Client, StatusCode};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, error, info, warn};
 
+use crate::circuit_breaker::CircuitBreaker;
+use crate::concurrency::{AimdLimiter, AimdLimiterConfig};
+use crate::rate_limiter::{TokenBucket, TokenBucketConfig};
 use crate::config::Config;
 use crate::log_generator::LogBatch;
 
@@ -18,6 +26,96 @@ const DEFAULT_BASE_DELAY_MS: u64 = 500;
 /// Maximum delay between retries (in milliseconds).
 const MAX_RETRY_DELAY_MS: u64 = 30_000;
 
+/// Default capacity of a fresh [`RetryTokenBucket`].
+const DEFAULT_RETRY_TOKEN_CAPACITY: u64 = 500;
+
+/// Tokens spent on a retry triggered by a timeout or connection failure —
+/// the slow, expensive-to-discover kind of failure, so it's charged more.
+const RETRY_COST_TIMEOUT_OR_CONNECT: u64 = 5;
+
+/// Tokens spent on a retry triggered by a 5xx server error.
+const RETRY_COST_SERVER_ERROR: u64 = 1;
+
+/// Tokens refunded to the bucket after each successful request, capped at
+/// the bucket's capacity.
+const RETRY_REFUND_ON_SUCCESS: u64 = 1;
+
+/// Default size, in bytes, above which [`LogClient::with_settings`] gzips a
+/// request body. Mirrors [`crate::config::Config::default`]'s threshold.
+const DEFAULT_COMPRESSION_THRESHOLD_BYTES: usize = 1024;
+
+/// A shared token bucket that bounds how many retries [`LogClient`] performs
+/// fleet-wide.
+///
+/// Every `send_batch` call retrying independently up to `max_retries` is
+/// fine in isolation, but during a broad outage it produces a synchronized
+/// flood of retries across every concurrent batch. `RetryTokenBucket` caps
+/// the *total* retry volume shared by every clone: each retry spends a few
+/// tokens before it's allowed to sleep and re-send, and every successful
+/// request refunds a small amount back up to capacity. Once the bucket runs
+/// dry, further retries are abandoned immediately rather than piling on a
+/// struggling backend.
+#[derive(Clone)]
+struct RetryTokenBucket {
+    tokens: Arc<AtomicU64>,
+    capacity: u64,
+}
+
+impl RetryTokenBucket {
+    fn new(capacity: u64) -> Self {
+        Self {
+            tokens: Arc::new(AtomicU64::new(capacity)),
+            capacity,
+        }
+    }
+
+    /// Try to spend `cost` tokens. Returns `true` if there were enough.
+    fn try_acquire(&self, cost: u64) -> bool {
+        let mut current = self.tokens.load(Ordering::Acquire);
+        loop {
+            if current < cost {
+                return false;
+            }
+            match self.tokens.compare_exchange_weak(
+                current,
+                current - cost,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => return true,
+                Err(observed) => current = observed,
+            }
+        }
+    }
+
+    /// Refund `amount` tokens, capped at the bucket's capacity.
+    fn refund(&self, amount: u64) {
+        let mut current = self.tokens.load(Ordering::Acquire);
+        loop {
+            let refunded = current.saturating_add(amount).min(self.capacity);
+            match self.tokens.compare_exchange_weak(
+                current,
+                refunded,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => return,
+                Err(observed) => current = observed,
+            }
+        }
+    }
+
+    fn level(&self) -> u64 {
+        self.tokens.load(Ordering::Acquire)
+    }
+}
+
+impl Default for RetryTokenBucket {
+    fn default() -> Self {
+        Self::new(DEFAULT_RETRY_TOKEN_CAPACITY)
+    }
+}
+
 /// Response from the log ingestion API.
 #[derive(Debug, Clone, Deserialize)]
 pub struct IngestResponse {
@@ -51,6 +149,10 @@ pub enum ClientError {
     Status {
         code: StatusCode,
         message: String,
+
+        /// How long the server asked us to wait before retrying, parsed
+        /// from a `Retry-After` header (seconds or HTTP-date form).
+        retry_after: Option<Duration>,
     },
 
     /// Failed to parse response body
@@ -67,15 +169,34 @@ pub enum ClientError {
 
     /// Client configuration error
     Config(String),
+
+    /// The circuit breaker is open; the request was rejected without
+    /// touching the network.
+    CircuitOpen(crate::circuit_breaker::CircuitOpenError),
+
+    /// The client-side rate limiter has no tokens available and the call
+    /// requested fail-fast behavior instead of waiting.
+    RateLimited,
 }
 
 impl std::fmt::Display for ClientError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ClientError::Request(e) => write!(f, "HTTP request failed: {}", e),
-            ClientError::Status { code, message } => {
-                write!(f, "Server error ({}): {}", code, message)
-            }
+            ClientError::Status {
+                code,
+                message,
+                retry_after,
+            } => match retry_after {
+                Some(d) => write!(
+                    f,
+                    "Server error ({}): {} (retry after {:.1}s)",
+                    code,
+                    message,
+                    d.as_secs_f64()
+                ),
+                None => write!(f, "Server error ({}): {}", code, message),
+            },
             ClientError::Parse(e) => write!(f, "Failed to parse response: {}", e),
             ClientError::RetriesExhausted {
                 attempts,
@@ -89,6 +210,8 @@ impl std::fmt::Display for ClientError {
             }
             ClientError::Timeout => write!(f, "Request timed out"),
             ClientError::Config(e) => write!(f, "Client configuration error: {}", e),
+            ClientError::CircuitOpen(e) => write!(f, "{}", e),
+            ClientError::RateLimited => write!(f, "Rate limited: no send tokens available"),
         }
     }
 }
@@ -97,6 +220,7 @@ impl std::error::Error for ClientError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             ClientError::Request(e) => Some(e),
+            ClientError::CircuitOpen(e) => Some(e),
             _ => None,
         }
     }
@@ -129,6 +253,101 @@ pub struct ClientStats {
 
     /// Total number of batches that failed after all retries
     pub batches_failed: u64,
+
+    /// Current state of the client's circuit breaker.
+    pub circuit_state: crate::circuit_breaker::CircuitState,
+
+    /// Tokens currently available in the shared retry bucket.
+    pub retry_tokens_available: u64,
+
+    /// Number of retries abandoned because the retry bucket was empty.
+    pub retries_denied_by_bucket: u64,
+
+    /// Total bytes of serialized JSON batch bodies before any compression.
+    pub bytes_uncompressed: u64,
+
+    /// Total bytes actually placed on the wire, after gzipping wherever
+    /// compression was applied.
+    pub bytes_on_wire: u64,
+
+    /// The AIMD concurrency limiter's current limit.
+    pub concurrency_limit: usize,
+
+    /// The AIMD concurrency limiter's current EWMA baseline RTT, or `None`
+    /// before the first successful send.
+    pub baseline_rtt: Option<Duration>,
+
+    /// Total time spent waiting for a send-rate-limiter token across all
+    /// sends.
+    pub time_throttled: Duration,
+}
+
+/// Per-call overrides for [`LogClient::send_batch_with`].
+///
+/// Any field left as `None` falls back to the client-wide default from
+/// [`Config`]/[`LogClient::with_settings`]. This mirrors how mature HTTP
+/// SDKs separate per-request retry/timeout policy from the client default —
+/// one urgent batch can fail fast, one backfill batch can retry harder,
+/// without reconfiguring the shared client.
+#[derive(Debug, Clone, Copy)]
+pub struct RequestConfig {
+    /// Override for the client's `max_retries`, for this call only.
+    pub max_retries: Option<u32>,
+
+    /// Override for the client's request timeout, for this call only.
+    pub timeout: Option<Duration>,
+
+    /// Whether this request is safe to retry on an ambiguous failure (a
+    /// timeout, where the server may or may not have processed the batch
+    /// before the connection dropped). Defaults to `true`, matching
+    /// `send_batch`'s existing behavior — the ingest endpoint dedupes by
+    /// `batch_id`, so retrying an ambiguous failure is safe unless the
+    /// caller marks a specific call otherwise.
+    pub idempotent: bool,
+
+    /// Whether to wait for a send-rate-limiter token before the first
+    /// attempt, versus failing fast with `ClientError::RateLimited` when
+    /// none is immediately available. Defaults to `true` (wait).
+    pub wait_for_rate_limit: bool,
+}
+
+impl Default for RequestConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: None,
+            timeout: None,
+            idempotent: true,
+            wait_for_rate_limit: true,
+        }
+    }
+}
+
+impl RequestConfig {
+    /// Override `max_retries` for this call.
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = Some(max_retries);
+        self
+    }
+
+    /// Override the request timeout for this call.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Mark this request as non-idempotent, so ambiguous failures (timeouts)
+    /// are not retried.
+    pub fn idempotent(mut self, idempotent: bool) -> Self {
+        self.idempotent = idempotent;
+        self
+    }
+
+    /// Fail fast with `ClientError::RateLimited` instead of waiting when the
+    /// send-rate limiter has no token immediately available.
+    pub fn wait_for_rate_limit(mut self, wait_for_rate_limit: bool) -> Self {
+        self.wait_for_rate_limit = wait_for_rate_limit;
+        self
+    }
 }
 
 /// HTTP client for sending log batches to the cloud API.
@@ -172,6 +391,37 @@ pub struct LogClient {
     /// Request timeout duration
     timeout: Duration,
 
+    /// Circuit breaker guarding the ingest endpoint
+    circuit_breaker: CircuitBreaker,
+
+    /// Shared bucket bounding the total number of retries this client performs
+    retry_bucket: RetryTokenBucket,
+
+    /// Count of retries abandoned because the retry bucket was empty
+    retries_denied: AtomicU64,
+
+    /// Whether request bodies over `compression_threshold` are gzipped
+    enable_compression: bool,
+
+    /// Size, in bytes, of the serialized JSON body above which it's gzipped
+    compression_threshold: usize,
+
+    /// Total bytes of serialized JSON bodies before any compression
+    bytes_uncompressed: AtomicU64,
+
+    /// Total bytes actually placed on the wire (gzipped where applicable)
+    bytes_on_wire: AtomicU64,
+
+    /// AIMD limiter bounding how many sends are in flight at once
+    concurrency: AimdLimiter,
+
+    /// Token bucket pacing the first attempt of every send, independent of
+    /// retries
+    rate_limiter: TokenBucket,
+
+    /// Total nanoseconds spent waiting for a send-rate-limiter token
+    throttled_nanos: AtomicU64,
+
     /// Client operation statistics
     stats: std::sync::atomic::AtomicU64,
 }
@@ -199,6 +449,19 @@ impl LogClient {
             ingest_url: config.ingest_url.clone(),
             max_retries: config.max_retries,
             timeout: config.request_timeout,
+            circuit_breaker: CircuitBreaker::new(config),
+            retry_bucket: RetryTokenBucket::default(),
+            retries_denied: AtomicU64::new(0),
+            enable_compression: config.enable_compression,
+            compression_threshold: config.compression_threshold,
+            bytes_uncompressed: AtomicU64::new(0),
+            bytes_on_wire: AtomicU64::new(0),
+            concurrency: AimdLimiter::new(AimdLimiterConfig::default()),
+            rate_limiter: TokenBucket::new(TokenBucketConfig {
+                rate_per_sec: config.max_requests_per_second,
+                burst: config.rate_limit_burst,
+            }),
+            throttled_nanos: AtomicU64::new(0),
             stats: std::sync::atomic::AtomicU64::new(0),
         })
     }
@@ -206,6 +469,10 @@ impl LogClient {
     /// Create a new log client with custom settings.
     ///
     /// This is useful for testing or when you need more control over the client.
+    /// The circuit breaker uses [`CircuitBreaker::default`] settings,
+    /// compression is enabled with the default 1 KiB threshold, and the
+    /// send-rate limiter uses [`TokenBucketConfig::default`]; use
+    /// [`LogClient::new`] to configure any of these via `Config`.
     pub fn with_settings(
         ingest_url: impl Into<String>,
         timeout: Duration,
@@ -223,18 +490,41 @@ impl LogClient {
             ingest_url: ingest_url.into(),
             max_retries,
             timeout,
+            circuit_breaker: CircuitBreaker::default(),
+            retry_bucket: RetryTokenBucket::default(),
+            retries_denied: AtomicU64::new(0),
+            enable_compression: true,
+            compression_threshold: DEFAULT_COMPRESSION_THRESHOLD_BYTES,
+            bytes_uncompressed: AtomicU64::new(0),
+            bytes_on_wire: AtomicU64::new(0),
+            concurrency: AimdLimiter::new(AimdLimiterConfig::default()),
+            rate_limiter: TokenBucket::default(),
+            throttled_nanos: AtomicU64::new(0),
             stats: std::sync::atomic::AtomicU64::new(0),
         })
     }
 
-    /// Send a batch of logs to the cloud API.
+    /// Send a batch of logs to the cloud API using the client's default
+    /// retry/timeout policy.
+    ///
+    /// Thin wrapper around [`LogClient::send_batch_with`] using
+    /// `RequestConfig::default()`; see that method for the full behavior.
+    pub async fn send_batch(&self, batch: LogBatch) -> Result<IngestResponse, ClientError> {
+        self.send_batch_with(batch, RequestConfig::default()).await
+    }
+
+    /// Send a batch of logs to the cloud API, overriding the client's
+    /// default retry/timeout policy for this call only.
     ///
-    /// This method implements retry logic with exponential backoff.
-    /// It will retry up to `max_retries` times on transient failures.
+    /// This method implements retry logic with exponential backoff. It will
+    /// retry up to `request_config.max_retries` (or the client default)
+    /// times on transient failures.
     ///
     /// # Arguments
     ///
     /// * `batch` - The log batch to send
+    /// * `request_config` - Per-call overrides; any `None` field falls back
+    ///   to the client default
     ///
     /// # Returns
     ///
@@ -244,7 +534,7 @@ impl LogClient {
     ///
     /// The following errors trigger retries:
     /// - Network connection errors
-    /// - Request timeouts
+    /// - Request timeouts (only when `request_config.idempotent`)
     /// - Server errors (5xx status codes)
     ///
     /// # Non-Retryable Errors
@@ -252,9 +542,25 @@ impl LogClient {
     /// The following errors do NOT trigger retries:
     /// - Client errors (4xx status codes, except 429)
     /// - Parse errors
-    pub async fn send_batch(&self, batch: LogBatch) -> Result<IngestResponse, ClientError> {
+    pub async fn send_batch_with(
+        &self,
+        batch: LogBatch,
+        request_config: RequestConfig,
+    ) -> Result<IngestResponse, ClientError> {
         let batch_size = batch.len();
         let batch_id = batch.batch_id.map(|id| id.to_string());
+        let max_retries = request_config.max_retries.unwrap_or(self.max_retries);
+        let timeout = request_config.timeout.unwrap_or(self.timeout);
+
+        // Pace the initial send, independent of the retry loop below: retries
+        // already have their own pacing via `retry_bucket`.
+        if request_config.wait_for_rate_limit {
+            let waited = self.rate_limiter.acquire().await;
+            self.throttled_nanos
+                .fetch_add(waited.as_nanos() as u64, Ordering::Relaxed);
+        } else if !self.rate_limiter.try_acquire() {
+            return Err(ClientError::RateLimited);
+        }
 
         debug!(
             batch_size = batch_size,
@@ -265,21 +571,34 @@ impl LogClient {
 
         let mut last_error: Option<ClientError> = None;
         let mut attempt = 0;
+        let mut retry_after: Option<Duration> = None;
 
-        while attempt <= self.max_retries {
+        while attempt <= max_retries {
             if attempt > 0 {
-                let delay = self.calculate_backoff_delay(attempt);
+                let delay = retry_after
+                    .map(|d| d.min(Duration::from_millis(MAX_RETRY_DELAY_MS)))
+                    .unwrap_or_else(|| self.calculate_backoff_delay(attempt));
                 warn!(
                     attempt = attempt,
-                    max_retries = self.max_retries,
+                    max_retries = max_retries,
                     delay_ms = delay.as_millis(),
                     "Retrying after failure"
                 );
                 tokio::time::sleep(delay).await;
             }
 
-            match self.send_request(&batch).await {
+            if let Err(open_err) = self.circuit_breaker.before_request() {
+                warn!(
+                    retry_after_ms = open_err.retry_after.as_millis(),
+                    "Circuit breaker open, rejecting batch without a network call"
+                );
+                return Err(ClientError::CircuitOpen(open_err));
+            }
+
+            match self.send_request_limited(&batch, timeout).await {
                 Ok(response) => {
+                    self.circuit_breaker.record_success();
+                    self.retry_bucket.refund(RETRY_REFUND_ON_SUCCESS);
                     info!(
                         batch_size = batch_size,
                         accepted = response.accepted,
@@ -289,15 +608,33 @@ impl LogClient {
                     return Ok(response);
                 }
                 Err(e) => {
-                    let is_retryable = self.is_retryable_error(&e);
+                    let is_retryable = self.is_retryable_error(&e, request_config.idempotent);
+                    if is_retryable {
+                        self.circuit_breaker.record_failure();
+                    }
+
+                    if is_retryable && attempt < max_retries {
+                        let retry_cost = self.retry_token_cost(&e);
+                        if !self.retry_bucket.try_acquire(retry_cost) {
+                            self.retries_denied.fetch_add(1, Ordering::Relaxed);
+                            warn!(
+                                error = %e,
+                                attempt = attempt + 1,
+                                "Retry token bucket empty, abandoning retries"
+                            );
+                            return Err(e);
+                        }
 
-                    if is_retryable && attempt < self.max_retries {
                         warn!(
                             error = %e,
                             attempt = attempt + 1,
-                            max_retries = self.max_retries,
+                            max_retries = max_retries,
                             "Request failed, will retry"
                         );
+                        retry_after = match &e {
+                            ClientError::Status { retry_after, .. } => *retry_after,
+                            _ => None,
+                        };
                         last_error = Some(e);
                         attempt += 1;
                     } else {
@@ -319,20 +656,73 @@ impl LogClient {
             .unwrap_or_else(|| "Unknown error".to_string());
 
         Err(ClientError::RetriesExhausted {
-            attempts: self.max_retries + 1,
+            attempts: max_retries + 1,
             last_error: last_error_msg,
         })
     }
 
+    /// Send a single HTTP request gated by the AIMD concurrency limiter.
+    ///
+    /// Acquires a permit before sending, measures the round-trip time, and
+    /// reports the outcome back to the limiter: a success updates the
+    /// baseline RTT and may grow the limit, while a timeout or 5xx always
+    /// backs it off. Client errors (4xx, parse failures) aren't a signal of
+    /// network congestion, so they're left alone.
+    async fn send_request_limited(
+        &self,
+        batch: &LogBatch,
+        timeout: Duration,
+    ) -> Result<IngestResponse, ClientError> {
+        let permit = self.concurrency.acquire().await;
+        let start = Instant::now();
+        let result = self.send_request(batch, timeout).await;
+        let rtt = start.elapsed();
+
+        match &result {
+            Ok(_) => self.concurrency.record_success(rtt, &permit),
+            Err(e) if is_congestion_signal(e) => self.concurrency.record_failure(),
+            Err(_) => {}
+        }
+
+        result
+    }
+
     /// Send a single HTTP request without retry logic.
-    async fn send_request(&self, batch: &LogBatch) -> Result<IngestResponse, ClientError> {
-        let response = self
-            .client
-            .post(&self.ingest_url)
-            .timeout(self.timeout)
-            .json(batch)
-            .send()
-            .await?;
+    ///
+    /// The batch is always serialized to JSON first. If compression is
+    /// enabled and the serialized body exceeds `compression_threshold`, the
+    /// body is gzipped and sent with `Content-Encoding: gzip`; otherwise it's
+    /// sent as plain JSON. Either way, [`LogClient::bytes_uncompressed`] and
+    /// [`LogClient::bytes_on_wire`] are updated so callers can see the
+    /// bandwidth saved.
+    async fn send_request(
+        &self,
+        batch: &LogBatch,
+        timeout: Duration,
+    ) -> Result<IngestResponse, ClientError> {
+        let json_body = serde_json::to_vec(batch)
+            .map_err(|e| ClientError::Config(format!("failed to serialize batch: {}", e)))?;
+        self.bytes_uncompressed
+            .fetch_add(json_body.len() as u64, Ordering::Relaxed);
+
+        let request = self.client.post(&self.ingest_url).timeout(timeout);
+        let request = if self.enable_compression && json_body.len() > self.compression_threshold {
+            let compressed = gzip_encode(&json_body)?;
+            self.bytes_on_wire
+                .fetch_add(compressed.len() as u64, Ordering::Relaxed);
+            request
+                .header(reqwest::header::CONTENT_ENCODING, "gzip")
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(compressed)
+        } else {
+            self.bytes_on_wire
+                .fetch_add(json_body.len() as u64, Ordering::Relaxed);
+            request
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(json_body)
+        };
+
+        let response = request.send().await?;
 
         let status = response.status();
 
@@ -342,6 +732,7 @@ impl LogClient {
             serde_json::from_str(&body).map_err(|e| ClientError::Parse(e.to_string()))
         } else {
             // Handle error response
+            let retry_after = parse_retry_after(response.headers());
             let message = response
                 .text()
                 .await
@@ -350,6 +741,7 @@ impl LogClient {
             Err(ClientError::Status {
                 code: status,
                 message,
+                retry_after,
             })
         }
     }
@@ -380,12 +772,17 @@ impl LogClient {
     /// - Timeouts
     /// - Server errors (5xx)
     /// - Rate limiting (429)
-    fn is_retryable_error(&self, error: &ClientError) -> bool {
+    ///
+    /// `idempotent` gates errors where we can't tell whether the batch
+    /// reached the server before the failure (a timeout): retrying those
+    /// risks double-ingesting a non-idempotent write, so they're only
+    /// retried when the caller's [`RequestConfig`] marks the request safe
+    /// to repeat.
+    fn is_retryable_error(&self, error: &ClientError, idempotent: bool) -> bool {
         match error {
-            ClientError::Request(e) => {
-                e.is_connect() || e.is_timeout() || e.is_request()
-            }
-            ClientError::Timeout => true,
+            ClientError::Request(e) if e.is_timeout() => idempotent,
+            ClientError::Request(e) => e.is_connect() || e.is_request(),
+            ClientError::Timeout => idempotent,
             ClientError::Status { code, .. } => {
                 code.is_server_error() || *code == StatusCode::TOO_MANY_REQUESTS
             }
@@ -393,9 +790,35 @@ impl LogClient {
             ClientError::Parse(_) => false,
             ClientError::RetriesExhausted { .. } => false,
             ClientError::Config(_) => false,
+            ClientError::CircuitOpen(_) => false,
+            ClientError::RateLimited => false,
+        }
+    }
+
+    /// Tokens the retry bucket should charge for retrying after `error`.
+    ///
+    /// Timeouts and connection failures are the slow, expensive-to-discover
+    /// kind, so they cost more than a fast 5xx response.
+    fn retry_token_cost(&self, error: &ClientError) -> u64 {
+        match error {
+            ClientError::Request(e) if e.is_connect() || e.is_timeout() => {
+                RETRY_COST_TIMEOUT_OR_CONNECT
+            }
+            ClientError::Timeout => RETRY_COST_TIMEOUT_OR_CONNECT,
+            _ => RETRY_COST_SERVER_ERROR,
         }
     }
 
+    /// Get the current number of tokens left in the shared retry bucket.
+    pub fn retry_token_level(&self) -> u64 {
+        self.retry_bucket.level()
+    }
+
+    /// Get the number of retries abandoned because the retry bucket was empty.
+    pub fn retries_denied_by_bucket(&self) -> u64 {
+        self.retries_denied.load(Ordering::Relaxed)
+    }
+
     /// Get the configured ingest URL.
     pub fn ingest_url(&self) -> &str {
         &self.ingest_url
@@ -410,6 +833,39 @@ impl LogClient {
     pub fn timeout(&self) -> Duration {
         self.timeout
     }
+
+    /// Get the current circuit breaker state.
+    pub fn circuit_state(&self) -> crate::circuit_breaker::CircuitState {
+        self.circuit_breaker.state()
+    }
+
+    /// Total bytes of serialized JSON batch bodies before any compression.
+    pub fn bytes_uncompressed(&self) -> u64 {
+        self.bytes_uncompressed.load(Ordering::Relaxed)
+    }
+
+    /// Total bytes actually placed on the wire, after gzipping wherever
+    /// compression was applied.
+    pub fn bytes_on_wire(&self) -> u64 {
+        self.bytes_on_wire.load(Ordering::Relaxed)
+    }
+
+    /// The AIMD concurrency limiter's current limit.
+    pub fn concurrency_limit(&self) -> usize {
+        self.concurrency.limit()
+    }
+
+    /// The AIMD concurrency limiter's current EWMA baseline RTT, or `None`
+    /// before the first successful send.
+    pub fn baseline_rtt(&self) -> Option<Duration> {
+        self.concurrency.baseline_rtt()
+    }
+
+    /// Total time spent waiting for a send-rate-limiter token across all
+    /// sends.
+    pub fn time_throttled(&self) -> Duration {
+        Duration::from_nanos(self.throttled_nanos.load(Ordering::Relaxed))
+    }
 }
 
 /// A client wrapper that tracks statistics and provides a higher-level API.
@@ -457,9 +913,55 @@ impl TrackedLogClient {
         }
     }
 
+    /// Send a batch of logs with per-call overrides and update statistics.
+    ///
+    /// See [`LogClient::send_batch_with`] for how `request_config` is applied.
+    pub async fn send_batch_with(
+        &self,
+        batch: LogBatch,
+        request_config: RequestConfig,
+    ) -> Result<IngestResponse, ClientError> {
+        let batch_size = batch.len() as u64;
+
+        match self.inner.send_batch_with(batch, request_config).await {
+            Ok(response) => {
+                if let Ok(mut stats) = self.stats.write() {
+                    stats.batches_sent += 1;
+                    stats.logs_sent += response.accepted;
+                }
+                Ok(response)
+            }
+            Err(e) => {
+                if let Ok(mut stats) = self.stats.write() {
+                    stats.batches_failed += 1;
+                    if let ClientError::RetriesExhausted { attempts, .. } = &e {
+                        stats.retries += (*attempts as u64).saturating_sub(1);
+                    }
+                }
+                Err(e)
+            }
+        }
+    }
+
     /// Get current client statistics.
+    ///
+    /// `circuit_state` is read live from the underlying breaker rather than
+    /// cached, since Open→Half-Open transitions happen purely on elapsed
+    /// time and wouldn't otherwise show up between sends. `retry_tokens_available`,
+    /// `retries_denied_by_bucket`, `bytes_uncompressed`, `bytes_on_wire`,
+    /// `concurrency_limit`, `baseline_rtt`, and `time_throttled` are read
+    /// live for the same reason.
     pub fn stats(&self) -> ClientStats {
-        self.stats.read().map(|s| s.clone()).unwrap_or_default()
+        let mut stats = self.stats.read().map(|s| s.clone()).unwrap_or_default();
+        stats.circuit_state = self.inner.circuit_state();
+        stats.retry_tokens_available = self.inner.retry_token_level();
+        stats.retries_denied_by_bucket = self.inner.retries_denied_by_bucket();
+        stats.bytes_uncompressed = self.inner.bytes_uncompressed();
+        stats.bytes_on_wire = self.inner.bytes_on_wire();
+        stats.concurrency_limit = self.inner.concurrency_limit();
+        stats.baseline_rtt = self.inner.baseline_rtt();
+        stats.time_throttled = self.inner.time_throttled();
+        stats
     }
 
     /// Get a reference to the inner client.
@@ -505,6 +1007,52 @@ pub async fn send_batch(
     Ok(())
 }
 
+/// Whether `error` is a congestion signal for the AIMD concurrency limiter —
+/// a timeout or a 5xx, as opposed to a client error or a local failure that
+/// says nothing about the link's capacity.
+fn is_congestion_signal(error: &ClientError) -> bool {
+    match error {
+        ClientError::Timeout => true,
+        ClientError::Request(e) => e.is_timeout(),
+        ClientError::Status { code, .. } => code.is_server_error(),
+        ClientError::Parse(_)
+        | ClientError::RetriesExhausted { .. }
+        | ClientError::Config(_)
+        | ClientError::CircuitOpen(_)
+        | ClientError::RateLimited => false,
+    }
+}
+
+/// Gzip-compress `data` at the default compression level.
+fn gzip_encode(data: &[u8]) -> Result<Vec<u8>, ClientError> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(data)
+        .map_err(|e| ClientError::Config(format!("failed to gzip request body: {}", e)))?;
+    encoder
+        .finish()
+        .map_err(|e| ClientError::Config(format!("failed to gzip request body: {}", e)))
+}
+
+/// Parse a `Retry-After` response header into a wait duration.
+///
+/// Supports both forms from RFC 7231: an integer number of seconds, and an
+/// HTTP-date (e.g. `Sun, 06 Nov 1994 08:49:37 GMT`) naming the moment to
+/// retry at. Returns `None` if the header is absent, malformed, or (for the
+/// date form) already in the past.
+fn parse_retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    let value = headers.get(reqwest::header::RETRY_AFTER)?.to_str().ok()?;
+
+    if let Ok(seconds) = value.trim().parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let target = chrono::DateTime::parse_from_rfc2822(value.trim()).ok()?;
+    (target.with_timezone(&chrono::Utc) - chrono::Utc::now())
+        .to_std()
+        .ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -525,6 +1073,7 @@ mod tests {
         let err = ClientError::Status {
             code: StatusCode::BAD_REQUEST,
             message: "Invalid JSON".to_string(),
+            retry_after: None,
         };
         assert!(format!("{}", err).contains("400"));
         assert!(format!("{}", err).contains("Invalid JSON"));
@@ -547,6 +1096,13 @@ mod tests {
         assert_eq!(client.ingest_url(), "http://localhost:8000/api/v1/ingest/logs");
         assert_eq!(client.max_retries(), 3);
         assert_eq!(client.timeout(), Duration::from_secs(30));
+        assert_eq!(
+            client.circuit_state(),
+            crate::circuit_breaker::CircuitState::Closed
+        );
+        assert_eq!(client.concurrency_limit(), 1);
+        assert_eq!(client.baseline_rtt(), None);
+        assert_eq!(client.time_throttled(), Duration::ZERO);
     }
 
     #[test]
@@ -602,32 +1158,71 @@ mod tests {
         let config = Config::default();
         let client = LogClient::new(&config).unwrap();
 
-        // Timeout should be retryable
-        assert!(client.is_retryable_error(&ClientError::Timeout));
+        // Timeout is an ambiguous failure: only retryable when the request
+        // is marked idempotent.
+        assert!(client.is_retryable_error(&ClientError::Timeout, true));
+        assert!(!client.is_retryable_error(&ClientError::Timeout, false));
 
         // Parse errors should not be retryable
-        assert!(!client.is_retryable_error(&ClientError::Parse("invalid json".to_string())));
-
-        // 5xx errors should be retryable
-        assert!(client.is_retryable_error(&ClientError::Status {
-            code: StatusCode::INTERNAL_SERVER_ERROR,
-            message: "Server error".to_string(),
-        }));
+        assert!(!client.is_retryable_error(&ClientError::Parse("invalid json".to_string()), true));
+
+        // 5xx errors should be retryable regardless of idempotency
+        assert!(client.is_retryable_error(
+            &ClientError::Status {
+                code: StatusCode::INTERNAL_SERVER_ERROR,
+                message: "Server error".to_string(),
+                retry_after: None,
+            },
+            false
+        ));
 
         // 429 Too Many Requests should be retryable
-        assert!(client.is_retryable_error(&ClientError::Status {
-            code: StatusCode::TOO_MANY_REQUESTS,
-            message: "Rate limited".to_string(),
-        }));
+        assert!(client.is_retryable_error(
+            &ClientError::Status {
+                code: StatusCode::TOO_MANY_REQUESTS,
+                message: "Rate limited".to_string(),
+                retry_after: None,
+            },
+            false
+        ));
 
         // 4xx errors (except 429) should not be retryable
-        assert!(!client.is_retryable_error(&ClientError::Status {
-            code: StatusCode::BAD_REQUEST,
-            message: "Bad request".to_string(),
-        }));
+        assert!(!client.is_retryable_error(
+            &ClientError::Status {
+                code: StatusCode::BAD_REQUEST,
+                message: "Bad request".to_string(),
+                retry_after: None,
+            },
+            true
+        ));
 
         // Config errors should not be retryable
-        assert!(!client.is_retryable_error(&ClientError::Config("config error".to_string())));
+        assert!(!client.is_retryable_error(&ClientError::Config("config error".to_string()), true));
+
+        // A rejection from an open circuit breaker should not be retried
+        assert!(!client.is_retryable_error(
+            &ClientError::CircuitOpen(crate::circuit_breaker::CircuitOpenError {
+                retry_after: Duration::from_secs(5),
+            }),
+            true
+        ));
+    }
+
+    #[test]
+    fn test_request_config_overrides_client_defaults() {
+        let request_config = RequestConfig::default()
+            .max_retries(10)
+            .timeout(Duration::from_secs(1))
+            .idempotent(false);
+
+        assert_eq!(request_config.max_retries, Some(10));
+        assert_eq!(request_config.timeout, Some(Duration::from_secs(1)));
+        assert!(!request_config.idempotent);
+
+        let default_config = RequestConfig::default();
+        assert_eq!(default_config.max_retries, None);
+        assert_eq!(default_config.timeout, None);
+        assert!(default_config.idempotent);
     }
 
     #[test]
@@ -674,6 +1269,47 @@ mod tests {
         assert_eq!(stats.failed_attempts, 0);
         assert_eq!(stats.retries, 0);
         assert_eq!(stats.batches_failed, 0);
+        assert_eq!(
+            stats.circuit_state,
+            crate::circuit_breaker::CircuitState::Closed
+        );
+        assert_eq!(stats.retry_tokens_available, 0);
+        assert_eq!(stats.retries_denied_by_bucket, 0);
+        assert_eq!(stats.bytes_uncompressed, 0);
+        assert_eq!(stats.bytes_on_wire, 0);
+        assert_eq!(stats.concurrency_limit, 0);
+        assert_eq!(stats.baseline_rtt, None);
+        assert_eq!(stats.time_throttled, Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_tracked_client_stats_reports_live_circuit_state() {
+        let config = Config::default();
+        let client = TrackedLogClient::new(&config).unwrap();
+
+        assert_eq!(
+            client.stats().circuit_state,
+            crate::circuit_breaker::CircuitState::Closed
+        );
+    }
+
+    #[test]
+    fn test_retry_token_bucket_acquire_and_refund() {
+        let bucket = RetryTokenBucket::new(10);
+        assert_eq!(bucket.level(), 10);
+
+        assert!(bucket.try_acquire(6));
+        assert_eq!(bucket.level(), 4);
+
+        assert!(!bucket.try_acquire(5));
+        assert_eq!(bucket.level(), 4);
+
+        bucket.refund(3);
+        assert_eq!(bucket.level(), 7);
+
+        // Refunding past capacity is clamped.
+        bucket.refund(100);
+        assert_eq!(bucket.level(), 10);
     }
 
     #[tokio::test]
@@ -687,4 +1323,107 @@ mod tests {
         assert_eq!(stats.batches_sent, 0);
         assert_eq!(stats.logs_sent, 0);
     }
+
+    #[test]
+    fn test_parse_retry_after_seconds_form() {
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(reqwest::header::RETRY_AFTER, "120".parse().unwrap());
+
+        assert_eq!(parse_retry_after(&headers), Some(Duration::from_secs(120)));
+    }
+
+    #[test]
+    fn test_parse_retry_after_http_date_form() {
+        let target = chrono::Utc::now() + chrono::Duration::seconds(30);
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(
+            reqwest::header::RETRY_AFTER,
+            target.to_rfc2822().parse().unwrap(),
+        );
+
+        let parsed = parse_retry_after(&headers).expect("HTTP-date Retry-After should parse");
+        // Allow a little slack for the time spent building/parsing the header.
+        assert!(parsed.as_secs() >= 28 && parsed.as_secs() <= 30);
+    }
+
+    #[test]
+    fn test_parse_retry_after_missing_or_unparseable() {
+        let headers = reqwest::header::HeaderMap::new();
+        assert_eq!(parse_retry_after(&headers), None);
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(reqwest::header::RETRY_AFTER, "not-a-duration".parse().unwrap());
+        assert_eq!(parse_retry_after(&headers), None);
+    }
+
+    #[test]
+    fn test_gzip_encode_round_trips() {
+        let original = b"hello world, hello world, hello world".repeat(20);
+        let compressed = gzip_encode(&original).unwrap();
+
+        assert!(compressed.len() < original.len());
+
+        use std::io::Read;
+        let mut decoder = flate2::read::GzDecoder::new(&compressed[..]);
+        let mut decoded = Vec::new();
+        decoder.read_to_end(&mut decoded).unwrap();
+        assert_eq!(decoded, original);
+    }
+
+    #[test]
+    fn test_client_with_settings_enables_compression_by_default() {
+        let client =
+            LogClient::with_settings("http://example.com/api/logs", Duration::from_secs(5), 1)
+                .unwrap();
+
+        assert!(client.enable_compression);
+        assert_eq!(client.compression_threshold, DEFAULT_COMPRESSION_THRESHOLD_BYTES);
+    }
+
+    #[tokio::test]
+    async fn test_send_batch_with_fails_fast_when_rate_limit_exhausted() {
+        let mut client =
+            LogClient::with_settings("http://127.0.0.1:1/api/logs", Duration::from_millis(50), 0)
+                .unwrap();
+        client.rate_limiter = TokenBucket::new(TokenBucketConfig {
+            rate_per_sec: 1.0,
+            burst: 1.0,
+        });
+        assert!(client.rate_limiter.try_acquire()); // drain the only token
+
+        let batch = create_test_batch(1);
+        let result = client
+            .send_batch_with(batch, RequestConfig::default().wait_for_rate_limit(false))
+            .await;
+
+        assert!(matches!(result, Err(ClientError::RateLimited)));
+    }
+
+    #[tokio::test]
+    async fn test_send_batch_with_waits_for_rate_limit_by_default() {
+        let mut client =
+            LogClient::with_settings("http://127.0.0.1:1/api/logs", Duration::from_millis(50), 0)
+                .unwrap();
+        client.rate_limiter = TokenBucket::new(TokenBucketConfig {
+            rate_per_sec: 1000.0,
+            burst: 1.0,
+        });
+        assert!(client.rate_limiter.try_acquire()); // drain the only token
+
+        let batch = create_test_batch(1);
+        let result = client.send_batch_with(batch, RequestConfig::default()).await;
+
+        // Waits rather than fails fast, so it surfaces a network error instead.
+        assert!(!matches!(result, Err(ClientError::RateLimited)));
+        assert!(client.time_throttled() > Duration::ZERO);
+    }
+
+    #[test]
+    fn test_parse_retry_after_past_date_is_none() {
+        let past = chrono::Utc::now() - chrono::Duration::seconds(60);
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(reqwest::header::RETRY_AFTER, past.to_rfc2822().parse().unwrap());
+
+        assert_eq!(parse_retry_after(&headers), None);
+    }
 }
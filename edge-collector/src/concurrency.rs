@@ -0,0 +1,302 @@
+//! Adaptive (AIMD) concurrency limiting for [`crate::client::LogClient`] sends.
+//!
+//! A fixed connection pool doesn't adapt to the endpoint's changing capacity:
+//! under load it either underutilizes a healthy link or overwhelms a
+//! struggling one. [`AimdLimiter`] instead tracks a concurrency limit that
+//! starts at 1 and adjusts after every send, the same additive-increase/
+//! multiplicative-decrease scheme TCP congestion control uses: a success
+//! that lands near the observed baseline RTT while the limiter is fully
+//! saturated grows the limit by 1; a timeout, 5xx, or a success whose RTT
+//! blows past the baseline multiplies the limit by a backoff factor, floored
+//! at 1. A [`tokio::sync::Semaphore`] enforces the limit; growing adds
+//! permits immediately, shrinking forgets permits as they're returned so
+//! in-flight requests are never preempted.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Weight given to a sample that's *lower* than the current baseline RTT —
+/// the baseline should snap down quickly once the link clears up.
+const EWMA_DOWN_ALPHA: f64 = 0.25;
+
+/// Weight given to a sample that's *higher* than the current baseline RTT —
+/// the baseline should drift up slowly, so a few slow samples aren't
+/// mistaken for a new, permanently higher floor.
+const EWMA_UP_ALPHA: f64 = 0.05;
+
+/// Bounds and tuning knobs for [`AimdLimiter`].
+#[derive(Debug, Clone, Copy)]
+pub struct AimdLimiterConfig {
+    /// Ceiling on the concurrency limit.
+    pub max_limit: usize,
+
+    /// A success only counts as "congested" once its RTT exceeds the
+    /// baseline by this multiple.
+    pub congestion_multiple: f64,
+
+    /// Multiplicative factor applied to the limit on backoff (e.g. `0.9`
+    /// cuts it by 10%).
+    pub backoff_factor: f64,
+}
+
+impl Default for AimdLimiterConfig {
+    fn default() -> Self {
+        Self {
+            max_limit: 64,
+            congestion_multiple: 2.0,
+            backoff_factor: 0.9,
+        }
+    }
+}
+
+struct Inner {
+    limit: usize,
+    baseline_rtt: Option<Duration>,
+}
+
+/// An additive-increase/multiplicative-decrease concurrency limiter.
+///
+/// Cloning shares the same underlying state and semaphore, the same pattern
+/// as [`crate::circuit_breaker::CircuitBreaker`].
+#[derive(Clone)]
+pub struct AimdLimiter {
+    config: AimdLimiterConfig,
+    semaphore: Arc<Semaphore>,
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl Default for AimdLimiter {
+    fn default() -> Self {
+        Self::new(AimdLimiterConfig::default())
+    }
+}
+
+impl AimdLimiter {
+    /// Create a limiter starting at a concurrency limit of 1.
+    pub fn new(config: AimdLimiterConfig) -> Self {
+        Self {
+            config,
+            semaphore: Arc::new(Semaphore::new(1)),
+            inner: Arc::new(Mutex::new(Inner {
+                limit: 1,
+                baseline_rtt: None,
+            })),
+        }
+    }
+
+    /// Acquire a permit, waiting for capacity if the limiter is saturated.
+    pub async fn acquire(&self) -> AimdPermit {
+        let permit = self
+            .semaphore
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("semaphore is never closed");
+        // Saturated iff no spare permits are sitting idle right now, i.e.
+        // every other permit is already checked out by another send.
+        let saturated = self.semaphore.available_permits() == 0;
+        AimdPermit {
+            _permit: permit,
+            saturated,
+        }
+    }
+
+    /// Report a successful send that took `rtt`, growing the limit if the
+    /// limiter was saturated and the link isn't congested, or backing off if
+    /// `rtt` blew past the baseline.
+    pub fn record_success(&self, rtt: Duration, permit: &AimdPermit) {
+        let mut inner = self.inner.lock().expect("AIMD limiter mutex poisoned");
+
+        let congested = inner
+            .baseline_rtt
+            .is_some_and(|baseline| rtt > baseline.mul_f64(self.config.congestion_multiple));
+
+        inner.baseline_rtt = Some(match inner.baseline_rtt {
+            None => rtt,
+            Some(baseline) if rtt < baseline => {
+                baseline.mul_f64(1.0 - EWMA_DOWN_ALPHA) + rtt.mul_f64(EWMA_DOWN_ALPHA)
+            }
+            Some(baseline) => baseline.mul_f64(1.0 - EWMA_UP_ALPHA) + rtt.mul_f64(EWMA_UP_ALPHA),
+        });
+
+        if congested {
+            self.shrink(&mut inner);
+        } else if permit.saturated && inner.limit < self.config.max_limit {
+            inner.limit += 1;
+            self.semaphore.add_permits(1);
+        }
+    }
+
+    /// Report a failed send (timeout or 5xx), unconditionally backing off.
+    pub fn record_failure(&self) {
+        let mut inner = self.inner.lock().expect("AIMD limiter mutex poisoned");
+        self.shrink(&mut inner);
+    }
+
+    /// Multiply the limit by `backoff_factor`, floored at 1, and forget the
+    /// difference in permits as they're returned to the semaphore.
+    fn shrink(&self, inner: &mut Inner) {
+        let new_limit = ((inner.limit as f64) * self.config.backoff_factor)
+            .floor()
+            .max(1.0) as usize;
+        let delta = inner.limit.saturating_sub(new_limit);
+        inner.limit = new_limit;
+
+        if delta > 0 {
+            let semaphore = self.semaphore.clone();
+            tokio::spawn(async move {
+                if let Ok(permit) = semaphore.acquire_many_owned(delta as u32).await {
+                    permit.forget();
+                }
+            });
+        }
+    }
+
+    /// The current concurrency limit.
+    pub fn limit(&self) -> usize {
+        self.inner.lock().expect("AIMD limiter mutex poisoned").limit
+    }
+
+    /// The current EWMA baseline RTT, or `None` before the first success.
+    pub fn baseline_rtt(&self) -> Option<Duration> {
+        self.inner
+            .lock()
+            .expect("AIMD limiter mutex poisoned")
+            .baseline_rtt
+    }
+}
+
+/// A held permit from an [`AimdLimiter`], tracking whether every other
+/// permit was checked out at acquisition time.
+pub struct AimdPermit {
+    _permit: OwnedSemaphorePermit,
+    saturated: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_starts_at_limit_one() {
+        let limiter = AimdLimiter::default();
+        assert_eq!(limiter.limit(), 1);
+        assert_eq!(limiter.baseline_rtt(), None);
+    }
+
+    #[tokio::test]
+    async fn test_saturated_success_grows_limit() {
+        let limiter = AimdLimiter::default();
+
+        let permit = limiter.acquire().await;
+        assert!(permit.saturated); // only permit, so acquiring it saturates the limiter
+        limiter.record_success(Duration::from_millis(10), &permit);
+
+        assert_eq!(limiter.limit(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_unsaturated_success_does_not_grow_limit() {
+        let limiter = AimdLimiter::new(AimdLimiterConfig {
+            max_limit: 64,
+            congestion_multiple: 2.0,
+            backoff_factor: 0.9,
+        });
+        limiter.semaphore.add_permits(1); // pretend the limit is already 2
+
+        let permit = limiter.acquire().await;
+        assert!(!permit.saturated); // a spare permit was available
+        limiter.record_success(Duration::from_millis(10), &permit);
+
+        assert_eq!(limiter.limit(), 1); // unchanged: inner.limit tracking wasn't touched above
+    }
+
+    #[tokio::test]
+    async fn test_failure_shrinks_limit() {
+        let limiter = AimdLimiter::default();
+
+        // Grow to 10 first. The limit only grows on a saturated acquire, so
+        // each round holds every permit and reports success on the last one.
+        for _ in 0..9 {
+            let mut held = Vec::new();
+            for _ in 0..limiter.limit() {
+                held.push(limiter.acquire().await);
+            }
+            limiter.record_success(Duration::from_millis(10), held.last().unwrap());
+        }
+        assert_eq!(limiter.limit(), 10);
+
+        limiter.record_failure();
+        assert_eq!(limiter.limit(), 9); // floor(10 * 0.9)
+    }
+
+    #[tokio::test]
+    async fn test_limit_floors_at_one() {
+        let limiter = AimdLimiter::default();
+
+        for _ in 0..20 {
+            limiter.record_failure();
+        }
+
+        assert_eq!(limiter.limit(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_limit_capped_at_max() {
+        let limiter = AimdLimiter::new(AimdLimiterConfig {
+            max_limit: 3,
+            ..AimdLimiterConfig::default()
+        });
+
+        // Saturate the limiter every round so each success may grow it.
+        for _ in 0..10 {
+            let mut held = Vec::new();
+            for _ in 0..limiter.limit() {
+                held.push(limiter.acquire().await);
+            }
+            limiter.record_success(Duration::from_millis(10), held.last().unwrap());
+        }
+
+        assert_eq!(limiter.limit(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_congested_rtt_shrinks_instead_of_growing() {
+        let limiter = AimdLimiter::default();
+
+        // Establish a fast baseline.
+        {
+            let permit = limiter.acquire().await;
+            limiter.record_success(Duration::from_millis(10), &permit);
+        }
+        assert_eq!(limiter.limit(), 2);
+
+        // A much slower RTT (> 2x baseline) should shrink, not grow, even
+        // while saturated.
+        let permit = limiter.acquire().await;
+        let permit2 = limiter.acquire().await;
+        assert!(permit2.saturated);
+        limiter.record_success(Duration::from_millis(200), &permit2);
+        drop(permit);
+
+        assert_eq!(limiter.limit(), 1); // floor(2 * 0.9)
+    }
+
+    #[tokio::test]
+    async fn test_baseline_rtt_tracks_minimum_with_slow_rise() {
+        let limiter = AimdLimiter::default();
+
+        let permit = limiter.acquire().await;
+        limiter.record_success(Duration::from_millis(100), &permit);
+        assert_eq!(limiter.baseline_rtt(), Some(Duration::from_millis(100)));
+
+        // A faster sample pulls the baseline down quickly.
+        let permit = limiter.acquire().await;
+        limiter.record_success(Duration::from_millis(50), &permit);
+        let baseline = limiter.baseline_rtt().unwrap();
+        assert!(baseline < Duration::from_millis(100));
+        assert!(baseline > Duration::from_millis(50));
+    }
+}
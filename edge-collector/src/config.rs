@@ -2,9 +2,32 @@
 //!
 //! This module provides environment-based configuration for the edge collector,
 //! including API URL, batch size, and flush interval settings.
+//!
+//! [`Config::from_env`] actually layers three sources before CLI flags ever
+//! get a look in (see [`Config::from_env_and_args`]):
+//! 1. A structured `EDGE_COLLECTOR_CONFIG_FILE` (TOML or YAML, picked by
+//!    extension) supplies new defaults for any field it sets.
+//! 2. An `EDGE_COLLECTOR_DOTENV` (default `.env`) is loaded into the
+//!    process environment, filling in any `EDGE_COLLECTOR_*` variable that
+//!    isn't already set — real environment variables always win over it.
+//! 3. `EDGE_COLLECTOR_*` environment variables are read as before, falling
+//!    back to the config-file default (or the hardcoded default) when unset.
+//!
+//! [`ConfigHandle`] wraps a `Config` behind a lock so it can be reloaded
+//! without restarting the service; [`watch_config_file`] polls
+//! `EDGE_COLLECTOR_CONFIG_FILE` for changes and swaps in a freshly validated
+//! `Config` whenever it's edited, mirroring [`crate::generator_config::watch`].
 
 use std::env;
-use std::time::Duration;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+
+use clap::Parser;
+use serde::Deserialize;
+use tracing::{info, warn};
+
+use crate::tuner::AdaptiveTuningConfig;
 
 /// Default API URL for the Python FastAPI backend
 const DEFAULT_API_URL: &str = "http://localhost:8000";
@@ -24,6 +47,46 @@ const MIN_FLUSH_INTERVAL_SECS: u64 = 1;
 /// Maximum flush interval to ensure reasonable data freshness
 const MAX_FLUSH_INTERVAL_SECS: u64 = 300;
 
+/// Default number of consecutive failures before the circuit breaker trips to Open.
+const DEFAULT_CB_CONSECUTIVE_FAILURES: u32 = 5;
+
+/// Maximum allowed consecutive-failure threshold.
+const MAX_CB_CONSECUTIVE_FAILURES: u32 = 1_000;
+
+/// Default duration the circuit breaker stays Open before probing again, in seconds.
+const DEFAULT_CB_OPEN_DURATION_SECS: u64 = 30;
+
+/// Minimum allowed Open duration, to avoid the breaker flapping.
+const MIN_CB_OPEN_DURATION_SECS: u64 = 1;
+
+/// Maximum allowed Open duration.
+const MAX_CB_OPEN_DURATION_SECS: u64 = 3_600;
+
+/// Default number of trial requests allowed through while Half-Open.
+const DEFAULT_CB_HALF_OPEN_MAX_PROBES: u32 = 1;
+
+/// Maximum allowed Half-Open probe count.
+const MAX_CB_HALF_OPEN_MAX_PROBES: u32 = 100;
+
+/// Default size, in bytes, above which a request body is gzip-compressed.
+const DEFAULT_COMPRESSION_THRESHOLD_BYTES: usize = 1024;
+
+/// Maximum allowed compression threshold — past this, compression is
+/// effectively disabled for any realistic batch, which is better spelled
+/// `enable_compression = false`.
+const MAX_COMPRESSION_THRESHOLD_BYTES: usize = 100 * 1024 * 1024;
+
+/// Default sustained send rate, in requests per second, before a send waits
+/// on the client-side rate limiter.
+const DEFAULT_MAX_REQUESTS_PER_SECOND: f64 = 50.0;
+
+/// Default burst capacity for the client-side rate limiter.
+const DEFAULT_RATE_LIMIT_BURST: f64 = 100.0;
+
+/// Maximum allowed send rate or burst capacity — past this, rate limiting
+/// is effectively a no-op for any realistic edge deployment.
+const MAX_RATE_LIMIT_VALUE: f64 = 1_000_000.0;
+
 /// Configuration for the Edge Collector service.
 ///
 /// All settings can be configured via environment variables:
@@ -49,6 +112,28 @@ pub struct Config {
 
     /// Maximum number of retry attempts for failed requests
     pub max_retries: u32,
+
+    /// Number of consecutive failures before the circuit breaker trips to Open
+    pub cb_consecutive_failures: u32,
+
+    /// How long the circuit breaker stays Open before allowing Half-Open probes
+    pub cb_open_duration: Duration,
+
+    /// Number of trial requests allowed through while Half-Open
+    pub cb_half_open_max_probes: u32,
+
+    /// Whether to gzip-compress request bodies that exceed `compression_threshold`
+    pub enable_compression: bool,
+
+    /// Size, in bytes, of the serialized JSON body above which it's gzipped
+    pub compression_threshold: usize,
+
+    /// Sustained send rate, in requests per second, enforced by the
+    /// client-side rate limiter on initial sends (not retries)
+    pub max_requests_per_second: f64,
+
+    /// Burst capacity for the client-side rate limiter
+    pub rate_limit_burst: f64,
 }
 
 /// Error type for configuration loading failures
@@ -90,9 +175,21 @@ impl Config {
     /// println!("API URL: {}", config.api_url);
     /// ```
     pub fn from_env() -> Result<Self, ConfigError> {
+        Self::from_env_with_config_path(None)
+    }
+
+    /// Like [`Config::from_env`], but `config_path` (if set) overrides
+    /// `EDGE_COLLECTOR_CONFIG_FILE` for this load — used by
+    /// [`Config::from_env_and_args`] to honor a `--config` flag.
+    fn from_env_with_config_path(config_path: Option<&Path>) -> Result<Self, ConfigError> {
+        load_dotenv_file();
+        let file_defaults = load_config_file_patch(config_path)?;
+
         // Load API URL
         let api_url = env::var("EDGE_COLLECTOR_API_URL")
-            .unwrap_or_else(|_| DEFAULT_API_URL.to_string());
+            .ok()
+            .or_else(|| file_defaults.api_url.clone())
+            .unwrap_or_else(|| DEFAULT_API_URL.to_string());
 
         // Validate and normalize API URL
         let api_url = api_url.trim_end_matches('/').to_string();
@@ -101,24 +198,62 @@ impl Config {
         let ingest_url = format!("{}/api/v1/ingest/logs", api_url);
 
         // Load and parse batch size
-        let batch_size = Self::parse_batch_size()?;
+        let batch_size = Self::parse_batch_size(file_defaults.batch_size.unwrap_or(DEFAULT_BATCH_SIZE))?;
 
         // Load and parse flush interval
-        let flush_interval_secs = Self::parse_flush_interval()?;
+        let flush_interval_secs = Self::parse_flush_interval(
+            file_defaults.flush_interval_secs.unwrap_or(DEFAULT_FLUSH_INTERVAL_SECS),
+        )?;
         let flush_interval = Duration::from_secs(flush_interval_secs);
 
         // Load request timeout (optional, defaults to 30 seconds)
         let request_timeout_secs: u64 = env::var("EDGE_COLLECTOR_REQUEST_TIMEOUT_SECS")
             .ok()
             .and_then(|v| v.parse().ok())
-            .unwrap_or(30);
+            .unwrap_or_else(|| file_defaults.request_timeout_secs.unwrap_or(30));
         let request_timeout = Duration::from_secs(request_timeout_secs);
 
         // Load max retries (optional, defaults to 3)
         let max_retries: u32 = env::var("EDGE_COLLECTOR_MAX_RETRIES")
             .ok()
             .and_then(|v| v.parse().ok())
-            .unwrap_or(3);
+            .unwrap_or_else(|| file_defaults.max_retries.unwrap_or(3));
+
+        // Load and parse circuit breaker settings
+        let cb_consecutive_failures = Self::parse_cb_consecutive_failures(
+            file_defaults.cb_consecutive_failures.unwrap_or(DEFAULT_CB_CONSECUTIVE_FAILURES),
+        )?;
+        let cb_open_duration_secs = Self::parse_cb_open_duration_secs(
+            file_defaults.cb_open_duration_secs.unwrap_or(DEFAULT_CB_OPEN_DURATION_SECS),
+        )?;
+        let cb_open_duration = Duration::from_secs(cb_open_duration_secs);
+        let cb_half_open_max_probes = Self::parse_cb_half_open_max_probes(
+            file_defaults.cb_half_open_max_probes.unwrap_or(DEFAULT_CB_HALF_OPEN_MAX_PROBES),
+        )?;
+
+        // Load compression settings (optional, defaults to enabled at 1 KiB)
+        let enable_compression: bool = env::var("EDGE_COLLECTOR_ENABLE_COMPRESSION")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or_else(|| file_defaults.enable_compression.unwrap_or(true));
+        let compression_threshold = Self::parse_compression_threshold(
+            file_defaults
+                .compression_threshold_bytes
+                .unwrap_or(DEFAULT_COMPRESSION_THRESHOLD_BYTES),
+        )?;
+
+        // Load client-side rate limiter settings (optional, defaults to
+        // 50 req/s with a burst of 100)
+        let max_requests_per_second = Self::parse_rate_limit_value(
+            "EDGE_COLLECTOR_MAX_REQUESTS_PER_SECOND",
+            file_defaults
+                .max_requests_per_second
+                .unwrap_or(DEFAULT_MAX_REQUESTS_PER_SECOND),
+        )?;
+        let rate_limit_burst = Self::parse_rate_limit_value(
+            "EDGE_COLLECTOR_RATE_LIMIT_BURST",
+            file_defaults.rate_limit_burst.unwrap_or(DEFAULT_RATE_LIMIT_BURST),
+        )?;
 
         Ok(Self {
             api_url,
@@ -127,81 +262,737 @@ impl Config {
             flush_interval,
             request_timeout,
             max_retries,
+            cb_consecutive_failures,
+            cb_open_duration,
+            cb_half_open_max_probes,
+            enable_compression,
+            compression_threshold,
+            max_requests_per_second,
+            rate_limit_burst,
         })
     }
 
-    /// Parse batch size from environment variable with validation.
-    fn parse_batch_size() -> Result<usize, ConfigError> {
+    /// Parse batch size from the environment variable, falling back to
+    /// `default` (already blended from any config file) when unset.
+    fn parse_batch_size(default: usize) -> Result<usize, ConfigError> {
         let env_var = "EDGE_COLLECTOR_BATCH_SIZE";
 
-        match env::var(env_var) {
-            Ok(value) => {
-                let batch_size: usize = value.parse().map_err(|_| ConfigError {
-                    message: format!("'{}' is not a valid number", value),
-                    env_var: Some(env_var.to_string()),
-                })?;
-
-                if batch_size == 0 {
-                    return Err(ConfigError {
-                        message: "batch size must be greater than 0".to_string(),
-                        env_var: Some(env_var.to_string()),
-                    });
-                }
+        let batch_size = match env::var(env_var) {
+            Ok(value) => value.parse().map_err(|_| ConfigError {
+                message: format!("'{}' is not a valid number", value),
+                env_var: Some(env_var.to_string()),
+            })?,
+            Err(_) => default,
+        };
+        validate_batch_size(batch_size, env_var)?;
+        Ok(batch_size)
+    }
 
-                if batch_size > MAX_BATCH_SIZE {
-                    return Err(ConfigError {
-                        message: format!(
-                            "batch size {} exceeds maximum allowed ({})",
-                            batch_size, MAX_BATCH_SIZE
-                        ),
-                        env_var: Some(env_var.to_string()),
-                    });
-                }
+    /// Parse flush interval from the environment variable, falling back to
+    /// `default` (already blended from any config file) when unset.
+    fn parse_flush_interval(default: u64) -> Result<u64, ConfigError> {
+        let env_var = "EDGE_COLLECTOR_FLUSH_INTERVAL_SECS";
 
-                Ok(batch_size)
-            }
-            Err(_) => Ok(DEFAULT_BATCH_SIZE),
+        let interval = match env::var(env_var) {
+            Ok(value) => value.parse().map_err(|_| ConfigError {
+                message: format!("'{}' is not a valid number", value),
+                env_var: Some(env_var.to_string()),
+            })?,
+            Err(_) => default,
+        };
+        validate_flush_interval(interval, env_var)?;
+        Ok(interval)
+    }
+
+    /// Parse the circuit breaker's consecutive-failure threshold from the
+    /// environment, falling back to `default` when unset.
+    fn parse_cb_consecutive_failures(default: u32) -> Result<u32, ConfigError> {
+        let env_var = "EDGE_COLLECTOR_CB_CONSECUTIVE_FAILURES";
+
+        let threshold = match env::var(env_var) {
+            Ok(value) => value.parse().map_err(|_| ConfigError {
+                message: format!("'{}' is not a valid number", value),
+                env_var: Some(env_var.to_string()),
+            })?,
+            Err(_) => default,
+        };
+        validate_cb_consecutive_failures(threshold, env_var)?;
+        Ok(threshold)
+    }
+
+    /// Parse the circuit breaker's Open-state duration from the environment,
+    /// falling back to `default` when unset.
+    fn parse_cb_open_duration_secs(default: u64) -> Result<u64, ConfigError> {
+        let env_var = "EDGE_COLLECTOR_CB_OPEN_DURATION_SECS";
+
+        let duration = match env::var(env_var) {
+            Ok(value) => value.parse().map_err(|_| ConfigError {
+                message: format!("'{}' is not a valid number", value),
+                env_var: Some(env_var.to_string()),
+            })?,
+            Err(_) => default,
+        };
+        validate_cb_open_duration(duration, env_var)?;
+        Ok(duration)
+    }
+
+    /// Parse the circuit breaker's Half-Open probe count from the environment,
+    /// falling back to `default` when unset.
+    fn parse_cb_half_open_max_probes(default: u32) -> Result<u32, ConfigError> {
+        let env_var = "EDGE_COLLECTOR_CB_HALF_OPEN_MAX_PROBES";
+
+        let probes = match env::var(env_var) {
+            Ok(value) => value.parse().map_err(|_| ConfigError {
+                message: format!("'{}' is not a valid number", value),
+                env_var: Some(env_var.to_string()),
+            })?,
+            Err(_) => default,
+        };
+        validate_cb_half_open_max_probes(probes, env_var)?;
+        Ok(probes)
+    }
+
+    /// Parse the compression threshold from the environment, falling back to
+    /// `default` when unset.
+    fn parse_compression_threshold(default: usize) -> Result<usize, ConfigError> {
+        let env_var = "EDGE_COLLECTOR_COMPRESSION_THRESHOLD_BYTES";
+
+        let threshold = match env::var(env_var) {
+            Ok(value) => value.parse().map_err(|_| ConfigError {
+                message: format!("'{}' is not a valid number", value),
+                env_var: Some(env_var.to_string()),
+            })?,
+            Err(_) => default,
+        };
+        validate_compression_threshold(threshold, env_var)?;
+        Ok(threshold)
+    }
+
+    /// Parse a rate limiter value (`max_requests_per_second` or
+    /// `rate_limit_burst`) from `env_var`, falling back to `default` when unset.
+    fn parse_rate_limit_value(env_var: &str, default: f64) -> Result<f64, ConfigError> {
+        let value = match env::var(env_var) {
+            Ok(value) => value.parse().map_err(|_| ConfigError {
+                message: format!("'{}' is not a valid number", value),
+                env_var: Some(env_var.to_string()),
+            })?,
+            Err(_) => default,
+        };
+        validate_rate_limit_value(value, env_var)?;
+        Ok(value)
+    }
+
+    /// Load configuration from environment variables, then overlay
+    /// command-line flags on top.
+    ///
+    /// Precedence, lowest to highest: baked-in defaults, a `--config` file
+    /// (or `EDGE_COLLECTOR_CONFIG_FILE` if `--config` isn't given),
+    /// `EDGE_COLLECTOR_*` environment variables (see [`Config::from_env`]),
+    /// then the rest of the CLI flags. Recognized flags: `--config`,
+    /// `--api-url`, `--batch-size`, `--flush-interval-secs`,
+    /// `--request-timeout-secs`, `--max-retries`, `--cb-consecutive-failures`,
+    /// `--cb-open-duration-secs`, `--cb-half-open-max-probes`,
+    /// `--enable-compression`, `--compression-threshold-bytes`,
+    /// `--max-requests-per-second`, `--rate-limit-burst`. Built on `clap`
+    /// (see [`CliArgs`]), so `--flag value` and `--flag=value` both work,
+    /// `--help` prints usage, and an unrecognized flag is an error rather
+    /// than being silently ignored. `args` should not include `argv[0]`, so
+    /// callers can pass `std::env::args().skip(1)` directly.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError` under the same conditions as `Config::from_env`,
+    /// plus a malformed or out-of-range CLI flag value, a flag missing its
+    /// value, or an unrecognized flag.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use edge_collector::config::Config;
+    ///
+    /// let config = Config::from_env_and_args(std::env::args().skip(1))
+    ///     .expect("Failed to load config");
+    /// ```
+    pub fn from_env_and_args<I, S>(args: I) -> Result<Self, ConfigError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<std::ffi::OsString> + Clone,
+    {
+        let overrides = parse_cli_args(args)?;
+        let mut config = Self::from_env_with_config_path(overrides.config_path.as_deref())?;
+        overrides.apply_to(&mut config);
+        Ok(config)
+    }
+
+    /// Build an [`AdaptiveTuningConfig`] for this collector, or `None` if
+    /// adaptive tuning isn't enabled.
+    ///
+    /// Gated behind `EDGE_COLLECTOR_ADAPTIVE=true` so the feature is opt-in;
+    /// the returned bounds are clamped to this module's own
+    /// [`MAX_BATCH_SIZE`] and [`MAX_FLUSH_INTERVAL_SECS`] rather than
+    /// [`AdaptiveTuningConfig::default`]'s more permissive ones, so a tuned
+    /// batch/interval can never exceed what `--batch-size`/
+    /// `--flush-interval-secs` would accept.
+    pub fn optimize_for(&self) -> Option<AdaptiveTuningConfig> {
+        let enabled = env::var("EDGE_COLLECTOR_ADAPTIVE")
+            .map(|v| v == "true")
+            .unwrap_or(false);
+
+        if !enabled {
+            return None;
         }
+
+        Some(AdaptiveTuningConfig {
+            max_batch_size: MAX_BATCH_SIZE,
+            max_flush_interval: Duration::from_secs(MAX_FLUSH_INTERVAL_SECS),
+            ..AdaptiveTuningConfig::default()
+        })
     }
+}
 
-    /// Parse flush interval from environment variable with validation.
-    fn parse_flush_interval() -> Result<u64, ConfigError> {
-        let env_var = "EDGE_COLLECTOR_FLUSH_INTERVAL_SECS";
+/// Partial, all-optional view of [`Config`] loadable from a structured file.
+///
+/// Any field left unset here falls back to the hardcoded default, same as an
+/// unset environment variable.
+#[derive(Debug, Clone, Default, Deserialize)]
+struct ConfigFilePatch {
+    api_url: Option<String>,
+    batch_size: Option<usize>,
+    flush_interval_secs: Option<u64>,
+    request_timeout_secs: Option<u64>,
+    max_retries: Option<u32>,
+    cb_consecutive_failures: Option<u32>,
+    cb_open_duration_secs: Option<u64>,
+    cb_half_open_max_probes: Option<u32>,
+    enable_compression: Option<bool>,
+    compression_threshold_bytes: Option<usize>,
+    max_requests_per_second: Option<f64>,
+    rate_limit_burst: Option<f64>,
+}
 
-        match env::var(env_var) {
-            Ok(value) => {
-                let interval: u64 = value.parse().map_err(|_| ConfigError {
-                    message: format!("'{}' is not a valid number", value),
-                    env_var: Some(env_var.to_string()),
-                })?;
-
-                if interval < MIN_FLUSH_INTERVAL_SECS {
-                    return Err(ConfigError {
-                        message: format!(
-                            "flush interval {} is below minimum ({}s)",
-                            interval, MIN_FLUSH_INTERVAL_SECS
-                        ),
-                        env_var: Some(env_var.to_string()),
-                    });
-                }
+/// Load the structured config file at `path_override`, or the one named by
+/// `EDGE_COLLECTOR_CONFIG_FILE` if `path_override` is `None`.
+///
+/// Returns an empty (all-`None`) patch when neither is set. Errors if a path
+/// is set but the file is missing or fails to parse.
+fn load_config_file_patch(path_override: Option<&Path>) -> Result<ConfigFilePatch, ConfigError> {
+    let env_var = "EDGE_COLLECTOR_CONFIG_FILE";
 
-                if interval > MAX_FLUSH_INTERVAL_SECS {
-                    return Err(ConfigError {
-                        message: format!(
-                            "flush interval {} exceeds maximum ({}s)",
-                            interval, MAX_FLUSH_INTERVAL_SECS
-                        ),
-                        env_var: Some(env_var.to_string()),
-                    });
-                }
+    match path_override.map(Path::to_path_buf).or_else(|| env::var(env_var).ok().map(PathBuf::from)) {
+        Some(path) => parse_config_file(&path),
+        None => Ok(ConfigFilePatch::default()),
+    }
+}
+
+/// Parse a single TOML or YAML config file into a [`ConfigFilePatch`],
+/// dispatching on file extension.
+fn parse_config_file(path: &Path) -> Result<ConfigFilePatch, ConfigError> {
+    let contents = std::fs::read_to_string(path).map_err(|e| ConfigError {
+        message: format!("failed to read config file: {}", e),
+        env_var: Some(path.display().to_string()),
+    })?;
+
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("toml") => toml::from_str(&contents).map_err(|e| ConfigError {
+            message: format!("invalid TOML: {}", e),
+            env_var: Some(path.display().to_string()),
+        }),
+        Some("yaml") | Some("yml") => serde_yaml::from_str(&contents).map_err(|e| ConfigError {
+            message: format!("invalid YAML: {}", e),
+            env_var: Some(path.display().to_string()),
+        }),
+        Some(other) => Err(ConfigError {
+            message: format!(
+                "unrecognized config extension '{}' (expected toml, yaml, or yml)",
+                other
+            ),
+            env_var: Some(path.display().to_string()),
+        }),
+        None => Err(ConfigError {
+            message: "config file has no extension".to_string(),
+            env_var: Some(path.display().to_string()),
+        }),
+    }
+}
+
+/// Load `EDGE_COLLECTOR_DOTENV` (default `.env`) into the process
+/// environment, filling in any variable not already set.
+///
+/// A missing `.env` file is not an error — it's expected in most deployments,
+/// which configure purely through the real environment. A variable that
+/// already exists in the environment is left untouched, so real environment
+/// variables always win over the `.env` file.
+fn load_dotenv_file() {
+    let path = env::var("EDGE_COLLECTOR_DOTENV").unwrap_or_else(|_| ".env".to_string());
+
+    let Ok(contents) = std::fs::read_to_string(&path) else {
+        return;
+    };
+
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
 
-                Ok(interval)
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim();
+        let value = value.trim().trim_matches('"').trim_matches('\'');
+
+        if env::var(key).is_err() {
+            env::set_var(key, value);
+        }
+    }
+}
+
+/// Poll interval [`watch_config_file`] uses to check the config file for changes.
+pub const DEFAULT_CONFIG_WATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A shared, atomically-swappable handle to a [`Config`].
+///
+/// Cloning a `ConfigHandle` is cheap and shares the same underlying config —
+/// every clone observes a reload performed through any other clone. Use
+/// [`ConfigHandle::load`] to take a snapshot for the current operation (e.g.
+/// a flush cycle); don't hold it across one, since a reload in between won't
+/// be reflected in an already-taken snapshot.
+#[derive(Debug, Clone)]
+pub struct ConfigHandle {
+    inner: Arc<RwLock<Config>>,
+}
+
+impl ConfigHandle {
+    /// Wrap `config` in a new handle.
+    pub fn new(config: Config) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(config)),
+        }
+    }
+
+    /// Take a cheap clone of the currently active config.
+    pub fn load(&self) -> Config {
+        self.inner.read().expect("config lock poisoned").clone()
+    }
+
+    fn store(&self, config: Config) {
+        *self.inner.write().expect("config lock poisoned") = config;
+    }
+}
+
+/// Watch `EDGE_COLLECTOR_CONFIG_FILE` for modifications and hot-reload
+/// `handle` whenever it changes on disk.
+///
+/// Polls the file's modification time every `poll_interval` on a background
+/// thread (no filesystem notification API is assumed to be available at the
+/// edge), matching [`crate::generator_config::watch`]. A reload re-runs the
+/// same `from_env` layering and validation used at startup; a reload that
+/// fails validation is logged and the previous good `Config` is kept running
+/// rather than tearing down the service. `batch_size`, `flush_interval`,
+/// `request_timeout`, and `max_retries` all take effect on the next flush
+/// cycle, since callers re-[`ConfigHandle::load`] each time they need them.
+///
+/// Does nothing if `EDGE_COLLECTOR_CONFIG_FILE` isn't set — there's no file
+/// to watch. The background thread exits once `handle` has no other clones
+/// left.
+pub fn watch_config_file(handle: ConfigHandle, poll_interval: Duration) {
+    let Ok(path) = env::var("EDGE_COLLECTOR_CONFIG_FILE").map(PathBuf::from) else {
+        return;
+    };
+
+    std::thread::spawn(move || {
+        let mut last_mtime = mtime(&path);
+
+        loop {
+            std::thread::sleep(poll_interval);
+
+            // Stop once the caller has dropped every other clone of the handle.
+            if Arc::strong_count(&handle.inner) <= 1 {
+                break;
+            }
+
+            let current_mtime = mtime(&path);
+            if current_mtime == last_mtime {
+                continue;
+            }
+            last_mtime = current_mtime;
+
+            match Config::from_env() {
+                Ok(config) => {
+                    info!(path = %path.display(), "Config file changed, reloaded successfully");
+                    handle.store(config);
+                }
+                Err(e) => {
+                    warn!(
+                        path = %path.display(),
+                        error = %e,
+                        "Config reload failed validation, keeping previous config"
+                    );
+                }
             }
-            Err(_) => Ok(DEFAULT_FLUSH_INTERVAL_SECS),
+        }
+    });
+}
+
+fn mtime(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn validate_batch_size(batch_size: usize, source: &str) -> Result<(), ConfigError> {
+    if batch_size == 0 {
+        return Err(ConfigError {
+            message: "batch size must be greater than 0".to_string(),
+            env_var: Some(source.to_string()),
+        });
+    }
+
+    if batch_size > MAX_BATCH_SIZE {
+        return Err(ConfigError {
+            message: format!(
+                "batch size {} exceeds maximum allowed ({})",
+                batch_size, MAX_BATCH_SIZE
+            ),
+            env_var: Some(source.to_string()),
+        });
+    }
+
+    Ok(())
+}
+
+fn validate_flush_interval(interval: u64, source: &str) -> Result<(), ConfigError> {
+    if interval < MIN_FLUSH_INTERVAL_SECS {
+        return Err(ConfigError {
+            message: format!(
+                "flush interval {} is below minimum ({}s)",
+                interval, MIN_FLUSH_INTERVAL_SECS
+            ),
+            env_var: Some(source.to_string()),
+        });
+    }
+
+    if interval > MAX_FLUSH_INTERVAL_SECS {
+        return Err(ConfigError {
+            message: format!(
+                "flush interval {} exceeds maximum ({}s)",
+                interval, MAX_FLUSH_INTERVAL_SECS
+            ),
+            env_var: Some(source.to_string()),
+        });
+    }
+
+    Ok(())
+}
+
+fn validate_cb_consecutive_failures(threshold: u32, source: &str) -> Result<(), ConfigError> {
+    if threshold == 0 {
+        return Err(ConfigError {
+            message: "consecutive failure threshold must be greater than 0".to_string(),
+            env_var: Some(source.to_string()),
+        });
+    }
+
+    if threshold > MAX_CB_CONSECUTIVE_FAILURES {
+        return Err(ConfigError {
+            message: format!(
+                "consecutive failure threshold {} exceeds maximum allowed ({})",
+                threshold, MAX_CB_CONSECUTIVE_FAILURES
+            ),
+            env_var: Some(source.to_string()),
+        });
+    }
+
+    Ok(())
+}
+
+fn validate_cb_open_duration(duration: u64, source: &str) -> Result<(), ConfigError> {
+    if duration < MIN_CB_OPEN_DURATION_SECS {
+        return Err(ConfigError {
+            message: format!(
+                "circuit breaker open duration {} is below minimum ({}s)",
+                duration, MIN_CB_OPEN_DURATION_SECS
+            ),
+            env_var: Some(source.to_string()),
+        });
+    }
+
+    if duration > MAX_CB_OPEN_DURATION_SECS {
+        return Err(ConfigError {
+            message: format!(
+                "circuit breaker open duration {} exceeds maximum ({}s)",
+                duration, MAX_CB_OPEN_DURATION_SECS
+            ),
+            env_var: Some(source.to_string()),
+        });
+    }
+
+    Ok(())
+}
+
+fn validate_cb_half_open_max_probes(probes: u32, source: &str) -> Result<(), ConfigError> {
+    if probes == 0 {
+        return Err(ConfigError {
+            message: "half-open max probes must be greater than 0".to_string(),
+            env_var: Some(source.to_string()),
+        });
+    }
+
+    if probes > MAX_CB_HALF_OPEN_MAX_PROBES {
+        return Err(ConfigError {
+            message: format!(
+                "half-open max probes {} exceeds maximum allowed ({})",
+                probes, MAX_CB_HALF_OPEN_MAX_PROBES
+            ),
+            env_var: Some(source.to_string()),
+        });
+    }
+
+    Ok(())
+}
+
+fn validate_rate_limit_value(value: f64, source: &str) -> Result<(), ConfigError> {
+    if !(value > 0.0) {
+        return Err(ConfigError {
+            message: "rate limit value must be greater than 0".to_string(),
+            env_var: Some(source.to_string()),
+        });
+    }
+
+    if value > MAX_RATE_LIMIT_VALUE {
+        return Err(ConfigError {
+            message: format!(
+                "rate limit value {} exceeds maximum allowed ({})",
+                value, MAX_RATE_LIMIT_VALUE
+            ),
+            env_var: Some(source.to_string()),
+        });
+    }
+
+    Ok(())
+}
+
+fn validate_compression_threshold(threshold: usize, source: &str) -> Result<(), ConfigError> {
+    if threshold > MAX_COMPRESSION_THRESHOLD_BYTES {
+        return Err(ConfigError {
+            message: format!(
+                "compression threshold {} exceeds maximum allowed ({})",
+                threshold, MAX_COMPRESSION_THRESHOLD_BYTES
+            ),
+            env_var: Some(source.to_string()),
+        });
+    }
+
+    Ok(())
+}
+
+/// Parsed command-line overrides for [`Config`], the highest-precedence
+/// layer on top of environment variables.
+#[derive(Debug, Default, Clone)]
+struct CliOverrides {
+    api_url: Option<String>,
+    batch_size: Option<usize>,
+    flush_interval_secs: Option<u64>,
+    request_timeout_secs: Option<u64>,
+    max_retries: Option<u32>,
+    cb_consecutive_failures: Option<u32>,
+    cb_open_duration_secs: Option<u64>,
+    cb_half_open_max_probes: Option<u32>,
+    enable_compression: Option<bool>,
+    compression_threshold_bytes: Option<usize>,
+    max_requests_per_second: Option<f64>,
+    rate_limit_burst: Option<f64>,
+
+    /// `--config` override for `EDGE_COLLECTOR_CONFIG_FILE`. Not applied by
+    /// [`CliOverrides::apply_to`] — [`Config::from_env_and_args`] consumes it
+    /// earlier, since it has to pick the config file before that file's
+    /// values are layered under the rest of these overrides.
+    config_path: Option<PathBuf>,
+}
+
+impl CliOverrides {
+    /// Apply whichever fields were set, leaving the rest of `config` untouched.
+    fn apply_to(self, config: &mut Config) {
+        if let Some(api_url) = self.api_url {
+            let api_url = api_url.trim_end_matches('/').to_string();
+            config.ingest_url = format!("{}/api/v1/ingest/logs", api_url);
+            config.api_url = api_url;
+        }
+        if let Some(v) = self.batch_size {
+            config.batch_size = v;
+        }
+        if let Some(v) = self.flush_interval_secs {
+            config.flush_interval = Duration::from_secs(v);
+        }
+        if let Some(v) = self.request_timeout_secs {
+            config.request_timeout = Duration::from_secs(v);
+        }
+        if let Some(v) = self.max_retries {
+            config.max_retries = v;
+        }
+        if let Some(v) = self.cb_consecutive_failures {
+            config.cb_consecutive_failures = v;
+        }
+        if let Some(v) = self.cb_open_duration_secs {
+            config.cb_open_duration = Duration::from_secs(v);
+        }
+        if let Some(v) = self.cb_half_open_max_probes {
+            config.cb_half_open_max_probes = v;
+        }
+        if let Some(v) = self.enable_compression {
+            config.enable_compression = v;
+        }
+        if let Some(v) = self.compression_threshold_bytes {
+            config.compression_threshold = v;
+        }
+        if let Some(v) = self.max_requests_per_second {
+            config.max_requests_per_second = v;
+        }
+        if let Some(v) = self.rate_limit_burst {
+            config.rate_limit_burst = v;
         }
     }
 }
 
+/// Command-line flags accepted by [`Config::from_env_and_args`], one per
+/// overridable `Config` field.
+///
+/// Every flag is optional: an unset flag leaves the corresponding field at
+/// whatever `Config::from_env` already resolved from the config file /
+/// environment / default chain. Parsed via `clap`, so `--flag=value` works
+/// alongside `--flag value`, `--help` is generated automatically, and an
+/// unrecognized flag is a parse error.
+#[derive(Parser, Debug, Default)]
+#[command(name = "edge-collector", about = "Edge-to-cloud log collector", long_about = None)]
+struct CliArgs {
+    /// Override the backend API URL.
+    #[arg(long)]
+    api_url: Option<String>,
+
+    /// Override the number of logs per batch.
+    #[arg(long)]
+    batch_size: Option<usize>,
+
+    /// Override the flush interval, in seconds.
+    #[arg(long)]
+    flush_interval_secs: Option<u64>,
+
+    /// Override the per-request HTTP timeout, in seconds.
+    #[arg(long)]
+    request_timeout_secs: Option<u64>,
+
+    /// Override the maximum retry count for a failed send.
+    #[arg(long)]
+    max_retries: Option<u32>,
+
+    /// Override the circuit breaker's consecutive-failure trip threshold.
+    #[arg(long)]
+    cb_consecutive_failures: Option<u32>,
+
+    /// Override how long the circuit breaker stays Open before probing
+    /// again, in seconds.
+    #[arg(long)]
+    cb_open_duration_secs: Option<u64>,
+
+    /// Override the number of trial requests let through while Half-Open.
+    #[arg(long)]
+    cb_half_open_max_probes: Option<u32>,
+
+    /// Override whether request bodies are gzip-compressed.
+    #[arg(long)]
+    enable_compression: Option<bool>,
+
+    /// Override the byte size above which a request body is compressed.
+    #[arg(long)]
+    compression_threshold_bytes: Option<usize>,
+
+    /// Override the sustained client-side send rate, in requests per second.
+    #[arg(long)]
+    max_requests_per_second: Option<f64>,
+
+    /// Override the client-side rate limiter's burst capacity.
+    #[arg(long)]
+    rate_limit_burst: Option<f64>,
+
+    /// Load a structured TOML/YAML config file, overriding
+    /// `EDGE_COLLECTOR_CONFIG_FILE` for this process.
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// Run in benchmark mode instead of starting the collector service.
+    ///
+    /// Already checked directly against `std::env::args()` in `main` before
+    /// `Config` is loaded; declared here too only so clap doesn't reject it
+    /// as an unrecognized flag when `run_benchmark_mode` re-parses the same
+    /// `argv` through `Config::from_env_and_args`.
+    #[arg(long)]
+    benchmark: bool,
+}
+
+/// Parse CLI flags into [`CliOverrides`] via [`CliArgs`], validating each
+/// recognized flag's value immediately (same limits as the environment
+/// loader).
+///
+/// `args` should not include `argv[0]`; a synthetic program name is
+/// prepended internally so callers can keep passing `std::env::args().skip(1)`
+/// directly.
+fn parse_cli_args<I, S>(args: I) -> Result<CliOverrides, ConfigError>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<std::ffi::OsString> + Clone,
+{
+    let cli = CliArgs::try_parse_from(
+        std::iter::once(std::ffi::OsString::from("edge-collector"))
+            .chain(args.into_iter().map(Into::into)),
+    )
+    .map_err(|e| ConfigError {
+        message: e.to_string(),
+        env_var: None,
+    })?;
+
+    let mut overrides = CliOverrides {
+        api_url: cli.api_url,
+        request_timeout_secs: cli.request_timeout_secs,
+        max_retries: cli.max_retries,
+        enable_compression: cli.enable_compression,
+        config_path: cli.config,
+        ..CliOverrides::default()
+    };
+
+    if let Some(v) = cli.batch_size {
+        validate_batch_size(v, "--batch-size")?;
+        overrides.batch_size = Some(v);
+    }
+    if let Some(v) = cli.flush_interval_secs {
+        validate_flush_interval(v, "--flush-interval-secs")?;
+        overrides.flush_interval_secs = Some(v);
+    }
+    if let Some(v) = cli.cb_consecutive_failures {
+        validate_cb_consecutive_failures(v, "--cb-consecutive-failures")?;
+        overrides.cb_consecutive_failures = Some(v);
+    }
+    if let Some(v) = cli.cb_open_duration_secs {
+        validate_cb_open_duration(v, "--cb-open-duration-secs")?;
+        overrides.cb_open_duration_secs = Some(v);
+    }
+    if let Some(v) = cli.cb_half_open_max_probes {
+        validate_cb_half_open_max_probes(v, "--cb-half-open-max-probes")?;
+        overrides.cb_half_open_max_probes = Some(v);
+    }
+    if let Some(v) = cli.compression_threshold_bytes {
+        validate_compression_threshold(v, "--compression-threshold-bytes")?;
+        overrides.compression_threshold_bytes = Some(v);
+    }
+    if let Some(v) = cli.max_requests_per_second {
+        validate_rate_limit_value(v, "--max-requests-per-second")?;
+        overrides.max_requests_per_second = Some(v);
+    }
+    if let Some(v) = cli.rate_limit_burst {
+        validate_rate_limit_value(v, "--rate-limit-burst")?;
+        overrides.rate_limit_burst = Some(v);
+    }
+
+    Ok(overrides)
+}
+
 impl Default for Config {
     /// Create a default configuration using default values.
     ///
@@ -214,6 +1005,13 @@ impl Default for Config {
             flush_interval: Duration::from_secs(DEFAULT_FLUSH_INTERVAL_SECS),
             request_timeout: Duration::from_secs(30),
             max_retries: 3,
+            cb_consecutive_failures: DEFAULT_CB_CONSECUTIVE_FAILURES,
+            cb_open_duration: Duration::from_secs(DEFAULT_CB_OPEN_DURATION_SECS),
+            cb_half_open_max_probes: DEFAULT_CB_HALF_OPEN_MAX_PROBES,
+            enable_compression: true,
+            compression_threshold: DEFAULT_COMPRESSION_THRESHOLD_BYTES,
+            max_requests_per_second: DEFAULT_MAX_REQUESTS_PER_SECOND,
+            rate_limit_burst: DEFAULT_RATE_LIMIT_BURST,
         }
     }
 }
@@ -342,6 +1140,326 @@ mod tests {
         assert!(err.message.contains("exceeds maximum"));
     }
 
+    #[test]
+    fn test_default_config_circuit_breaker_fields() {
+        let config = Config::default();
+        assert_eq!(config.cb_consecutive_failures, 5);
+        assert_eq!(config.cb_open_duration, Duration::from_secs(30));
+        assert_eq!(config.cb_half_open_max_probes, 1);
+    }
+
+    #[test]
+    fn test_config_from_env_custom_circuit_breaker_values() {
+        let _guard1 = EnvGuard::set("EDGE_COLLECTOR_CB_CONSECUTIVE_FAILURES", "10");
+        let _guard2 = EnvGuard::set("EDGE_COLLECTOR_CB_OPEN_DURATION_SECS", "60");
+        let _guard3 = EnvGuard::set("EDGE_COLLECTOR_CB_HALF_OPEN_MAX_PROBES", "3");
+
+        let config = Config::from_env().expect("Should load custom circuit breaker values");
+        assert_eq!(config.cb_consecutive_failures, 10);
+        assert_eq!(config.cb_open_duration, Duration::from_secs(60));
+        assert_eq!(config.cb_half_open_max_probes, 3);
+    }
+
+    #[test]
+    fn test_default_config_compression_fields() {
+        let config = Config::default();
+        assert!(config.enable_compression);
+        assert_eq!(config.compression_threshold, DEFAULT_COMPRESSION_THRESHOLD_BYTES);
+    }
+
+    #[test]
+    fn test_config_from_env_custom_compression_values() {
+        let _guard1 = EnvGuard::set("EDGE_COLLECTOR_ENABLE_COMPRESSION", "false");
+        let _guard2 = EnvGuard::set("EDGE_COLLECTOR_COMPRESSION_THRESHOLD_BYTES", "2048");
+
+        let config = Config::from_env().expect("Should load custom compression values");
+        assert!(!config.enable_compression);
+        assert_eq!(config.compression_threshold, 2048);
+    }
+
+    #[test]
+    fn test_compression_threshold_exceeds_max_rejected() {
+        let _guard = EnvGuard::set(
+            "EDGE_COLLECTOR_COMPRESSION_THRESHOLD_BYTES",
+            "999999999999",
+        );
+
+        let result = Config::from_env();
+        assert!(result.is_err());
+        assert!(result.unwrap_err().message.contains("exceeds maximum"));
+    }
+
+    #[test]
+    fn test_default_config_rate_limit_fields() {
+        let config = Config::default();
+        assert_eq!(config.max_requests_per_second, DEFAULT_MAX_REQUESTS_PER_SECOND);
+        assert_eq!(config.rate_limit_burst, DEFAULT_RATE_LIMIT_BURST);
+    }
+
+    #[test]
+    fn test_config_from_env_custom_rate_limit_values() {
+        let _guard1 = EnvGuard::set("EDGE_COLLECTOR_MAX_REQUESTS_PER_SECOND", "10.5");
+        let _guard2 = EnvGuard::set("EDGE_COLLECTOR_RATE_LIMIT_BURST", "20");
+
+        let config = Config::from_env().expect("Should load custom rate limit values");
+        assert_eq!(config.max_requests_per_second, 10.5);
+        assert_eq!(config.rate_limit_burst, 20.0);
+    }
+
+    #[test]
+    fn test_zero_max_requests_per_second_rejected() {
+        let _guard = EnvGuard::set("EDGE_COLLECTOR_MAX_REQUESTS_PER_SECOND", "0");
+
+        let result = Config::from_env();
+        assert!(result.is_err());
+        assert!(result.unwrap_err().message.contains("greater than 0"));
+    }
+
+    #[test]
+    fn test_rate_limit_burst_exceeds_max_rejected() {
+        let _guard = EnvGuard::set("EDGE_COLLECTOR_RATE_LIMIT_BURST", "99999999");
+
+        let result = Config::from_env();
+        assert!(result.is_err());
+        assert!(result.unwrap_err().message.contains("exceeds maximum"));
+    }
+
+    #[test]
+    fn test_zero_cb_consecutive_failures_rejected() {
+        let _guard = EnvGuard::set("EDGE_COLLECTOR_CB_CONSECUTIVE_FAILURES", "0");
+
+        let result = Config::from_env();
+        assert!(result.is_err());
+        assert!(result.unwrap_err().message.contains("greater than 0"));
+    }
+
+    #[test]
+    fn test_cb_open_duration_below_min_rejected() {
+        let _guard = EnvGuard::set("EDGE_COLLECTOR_CB_OPEN_DURATION_SECS", "0");
+
+        let result = Config::from_env();
+        assert!(result.is_err());
+        assert!(result.unwrap_err().message.contains("below minimum"));
+    }
+
+    #[test]
+    fn test_cb_half_open_max_probes_exceeds_max_rejected() {
+        let _guard = EnvGuard::set("EDGE_COLLECTOR_CB_HALF_OPEN_MAX_PROBES", "999");
+
+        let result = Config::from_env();
+        assert!(result.is_err());
+        assert!(result.unwrap_err().message.contains("exceeds maximum"));
+    }
+
+    #[test]
+    fn test_config_file_toml_provides_defaults() {
+        let dir = std::env::temp_dir();
+        let path = dir.join("edge_collector_test_config.toml");
+        std::fs::write(&path, "batch_size = 250\napi_url = \"http://from-file:8000\"\n").unwrap();
+
+        let _guard = EnvGuard::set("EDGE_COLLECTOR_CONFIG_FILE", path.to_str().unwrap());
+        let config = Config::from_env().expect("Should load with file defaults");
+
+        assert_eq!(config.batch_size, 250);
+        assert_eq!(config.api_url, "http://from-file:8000");
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_env_var_wins_over_config_file() {
+        let dir = std::env::temp_dir();
+        let path = dir.join("edge_collector_test_config_env_wins.toml");
+        std::fs::write(&path, "batch_size = 250\n").unwrap();
+
+        let _guard1 = EnvGuard::set("EDGE_COLLECTOR_CONFIG_FILE", path.to_str().unwrap());
+        let _guard2 = EnvGuard::set("EDGE_COLLECTOR_BATCH_SIZE", "333");
+        let config = Config::from_env().expect("Should load with env override");
+
+        assert_eq!(config.batch_size, 333);
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_config_flag_overrides_config_file_env_var() {
+        let dir = std::env::temp_dir();
+        let env_path = dir.join("edge_collector_test_config_flag_env.toml");
+        let flag_path = dir.join("edge_collector_test_config_flag_cli.toml");
+        std::fs::write(&env_path, "batch_size = 250\n").unwrap();
+        std::fs::write(&flag_path, "batch_size = 400\n").unwrap();
+
+        let _guard = EnvGuard::set("EDGE_COLLECTOR_CONFIG_FILE", env_path.to_str().unwrap());
+        let config = Config::from_env_and_args(["--config".to_string(), flag_path.to_str().unwrap().to_string()])
+            .expect("Should load with --config file");
+
+        assert_eq!(config.batch_size, 400);
+
+        std::fs::remove_file(&env_path).ok();
+        std::fs::remove_file(&flag_path).ok();
+    }
+
+    #[test]
+    fn test_missing_config_file_errors() {
+        let _guard = EnvGuard::set("EDGE_COLLECTOR_CONFIG_FILE", "/nonexistent/edge-collector.toml");
+        let result = Config::from_env();
+        assert!(result.is_err());
+        assert!(result.unwrap_err().message.contains("failed to read config file"));
+    }
+
+    #[test]
+    fn test_config_file_unrecognized_extension_errors() {
+        let dir = std::env::temp_dir();
+        let path = dir.join("edge_collector_test_config.ini");
+        std::fs::write(&path, "batch_size = 250\n").unwrap();
+
+        let _guard = EnvGuard::set("EDGE_COLLECTOR_CONFIG_FILE", path.to_str().unwrap());
+        let result = Config::from_env();
+        assert!(result.is_err());
+        assert!(result.unwrap_err().message.contains("unrecognized"));
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_dotenv_file_fills_unset_variables() {
+        let dir = std::env::temp_dir();
+        let path = dir.join("edge_collector_test.env");
+        std::fs::write(&path, "EDGE_COLLECTOR_TEST_DOTENV_BATCH_SIZE=77\n").unwrap();
+
+        let _guard1 = EnvGuard::set("EDGE_COLLECTOR_DOTENV", path.to_str().unwrap());
+        let _guard2 = EnvGuard::remove("EDGE_COLLECTOR_TEST_DOTENV_BATCH_SIZE");
+
+        load_dotenv_file();
+        assert_eq!(
+            env::var("EDGE_COLLECTOR_TEST_DOTENV_BATCH_SIZE").as_deref(),
+            Ok("77")
+        );
+
+        env::remove_var("EDGE_COLLECTOR_TEST_DOTENV_BATCH_SIZE");
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_dotenv_file_does_not_override_real_env() {
+        let dir = std::env::temp_dir();
+        let path = dir.join("edge_collector_test_no_override.env");
+        std::fs::write(&path, "EDGE_COLLECTOR_TEST_DOTENV_NO_OVERRIDE=from_file\n").unwrap();
+
+        let _guard1 = EnvGuard::set("EDGE_COLLECTOR_DOTENV", path.to_str().unwrap());
+        let _guard2 = EnvGuard::set("EDGE_COLLECTOR_TEST_DOTENV_NO_OVERRIDE", "from_env");
+
+        load_dotenv_file();
+        assert_eq!(
+            env::var("EDGE_COLLECTOR_TEST_DOTENV_NO_OVERRIDE").as_deref(),
+            Ok("from_env")
+        );
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_cli_overrides_env() {
+        let _guard1 = EnvGuard::set("EDGE_COLLECTOR_API_URL", "http://from-env:9000");
+        let _guard2 = EnvGuard::set("EDGE_COLLECTOR_BATCH_SIZE", "200");
+
+        let config = Config::from_env_and_args(["--batch-size", "50"])
+            .expect("Should load with CLI override");
+
+        // CLI wins over env for batch_size...
+        assert_eq!(config.batch_size, 50);
+        // ...but env still applies where no CLI flag was given.
+        assert_eq!(config.api_url, "http://from-env:9000");
+    }
+
+    #[test]
+    fn test_cli_overrides_all_recognized_flags() {
+        let config = Config::from_env_and_args([
+            "--api-url",
+            "http://cli:7000/",
+            "--batch-size",
+            "10",
+            "--flush-interval-secs",
+            "2",
+            "--request-timeout-secs",
+            "15",
+            "--max-retries",
+            "1",
+            "--cb-consecutive-failures",
+            "2",
+            "--cb-open-duration-secs",
+            "5",
+            "--cb-half-open-max-probes",
+            "4",
+            "--enable-compression",
+            "false",
+            "--compression-threshold-bytes",
+            "512",
+            "--max-requests-per-second",
+            "25",
+            "--rate-limit-burst",
+            "50",
+        ])
+        .expect("Should load with all CLI overrides");
+
+        assert_eq!(config.api_url, "http://cli:7000");
+        assert_eq!(config.ingest_url, "http://cli:7000/api/v1/ingest/logs");
+        assert_eq!(config.batch_size, 10);
+        assert_eq!(config.flush_interval, Duration::from_secs(2));
+        assert_eq!(config.request_timeout, Duration::from_secs(15));
+        assert_eq!(config.max_retries, 1);
+        assert_eq!(config.cb_consecutive_failures, 2);
+        assert_eq!(config.cb_open_duration, Duration::from_secs(5));
+        assert_eq!(config.cb_half_open_max_probes, 4);
+        assert!(!config.enable_compression);
+        assert_eq!(config.compression_threshold, 512);
+        assert_eq!(config.max_requests_per_second, 25.0);
+        assert_eq!(config.rate_limit_burst, 50.0);
+    }
+
+    #[test]
+    fn test_cli_unrecognized_flag_rejected() {
+        let result = Config::from_env_and_args(["--unknown-flag", "value"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cli_flag_equals_syntax() {
+        let config = Config::from_env_and_args(["--batch-size=42"])
+            .expect("clap should accept --flag=value syntax");
+        assert_eq!(config.batch_size, 42);
+    }
+
+    #[test]
+    fn test_cli_invalid_batch_size_rejected() {
+        let result = Config::from_env_and_args(["--batch-size", "0"]);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().message.contains("greater than 0"));
+    }
+
+    #[test]
+    fn test_cli_flag_missing_value_rejected() {
+        let result = Config::from_env_and_args(["--batch-size"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_optimize_for_disabled_by_default() {
+        let _guard = EnvGuard::remove("EDGE_COLLECTOR_ADAPTIVE");
+        let config = Config::from_env().expect("Should load defaults");
+        assert!(config.optimize_for().is_none());
+    }
+
+    #[test]
+    fn test_optimize_for_enabled_clamps_to_config_constants() {
+        let _guard = EnvGuard::set("EDGE_COLLECTOR_ADAPTIVE", "true");
+        let config = Config::from_env().expect("Should load defaults");
+        let tuning = config.optimize_for().expect("Should be enabled");
+
+        assert_eq!(tuning.max_batch_size, MAX_BATCH_SIZE);
+        assert_eq!(tuning.max_flush_interval, Duration::from_secs(MAX_FLUSH_INTERVAL_SECS));
+    }
+
     #[test]
     fn test_config_error_display() {
         let error = ConfigError {
@@ -362,4 +1480,64 @@ mod tests {
             "Configuration error: general error"
         );
     }
+
+    #[test]
+    fn test_config_handle_load_returns_current_snapshot() {
+        let handle = ConfigHandle::new(Config {
+            batch_size: 42,
+            ..Config::default()
+        });
+
+        assert_eq!(handle.load().batch_size, 42);
+
+        handle.store(Config {
+            batch_size: 99,
+            ..Config::default()
+        });
+        assert_eq!(handle.load().batch_size, 99);
+    }
+
+    #[test]
+    fn test_watch_config_file_reloads_on_change() {
+        let dir = std::env::temp_dir();
+        let path = dir.join("edge_collector_test_watch_config.toml");
+        std::fs::write(&path, "batch_size = 111\n").unwrap();
+
+        let _guard = EnvGuard::set("EDGE_COLLECTOR_CONFIG_FILE", path.to_str().unwrap());
+        let initial = Config::from_env().expect("initial load should succeed");
+        assert_eq!(initial.batch_size, 111);
+
+        let handle = ConfigHandle::new(initial);
+        watch_config_file(handle.clone(), Duration::from_millis(20));
+
+        std::thread::sleep(Duration::from_millis(60));
+        std::fs::write(&path, "batch_size = 222\n").unwrap();
+        std::thread::sleep(Duration::from_millis(200));
+
+        assert_eq!(handle.load().batch_size, 222);
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_watch_config_file_keeps_previous_config_on_invalid_reload() {
+        let dir = std::env::temp_dir();
+        let path = dir.join("edge_collector_test_watch_config_invalid.toml");
+        std::fs::write(&path, "batch_size = 111\n").unwrap();
+
+        let _guard = EnvGuard::set("EDGE_COLLECTOR_CONFIG_FILE", path.to_str().unwrap());
+        let initial = Config::from_env().expect("initial load should succeed");
+
+        let handle = ConfigHandle::new(initial);
+        watch_config_file(handle.clone(), Duration::from_millis(20));
+
+        std::thread::sleep(Duration::from_millis(60));
+        std::fs::write(&path, "batch_size = 0\n").unwrap();
+        std::thread::sleep(Duration::from_millis(200));
+
+        // Invalid reload (batch_size = 0 fails validation) is dropped.
+        assert_eq!(handle.load().batch_size, 111);
+
+        std::fs::remove_file(&path).ok();
+    }
 }
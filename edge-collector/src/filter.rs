@@ -0,0 +1,344 @@
+//! Bitmask-composable severity filtering with named presets.
+//!
+//! Combines ideas from Fuchsia's `log_listener` (severity floor + source/tag
+//! selectors + regex) and kanidm's bitmask `LogLevel` presets: an include set
+//! is a `u8` bitmask over the six [`LogLevel`] variants, with named presets
+//! for the common cases and a builder for custom masks. [`LogFilter`] layers
+//! an optional `source_id` regex and metadata key/value predicates on top, so
+//! consumers can drop noise at the source instead of shipping everything to
+//! the cloud.
+
+use std::collections::HashMap;
+
+use regex::Regex;
+
+use crate::log_generator::{LogEntry, LogGenerator, LogLevel};
+
+fn level_bit(level: LogLevel) -> u8 {
+    match level {
+        LogLevel::Trace => 1 << 0,
+        LogLevel::Debug => 1 << 1,
+        LogLevel::Info => 1 << 2,
+        LogLevel::Warn => 1 << 3,
+        LogLevel::Error => 1 << 4,
+        LogLevel::Fatal => 1 << 5,
+    }
+}
+
+/// A bitmask over the six [`LogLevel`] variants, one bit per level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LevelMask(u8);
+
+impl LevelMask {
+    /// Only `Error` and `Fatal` pass.
+    pub const QUIET: LevelMask = LevelMask((1 << 4) | (1 << 5));
+
+    /// `Info`, `Warn`, `Error`, and `Fatal` pass (the common production default).
+    pub const DEFAULT: LevelMask = LevelMask((1 << 2) | (1 << 3) | (1 << 4) | (1 << 5));
+
+    /// Every level passes.
+    pub const VERBOSE: LevelMask = LevelMask(0b0011_1111);
+
+    /// No level passes.
+    pub const NONE: LevelMask = LevelMask(0);
+
+    /// Start building a custom mask with no levels included.
+    pub fn builder() -> LevelMaskBuilder {
+        LevelMaskBuilder(0)
+    }
+
+    /// A mask containing exactly the given level.
+    pub fn only(level: LogLevel) -> Self {
+        LevelMask(level_bit(level))
+    }
+
+    /// Whether `level` is included in this mask.
+    pub fn contains(&self, level: LogLevel) -> bool {
+        self.0 & level_bit(level) != 0
+    }
+
+    /// Combine two masks (union of included levels).
+    pub fn union(self, other: LevelMask) -> LevelMask {
+        LevelMask(self.0 | other.0)
+    }
+
+    /// The raw bitmask value.
+    pub fn bits(&self) -> u8 {
+        self.0
+    }
+}
+
+impl std::ops::BitOr for LevelMask {
+    type Output = LevelMask;
+
+    fn bitor(self, rhs: LevelMask) -> LevelMask {
+        self.union(rhs)
+    }
+}
+
+/// Builder for a custom [`LevelMask`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LevelMaskBuilder(u8);
+
+impl LevelMaskBuilder {
+    /// Include `level` in the mask being built.
+    pub fn with(mut self, level: LogLevel) -> Self {
+        self.0 |= level_bit(level);
+        self
+    }
+
+    /// Finish building and produce the [`LevelMask`].
+    pub fn build(self) -> LevelMask {
+        LevelMask(self.0)
+    }
+}
+
+/// Errors constructing a [`LogFilter`].
+#[derive(Debug)]
+pub enum FilterError {
+    /// The `source_id` pattern was not a valid regex.
+    InvalidPattern(String),
+}
+
+impl std::fmt::Display for FilterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FilterError::InvalidPattern(msg) => write!(f, "invalid source_id pattern: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for FilterError {}
+
+/// Filters [`LogEntry`] values by level, `source_id` pattern, and metadata.
+///
+/// All configured conditions must match (AND semantics): an entry passes
+/// only if its level is in the mask, its `source_id` matches the optional
+/// regex, and every registered metadata predicate is satisfied.
+#[derive(Debug, Clone)]
+pub struct LogFilter {
+    level_mask: LevelMask,
+    source_id_pattern: Option<Regex>,
+    metadata_predicates: Vec<(String, serde_json::Value)>,
+}
+
+impl LogFilter {
+    /// Create a filter that only checks the level mask.
+    pub fn new(level_mask: LevelMask) -> Self {
+        Self {
+            level_mask,
+            source_id_pattern: None,
+            metadata_predicates: Vec::new(),
+        }
+    }
+
+    /// Require `source_id` to match the given regex pattern.
+    pub fn with_source_pattern(mut self, pattern: &str) -> Result<Self, FilterError> {
+        let regex = Regex::new(pattern).map_err(|e| FilterError::InvalidPattern(e.to_string()))?;
+        self.source_id_pattern = Some(regex);
+        Ok(self)
+    }
+
+    /// Require `metadata[key] == value` (as JSON equality).
+    pub fn with_metadata_eq(mut self, key: impl Into<String>, value: serde_json::Value) -> Self {
+        self.metadata_predicates.push((key.into(), value));
+        self
+    }
+
+    /// Whether `entry` satisfies every condition on this filter.
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        if !self.level_mask.contains(entry.level) {
+            return false;
+        }
+
+        if let Some(pattern) = &self.source_id_pattern {
+            if !pattern.is_match(&entry.source_id) {
+                return false;
+            }
+        }
+
+        if !self.metadata_predicates.is_empty() {
+            let empty: HashMap<String, serde_json::Value> = HashMap::new();
+            let metadata = entry.metadata.as_ref().unwrap_or(&empty);
+            for (key, expected) in &self.metadata_predicates {
+                match metadata.get(key) {
+                    Some(actual) if actual == expected => {}
+                    _ => return false,
+                }
+            }
+        }
+
+        true
+    }
+}
+
+/// Iterator adapter yielding only [`LogEntry`] values that match a [`LogFilter`].
+///
+/// Since generation is independent per call, a bounded number of attempts are
+/// made per `next()` so that an overly strict filter cannot spin forever.
+pub struct FilteredStream<'a> {
+    generator: &'a LogGenerator,
+    filter: &'a LogFilter,
+    max_attempts_per_item: usize,
+}
+
+impl<'a> Iterator for FilteredStream<'a> {
+    type Item = LogEntry;
+
+    fn next(&mut self) -> Option<LogEntry> {
+        for _ in 0..self.max_attempts_per_item {
+            let entry = self.generator.generate();
+            if self.filter.matches(&entry) {
+                return Some(entry);
+            }
+        }
+        None
+    }
+}
+
+/// Maximum number of generate attempts `generate_stream` makes per yielded
+/// item before giving up on an overly restrictive filter.
+const DEFAULT_MAX_ATTEMPTS_PER_ITEM: usize = 1_000;
+
+impl LogGenerator {
+    /// Generate a single entry, returning it only if it matches `filter`.
+    pub fn generate_filtered(&self, filter: &LogFilter) -> Option<LogEntry> {
+        let entry = self.generate();
+        if filter.matches(&entry) {
+            Some(entry)
+        } else {
+            None
+        }
+    }
+
+    /// An iterator that yields only entries matching `filter`.
+    pub fn generate_stream<'a>(&'a self, filter: &'a LogFilter) -> FilteredStream<'a> {
+        FilteredStream {
+            generator: self,
+            filter,
+            max_attempts_per_item: DEFAULT_MAX_ATTEMPTS_PER_ITEM,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preset_masks() {
+        assert!(LevelMask::QUIET.contains(LogLevel::Error));
+        assert!(LevelMask::QUIET.contains(LogLevel::Fatal));
+        assert!(!LevelMask::QUIET.contains(LogLevel::Info));
+
+        assert!(LevelMask::DEFAULT.contains(LogLevel::Info));
+        assert!(!LevelMask::DEFAULT.contains(LogLevel::Debug));
+
+        assert!(LevelMask::VERBOSE.contains(LogLevel::Trace));
+        assert!(LevelMask::VERBOSE.contains(LogLevel::Fatal));
+
+        assert!(!LevelMask::NONE.contains(LogLevel::Info));
+    }
+
+    #[test]
+    fn test_builder_custom_mask() {
+        let mask = LevelMask::builder()
+            .with(LogLevel::Warn)
+            .with(LogLevel::Error)
+            .build();
+
+        assert!(mask.contains(LogLevel::Warn));
+        assert!(mask.contains(LogLevel::Error));
+        assert!(!mask.contains(LogLevel::Info));
+    }
+
+    #[test]
+    fn test_mask_union_operator() {
+        let mask = LevelMask::only(LogLevel::Info) | LevelMask::only(LogLevel::Warn);
+        assert!(mask.contains(LogLevel::Info));
+        assert!(mask.contains(LogLevel::Warn));
+        assert!(!mask.contains(LogLevel::Error));
+    }
+
+    #[test]
+    fn test_filter_matches_on_level_only() {
+        let filter = LogFilter::new(LevelMask::only(LogLevel::Info));
+        let entry = LogEntry::new("sensor-1", LogLevel::Info, "hi");
+        assert!(filter.matches(&entry));
+
+        let entry = LogEntry::new("sensor-1", LogLevel::Error, "hi");
+        assert!(!filter.matches(&entry));
+    }
+
+    #[test]
+    fn test_filter_with_source_pattern() {
+        let filter = LogFilter::new(LevelMask::VERBOSE)
+            .with_source_pattern("^edge-temperature-")
+            .unwrap();
+
+        let matching = LogEntry::new("edge-temperature-001", LogLevel::Info, "hi");
+        let other = LogEntry::new("edge-humidity-001", LogLevel::Info, "hi");
+
+        assert!(filter.matches(&matching));
+        assert!(!filter.matches(&other));
+    }
+
+    #[test]
+    fn test_filter_rejects_invalid_pattern() {
+        let result = LogFilter::new(LevelMask::VERBOSE).with_source_pattern("(unterminated");
+        assert!(matches!(result, Err(FilterError::InvalidPattern(_))));
+    }
+
+    #[test]
+    fn test_filter_with_metadata_predicate() {
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "sensor_type".to_string(),
+            serde_json::Value::String("temperature".to_string()),
+        );
+        let entry = LogEntry::new("edge-1", LogLevel::Info, "hi").with_metadata(metadata);
+
+        let filter = LogFilter::new(LevelMask::VERBOSE)
+            .with_metadata_eq("sensor_type", serde_json::json!("temperature"));
+        assert!(filter.matches(&entry));
+
+        let filter = LogFilter::new(LevelMask::VERBOSE)
+            .with_metadata_eq("sensor_type", serde_json::json!("humidity"));
+        assert!(!filter.matches(&entry));
+    }
+
+    #[test]
+    fn test_filter_metadata_predicate_without_metadata_fails() {
+        let entry = LogEntry::new("edge-1", LogLevel::Info, "hi");
+        let filter =
+            LogFilter::new(LevelMask::VERBOSE).with_metadata_eq("sensor_type", serde_json::json!("temperature"));
+        assert!(!filter.matches(&entry));
+    }
+
+    #[test]
+    fn test_generate_filtered_respects_level_mask() {
+        let generator = LogGenerator::with_defaults();
+        let filter = LogFilter::new(LevelMask::NONE);
+        assert!(generator.generate_filtered(&filter).is_none());
+    }
+
+    #[test]
+    fn test_generate_stream_yields_only_matching_entries() {
+        let generator = LogGenerator::with_defaults();
+        let filter = LogFilter::new(LevelMask::VERBOSE);
+
+        let entries: Vec<LogEntry> = generator.generate_stream(&filter).take(10).collect();
+        assert_eq!(entries.len(), 10);
+        for entry in &entries {
+            assert!(filter.matches(entry));
+        }
+    }
+
+    #[test]
+    fn test_generate_stream_gives_up_on_impossible_filter() {
+        let generator = LogGenerator::with_defaults();
+        let filter = LogFilter::new(LevelMask::NONE);
+        let mut stream = generator.generate_stream(&filter);
+        assert!(stream.next().is_none());
+    }
+}
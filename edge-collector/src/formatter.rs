@@ -0,0 +1,388 @@
+//! Human-readable log formatting and local file persistence.
+//!
+//! Beyond the JSON wire format, operators watching an edge collector on a
+//! terminal or tailing a local log file want one line per [`LogEntry`]. The
+//! [`Formatter`] trait produces that line as
+//! `timestamp level source_id message [k=v ...]`, with [`PlainFormatter`] for
+//! plain text and [`AnsiFormatter`] for a colorized variant (borrowed from
+//! Fuchsia's `log_listener`: red for Error/Fatal, yellow for Warn). Pair a
+//! formatter with a [`RotatingFileSink`] to persist formatted lines to disk,
+//! rolling to a new file once a size cap is exceeded.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::log_generator::{LogEntry, LogGenerator, LogLevel};
+
+/// Default byte capacity of a single rotated file (~64 KB).
+pub const DEFAULT_ROTATION_CAPACITY: u64 = 64 * 1024;
+
+/// Default number of rotated files to retain alongside the active file.
+pub const DEFAULT_MAX_ROTATED_FILES: usize = 5;
+
+/// Renders a [`LogEntry`] as a single human-readable line.
+pub trait Formatter {
+    /// Format `entry` as one line, without a trailing newline.
+    fn format(&self, entry: &LogEntry) -> String;
+}
+
+fn format_plain_line(entry: &LogEntry) -> String {
+    let mut line = format!(
+        "{} {:<5} {} {}",
+        entry.timestamp.to_rfc3339(),
+        entry.level.to_string().to_uppercase(),
+        entry.source_id,
+        entry.message
+    );
+
+    if let Some(metadata) = &entry.metadata {
+        if !metadata.is_empty() {
+            let mut pairs: Vec<String> = metadata
+                .iter()
+                .map(|(k, v)| format!("{}={}", k, v))
+                .collect();
+            pairs.sort();
+            line.push(' ');
+            line.push('[');
+            line.push_str(&pairs.join(" "));
+            line.push(']');
+        }
+    }
+
+    line
+}
+
+/// Formats entries as plain text with no ANSI escape sequences.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PlainFormatter;
+
+impl Formatter for PlainFormatter {
+    fn format(&self, entry: &LogEntry) -> String {
+        format_plain_line(entry)
+    }
+}
+
+fn ansi_color_code(level: LogLevel) -> &'static str {
+    match level {
+        LogLevel::Trace => "\x1b[90m",  // bright black
+        LogLevel::Debug => "\x1b[36m",  // cyan
+        LogLevel::Info => "\x1b[32m",   // green
+        LogLevel::Warn => "\x1b[33m",   // yellow
+        LogLevel::Error => "\x1b[31m",  // red
+        LogLevel::Fatal => "\x1b[31m",  // red
+    }
+}
+
+const ANSI_RESET: &str = "\x1b[0m";
+
+/// Formats entries with per-level ANSI colors (red for Error/Fatal, yellow
+/// for Warn, and so on), honoring the `NO_COLOR` convention
+/// (<https://no-color.org>) in addition to an explicit `no_color` toggle.
+#[derive(Debug, Clone, Copy)]
+pub struct AnsiFormatter {
+    no_color: bool,
+}
+
+impl AnsiFormatter {
+    /// Create a formatter that colors output unless `NO_COLOR` is set in the environment.
+    pub fn new() -> Self {
+        Self {
+            no_color: std::env::var_os("NO_COLOR").is_some(),
+        }
+    }
+
+    /// Create a formatter with an explicit color toggle, still honoring `NO_COLOR`.
+    pub fn with_no_color(no_color: bool) -> Self {
+        Self {
+            no_color: no_color || std::env::var_os("NO_COLOR").is_some(),
+        }
+    }
+}
+
+impl Default for AnsiFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Formatter for AnsiFormatter {
+    fn format(&self, entry: &LogEntry) -> String {
+        let line = format_plain_line(entry);
+        if self.no_color {
+            line
+        } else {
+            format!("{}{}{}", ansi_color_code(entry.level), line, ANSI_RESET)
+        }
+    }
+}
+
+/// Errors writing to a [`RotatingFileSink`].
+#[derive(Debug)]
+pub enum SinkError {
+    /// An I/O error occurred opening or writing a log file.
+    Io(io::Error),
+}
+
+impl std::fmt::Display for SinkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SinkError::Io(e) => write!(f, "rotating file sink I/O error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for SinkError {}
+
+impl From<io::Error> for SinkError {
+    fn from(e: io::Error) -> Self {
+        SinkError::Io(e)
+    }
+}
+
+/// Writes formatted lines to disk, rolling to a new file once the active
+/// file exceeds a configurable byte capacity.
+///
+/// Rotated files are named `<base>.1`, `<base>.2`, ... in order of
+/// recency, with at most `max_rotated_files` kept; older ones are deleted.
+pub struct RotatingFileSink {
+    base_path: PathBuf,
+    capacity_bytes: u64,
+    max_rotated_files: usize,
+    file: File,
+    bytes_written: u64,
+}
+
+impl RotatingFileSink {
+    /// Open (creating if necessary) a rotating sink at `base_path` with the
+    /// default capacity and retention.
+    pub fn new(base_path: impl Into<PathBuf>) -> Result<Self, SinkError> {
+        Self::with_capacity(
+            base_path,
+            DEFAULT_ROTATION_CAPACITY,
+            DEFAULT_MAX_ROTATED_FILES,
+        )
+    }
+
+    /// Open a rotating sink with an explicit capacity and retention count.
+    pub fn with_capacity(
+        base_path: impl Into<PathBuf>,
+        capacity_bytes: u64,
+        max_rotated_files: usize,
+    ) -> Result<Self, SinkError> {
+        let base_path = base_path.into();
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&base_path)?;
+        let bytes_written = file.metadata()?.len();
+
+        Ok(Self {
+            base_path,
+            capacity_bytes,
+            max_rotated_files,
+            file,
+            bytes_written,
+        })
+    }
+
+    /// Write a single formatted line (a trailing newline is appended),
+    /// rotating first if the active file is already at capacity.
+    pub fn write_line(&mut self, line: &str) -> Result<(), SinkError> {
+        if self.bytes_written >= self.capacity_bytes {
+            self.rotate()?;
+        }
+
+        writeln!(self.file, "{}", line)?;
+        self.bytes_written += line.len() as u64 + 1;
+        Ok(())
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut name = self
+            .base_path
+            .file_name()
+            .map(|n| n.to_os_string())
+            .unwrap_or_default();
+        name.push(format!(".{}", index));
+        self.base_path.with_file_name(name)
+    }
+
+    fn rotate(&mut self) -> Result<(), SinkError> {
+        // Shift existing rotated files up by one, dropping anything that
+        // would exceed the retention count.
+        for index in (1..self.max_rotated_files).rev() {
+            let from = self.rotated_path(index);
+            let to = self.rotated_path(index + 1);
+            if from.exists() {
+                fs::rename(&from, &to)?;
+            }
+        }
+        if self.max_rotated_files > 0 {
+            let oldest = self.rotated_path(self.max_rotated_files + 1);
+            if oldest.exists() {
+                fs::remove_file(&oldest)?;
+            }
+            fs::rename(&self.base_path, self.rotated_path(1))?;
+        } else {
+            fs::remove_file(&self.base_path)?;
+        }
+
+        self.file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.base_path)?;
+        self.bytes_written = 0;
+        Ok(())
+    }
+
+    /// Path of the currently active (not-yet-rotated) file.
+    pub fn active_path(&self) -> &Path {
+        &self.base_path
+    }
+}
+
+impl LogGenerator {
+    /// Generate `count` entries, formatting and writing each to `sink`.
+    pub fn stream_to_sink(
+        &self,
+        sink: &mut RotatingFileSink,
+        formatter: &dyn Formatter,
+        count: usize,
+    ) -> Result<(), SinkError> {
+        for _ in 0..count {
+            let entry = self.generate();
+            sink.write_line(&formatter.format(&entry))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn sample_entry() -> LogEntry {
+        let mut metadata = HashMap::new();
+        metadata.insert("unit".to_string(), serde_json::json!("C"));
+        LogEntry::new("edge-temperature-001", LogLevel::Warn, "hot").with_metadata(metadata)
+    }
+
+    #[test]
+    fn test_plain_formatter_includes_core_fields() {
+        let line = PlainFormatter.format(&sample_entry());
+        assert!(line.contains("WARN"));
+        assert!(line.contains("edge-temperature-001"));
+        assert!(line.contains("hot"));
+        assert!(line.contains("unit=\"C\""));
+    }
+
+    #[test]
+    fn test_plain_formatter_has_no_escape_codes() {
+        let line = PlainFormatter.format(&sample_entry());
+        assert!(!line.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_ansi_formatter_wraps_in_color_when_enabled() {
+        let formatter = AnsiFormatter::with_no_color(false);
+        let line = formatter.format(&sample_entry());
+        assert!(line.starts_with("\x1b["));
+        assert!(line.ends_with(ANSI_RESET));
+    }
+
+    #[test]
+    fn test_ansi_formatter_respects_no_color_toggle() {
+        let formatter = AnsiFormatter::with_no_color(true);
+        let line = formatter.format(&sample_entry());
+        assert!(!line.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_error_and_fatal_use_red() {
+        assert_eq!(ansi_color_code(LogLevel::Error), ansi_color_code(LogLevel::Fatal));
+    }
+
+    #[test]
+    fn test_rotating_sink_writes_lines() {
+        let dir = std::env::temp_dir().join(format!("edge-collector-sink-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("test-writes.log");
+        let _ = fs::remove_file(&path);
+
+        let mut sink = RotatingFileSink::new(&path).unwrap();
+        sink.write_line("hello").unwrap();
+        sink.write_line("world").unwrap();
+
+        let contents = fs::read_to_string(&path).unwrap();
+        assert_eq!(contents, "hello\nworld\n");
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_rotating_sink_rotates_past_capacity() {
+        let dir = std::env::temp_dir().join(format!("edge-collector-sink-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("test-rotate.log");
+        let rotated = dir.join("test-rotate.log.1");
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(&rotated);
+
+        let mut sink = RotatingFileSink::with_capacity(&path, 10, 3).unwrap();
+        sink.write_line("0123456789").unwrap();
+        sink.write_line("after-rotation").unwrap();
+
+        assert!(rotated.exists());
+        let active = fs::read_to_string(&path).unwrap();
+        assert_eq!(active, "after-rotation\n");
+
+        fs::remove_file(&path).ok();
+        fs::remove_file(&rotated).ok();
+    }
+
+    #[test]
+    fn test_rotating_sink_bounds_retained_files() {
+        let dir = std::env::temp_dir().join(format!("edge-collector-sink-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("test-bounded.log");
+        for i in 0..=3 {
+            let _ = fs::remove_file(dir.join(format!("test-bounded.log.{}", i)));
+        }
+        let _ = fs::remove_file(&path);
+
+        let mut sink = RotatingFileSink::with_capacity(&path, 1, 2).unwrap();
+        for i in 0..10 {
+            sink.write_line(&format!("line-{}", i)).unwrap();
+        }
+
+        assert!(dir.join("test-bounded.log.1").exists());
+        assert!(dir.join("test-bounded.log.2").exists());
+        assert!(!dir.join("test-bounded.log.3").exists());
+
+        for i in 0..=2 {
+            fs::remove_file(dir.join(format!("test-bounded.log.{}", i))).ok();
+        }
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_stream_to_sink_writes_requested_count() {
+        let dir = std::env::temp_dir().join(format!("edge-collector-sink-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("test-stream.log");
+        let _ = fs::remove_file(&path);
+
+        let generator = LogGenerator::with_defaults();
+        let mut sink = RotatingFileSink::new(&path).unwrap();
+        generator
+            .stream_to_sink(&mut sink, &PlainFormatter, 5)
+            .unwrap();
+
+        let contents = fs::read_to_string(&path).unwrap();
+        assert_eq!(contents.lines().count(), 5);
+
+        fs::remove_file(&path).ok();
+    }
+}
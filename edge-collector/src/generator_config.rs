@@ -0,0 +1,622 @@
+//! Layered file/environment configuration loading for [`GeneratorConfig`].
+//!
+//! Operators want to tune `sensors_per_type`, `base_interval_ms`, `error_rate`,
+//! and `level_weights` without recompiling the collector. [`GeneratorConfig::from_layered`]
+//! loads a base TOML/YAML/JSON file, overlays an optional environment-specific
+//! file, then overlays `EDGEGEN_`-prefixed environment variables on top —
+//! later layers win, following the same merge-then-override model as
+//! `config-maint`-style layered config loaders. [`watch`] re-runs that same
+//! layering whenever one of the watched files changes, pushing the refreshed
+//! [`GeneratorConfig`] down a channel so a running generator can pick up new
+//! weights/rates without a restart.
+//!
+//! [`GeneratorConfigBuilder`] (via [`GeneratorConfig::builder`]) offers the
+//! same layering as a fluent builder for deployments that want a fourth,
+//! highest-precedence layer of explicit in-code overrides on top of the file
+//! and environment layers — baked-in defaults, then an optional file, then
+//! `EDGE_COLLECTOR_`-prefixed environment variables, then whatever the
+//! builder's setters were called with.
+
+use std::collections::HashMap;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use serde::Deserialize;
+
+use crate::log_generator::GeneratorConfig;
+
+/// Environment variable prefix used by the [`GeneratorConfig::from_layered`]
+/// examples.
+pub const DEFAULT_ENV_PREFIX: &str = "EDGEGEN";
+
+/// Environment variable prefix [`GeneratorConfigBuilder`] uses by default,
+/// matching the `EDGE_COLLECTOR_*` convention already used by [`crate::config::Config`].
+pub const DEFAULT_BUILDER_ENV_PREFIX: &str = "EDGE_COLLECTOR";
+
+/// Default `poll_interval` to pass to [`watch`]; `watch` polls at whatever
+/// interval its caller passes.
+pub const DEFAULT_WATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Error loading or validating a layered [`GeneratorConfig`].
+#[derive(Debug)]
+pub struct GeneratorConfigError {
+    pub message: String,
+    pub source_path: Option<PathBuf>,
+}
+
+impl GeneratorConfigError {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            source_path: None,
+        }
+    }
+
+    fn in_file(path: &Path, message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            source_path: Some(path.to_path_buf()),
+        }
+    }
+}
+
+impl std::fmt::Display for GeneratorConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.source_path {
+            Some(path) => write!(f, "generator config error in {}: {}", path.display(), self.message),
+            None => write!(f, "generator config error: {}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for GeneratorConfigError {}
+
+/// Partial, all-optional view of [`GeneratorConfig`] that a single layer
+/// (file or environment) may override.
+///
+/// `custom_sensors` is intentionally not part of this patch: templates are
+/// Rust trait objects registered via [`GeneratorConfig::with_sensor`] and
+/// are not something a TOML/YAML/JSON file can describe.
+#[derive(Debug, Clone, Default, Deserialize)]
+struct ConfigPatch {
+    sensors_per_type: Option<usize>,
+    base_interval_ms: Option<u64>,
+    include_metadata: Option<bool>,
+    error_rate: Option<f64>,
+    level_weights: Option<[u32; 6]>,
+}
+
+impl ConfigPatch {
+    /// Overlay `other` on top of `self`: fields present in `other` win.
+    fn merge(self, other: ConfigPatch) -> ConfigPatch {
+        ConfigPatch {
+            sensors_per_type: other.sensors_per_type.or(self.sensors_per_type),
+            base_interval_ms: other.base_interval_ms.or(self.base_interval_ms),
+            include_metadata: other.include_metadata.or(self.include_metadata),
+            error_rate: other.error_rate.or(self.error_rate),
+            level_weights: other.level_weights.or(self.level_weights),
+        }
+    }
+
+    /// Apply this patch on top of `base`, returning a fully-formed config.
+    fn apply_to(&self, mut base: GeneratorConfig) -> GeneratorConfig {
+        if let Some(v) = self.sensors_per_type {
+            base.sensors_per_type = v;
+        }
+        if let Some(v) = self.base_interval_ms {
+            base.base_interval_ms = v;
+        }
+        if let Some(v) = self.include_metadata {
+            base.include_metadata = v;
+        }
+        if let Some(v) = self.error_rate {
+            base.error_rate = v;
+        }
+        if let Some(v) = self.level_weights {
+            base.level_weights = v;
+        }
+        base
+    }
+}
+
+/// Parse a single config file into a [`ConfigPatch`], dispatching on extension.
+fn parse_file(path: &Path) -> Result<ConfigPatch, GeneratorConfigError> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| GeneratorConfigError::in_file(path, format!("failed to read file: {}", e)))?;
+
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("toml") => toml::from_str(&contents)
+            .map_err(|e| GeneratorConfigError::in_file(path, format!("invalid TOML: {}", e))),
+        Some("yaml") | Some("yml") => serde_yaml::from_str(&contents)
+            .map_err(|e| GeneratorConfigError::in_file(path, format!("invalid YAML: {}", e))),
+        Some("json") => serde_json::from_str(&contents)
+            .map_err(|e| GeneratorConfigError::in_file(path, format!("invalid JSON: {}", e))),
+        Some(other) => Err(GeneratorConfigError::in_file(
+            path,
+            format!("unrecognized config extension '{}' (expected toml, yaml, yml, or json)", other),
+        )),
+        None => Err(GeneratorConfigError::in_file(path, "config file has no extension")),
+    }
+}
+
+/// Build a [`ConfigPatch`] from `EDGEGEN_`-prefixed (or `env_prefix`-prefixed)
+/// environment variables.
+fn patch_from_env(env_prefix: &str) -> Result<ConfigPatch, GeneratorConfigError> {
+    let vars: HashMap<String, String> = env::vars().collect();
+    let key = |name: &str| format!("{}_{}", env_prefix, name);
+
+    let sensors_per_type = match vars.get(&key("SENSORS_PER_TYPE")) {
+        Some(v) => Some(v.parse().map_err(|_| {
+            GeneratorConfigError::new(format!("'{}' is not a valid sensors_per_type", v))
+        })?),
+        None => None,
+    };
+
+    let base_interval_ms = match vars.get(&key("BASE_INTERVAL_MS")) {
+        Some(v) => Some(v.parse().map_err(|_| {
+            GeneratorConfigError::new(format!("'{}' is not a valid base_interval_ms", v))
+        })?),
+        None => None,
+    };
+
+    let include_metadata = match vars.get(&key("INCLUDE_METADATA")) {
+        Some(v) => Some(v.parse().map_err(|_| {
+            GeneratorConfigError::new(format!("'{}' is not a valid include_metadata (use true/false)", v))
+        })?),
+        None => None,
+    };
+
+    let error_rate = match vars.get(&key("ERROR_RATE")) {
+        Some(v) => Some(v.parse().map_err(|_| {
+            GeneratorConfigError::new(format!("'{}' is not a valid error_rate", v))
+        })?),
+        None => None,
+    };
+
+    let level_weights = match vars.get(&key("LEVEL_WEIGHTS")) {
+        Some(v) => Some(parse_level_weights(v)?),
+        None => None,
+    };
+
+    Ok(ConfigPatch {
+        sensors_per_type,
+        base_interval_ms,
+        include_metadata,
+        error_rate,
+        level_weights,
+    })
+}
+
+/// Parse a comma-separated `"5,15,60,12,7,1"` string into six level weights.
+fn parse_level_weights(value: &str) -> Result<[u32; 6], GeneratorConfigError> {
+    let parsed: Vec<u32> = value
+        .split(',')
+        .map(|part| {
+            part.trim()
+                .parse()
+                .map_err(|_| GeneratorConfigError::new(format!("'{}' is not a valid weight", part)))
+        })
+        .collect::<Result<_, _>>()?;
+
+    validate_level_weights(&parsed)?;
+
+    let mut weights = [0u32; 6];
+    weights.copy_from_slice(&parsed);
+    Ok(weights)
+}
+
+/// Validate that a level weight vector has exactly six entries (one per
+/// [`crate::log_generator::LogLevel`]).
+fn validate_level_weights(weights: &[u32]) -> Result<(), GeneratorConfigError> {
+    if weights.len() != 6 {
+        return Err(GeneratorConfigError::new(format!(
+            "level_weights must have exactly 6 entries (trace, debug, info, warn, error, fatal), got {}",
+            weights.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Validate that `error_rate` is a proportion in `0.0..=1.0`.
+fn validate_error_rate(error_rate: f64) -> Result<(), GeneratorConfigError> {
+    if !(0.0..=1.0).contains(&error_rate) {
+        return Err(GeneratorConfigError::new(format!(
+            "error_rate must be between 0.0 and 1.0, got {}",
+            error_rate
+        )));
+    }
+    Ok(())
+}
+
+/// Validate that `sensors_per_type` is non-zero (zero would generate no
+/// logs at all).
+fn validate_sensors_per_type(sensors_per_type: usize) -> Result<(), GeneratorConfigError> {
+    if sensors_per_type == 0 {
+        return Err(GeneratorConfigError::new(
+            "sensors_per_type must be greater than 0",
+        ));
+    }
+    Ok(())
+}
+
+/// Validate every field a [`ConfigPatch`] sets, applying `patch` on top of
+/// [`GeneratorConfig::default`] only once validation passes.
+fn validate_and_apply(patch: ConfigPatch) -> Result<GeneratorConfig, GeneratorConfigError> {
+    if let Some(sensors_per_type) = patch.sensors_per_type {
+        validate_sensors_per_type(sensors_per_type)?;
+    }
+    if let Some(weights) = patch.level_weights {
+        validate_level_weights(&weights)?;
+    }
+    if let Some(error_rate) = patch.error_rate {
+        validate_error_rate(error_rate)?;
+    }
+
+    Ok(patch.apply_to(GeneratorConfig::default()))
+}
+
+impl GeneratorConfig {
+    /// Load a [`GeneratorConfig`] from a stack of layered config files plus
+    /// `env_prefix`-prefixed environment variables.
+    ///
+    /// `paths` are applied in order, each one overriding only the fields it
+    /// sets in the previous layer (e.g. a `base.toml` followed by a
+    /// `production.yaml`); missing files are skipped rather than treated as
+    /// errors, so operators can pass an optional environment-specific path
+    /// that doesn't always exist. Environment variables are applied last and
+    /// win over every file layer.
+    ///
+    /// Starts from [`GeneratorConfig::default`] for any field no layer sets.
+    /// `custom_sensors` is always empty on the returned config — register
+    /// templates afterwards with [`GeneratorConfig::with_sensor`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GeneratorConfigError`] if a present file fails to parse, an
+    /// environment variable is present but malformed, or the merged
+    /// `level_weights`/`error_rate` fail validation.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use edge_collector::generator_config::DEFAULT_ENV_PREFIX;
+    /// use edge_collector::log_generator::GeneratorConfig;
+    ///
+    /// let config = GeneratorConfig::from_layered(
+    ///     &["config/base.toml", "config/production.toml"],
+    ///     DEFAULT_ENV_PREFIX,
+    /// )
+    /// .expect("failed to load generator config");
+    /// ```
+    pub fn from_layered(
+        paths: &[impl AsRef<Path>],
+        env_prefix: &str,
+    ) -> Result<GeneratorConfig, GeneratorConfigError> {
+        let mut patch = ConfigPatch::default();
+
+        for path in paths {
+            let path = path.as_ref();
+            if !path.exists() {
+                continue;
+            }
+            patch = patch.merge(parse_file(path)?);
+        }
+
+        patch = patch.merge(patch_from_env(env_prefix)?);
+
+        validate_and_apply(patch)
+    }
+
+    /// Start building a [`GeneratorConfig`] from layered defaults, an
+    /// optional file, environment variables, and explicit overrides, in that
+    /// precedence order. See [`GeneratorConfigBuilder`].
+    pub fn builder() -> GeneratorConfigBuilder {
+        GeneratorConfigBuilder::new()
+    }
+}
+
+/// Builder that layers a [`GeneratorConfig`] from baked-in defaults, an
+/// optional TOML/YAML/JSON file (auto-detected by extension), environment
+/// variables, and finally explicit overrides — each layer overriding only
+/// the fields the previous one set, with later layers winning.
+///
+/// Unlike [`GeneratorConfig::from_layered`] (file(s) + env only), this adds
+/// explicit setter overrides as the highest-precedence layer, so the same
+/// binary can ship one profile file per environment and still let a caller
+/// pin specific fields in code.
+#[derive(Debug, Default)]
+pub struct GeneratorConfigBuilder {
+    path: Option<PathBuf>,
+    env_prefix: Option<String>,
+    overrides: ConfigPatch,
+}
+
+impl GeneratorConfigBuilder {
+    fn new() -> Self {
+        Self {
+            path: None,
+            env_prefix: None,
+            overrides: ConfigPatch::default(),
+        }
+    }
+
+    /// Load an optional config file as the second layer. A missing file is
+    /// skipped rather than treated as an error.
+    pub fn file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Override the environment variable prefix (defaults to
+    /// [`DEFAULT_BUILDER_ENV_PREFIX`]).
+    pub fn env_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.env_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Explicitly override `sensors_per_type`, taking precedence over the
+    /// file and environment layers.
+    pub fn sensors_per_type(mut self, value: usize) -> Self {
+        self.overrides.sensors_per_type = Some(value);
+        self
+    }
+
+    /// Explicitly override `base_interval_ms`, taking precedence over the
+    /// file and environment layers.
+    pub fn base_interval_ms(mut self, value: u64) -> Self {
+        self.overrides.base_interval_ms = Some(value);
+        self
+    }
+
+    /// Explicitly override `include_metadata`, taking precedence over the
+    /// file and environment layers.
+    pub fn include_metadata(mut self, value: bool) -> Self {
+        self.overrides.include_metadata = Some(value);
+        self
+    }
+
+    /// Explicitly override `error_rate`, taking precedence over the file and
+    /// environment layers.
+    pub fn error_rate(mut self, value: f64) -> Self {
+        self.overrides.error_rate = Some(value);
+        self
+    }
+
+    /// Explicitly override `level_weights`, taking precedence over the file
+    /// and environment layers.
+    pub fn level_weights(mut self, value: [u32; 6]) -> Self {
+        self.overrides.level_weights = Some(value);
+        self
+    }
+
+    /// Resolve all four layers into a validated [`GeneratorConfig`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GeneratorConfigError`] if the file layer fails to parse, an
+    /// environment variable is malformed, or the merged config fails
+    /// validation (`sensors_per_type == 0`, `error_rate` outside
+    /// `0.0..=1.0`, or `level_weights` without exactly 6 entries).
+    pub fn build(self) -> Result<GeneratorConfig, GeneratorConfigError> {
+        let mut patch = ConfigPatch::default();
+
+        if let Some(path) = &self.path {
+            if path.exists() {
+                patch = patch.merge(parse_file(path)?);
+            }
+        }
+
+        let env_prefix = self.env_prefix.as_deref().unwrap_or(DEFAULT_BUILDER_ENV_PREFIX);
+        patch = patch.merge(patch_from_env(env_prefix)?);
+        patch = patch.merge(self.overrides);
+
+        validate_and_apply(patch)
+    }
+}
+
+/// Watch `paths` for modifications and push a freshly re-layered
+/// [`GeneratorConfig`] through the returned channel each time one changes.
+///
+/// Polls file modification times every `poll_interval` on a background
+/// thread (no filesystem notification API is assumed to be available at the
+/// edge). A config that fails to load on reload is skipped: nothing is sent
+/// and the failure is not reported, so the receiver keeps the previously
+/// loaded config.
+///
+/// The background thread exits the first time a reloaded config cannot be
+/// sent because the returned receiver has been dropped.
+pub fn watch(
+    paths: Vec<PathBuf>,
+    env_prefix: String,
+    poll_interval: Duration,
+) -> tokio::sync::mpsc::UnboundedReceiver<GeneratorConfig> {
+    let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+
+    std::thread::spawn(move || {
+        let mut last_mtimes: Vec<Option<SystemTime>> = paths.iter().map(|p| mtime(p)).collect();
+
+        loop {
+            std::thread::sleep(poll_interval);
+
+            let current_mtimes: Vec<Option<SystemTime>> = paths.iter().map(|p| mtime(p)).collect();
+            if current_mtimes == last_mtimes {
+                continue;
+            }
+            last_mtimes = current_mtimes;
+
+            match GeneratorConfig::from_layered(&paths, &env_prefix) {
+                Ok(config) => {
+                    if tx.send(config).is_err() {
+                        // Receiver dropped; stop watching.
+                        break;
+                    }
+                }
+                Err(_) => {
+                    // Keep running on the last good config; the failure is
+                    // not reported.
+                    continue;
+                }
+            }
+        }
+    });
+
+    rx
+}
+
+fn mtime(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn write_temp_file(dir: &Path, name: &str, contents: &str) -> PathBuf {
+        let path = dir.join(name);
+        let mut file = std::fs::File::create(&path).unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_missing_paths_fall_back_to_defaults() {
+        let config = GeneratorConfig::from_layered(
+            &["/nonexistent/base.toml", "/nonexistent/prod.toml"],
+            "EDGEGEN_TEST_MISSING",
+        )
+        .expect("missing files should be skipped, not error");
+
+        assert_eq!(config.sensors_per_type, GeneratorConfig::default().sensors_per_type);
+    }
+
+    #[test]
+    fn test_base_file_overridden_by_env_specific_file() {
+        let dir = std::env::temp_dir();
+        let base = write_temp_file(
+            &dir,
+            "edgegen_test_base.toml",
+            "sensors_per_type = 3\nerror_rate = 0.05\n",
+        );
+        let prod = write_temp_file(&dir, "edgegen_test_prod.toml", "sensors_per_type = 10\n");
+
+        let config = GeneratorConfig::from_layered(&[&base, &prod], "EDGEGEN_TEST_LAYER_NOPE")
+            .expect("layered load should succeed");
+
+        assert_eq!(config.sensors_per_type, 10); // prod wins
+        assert!((config.error_rate - 0.05).abs() < f64::EPSILON); // base still applies
+
+        std::fs::remove_file(&base).ok();
+        std::fs::remove_file(&prod).ok();
+    }
+
+    #[test]
+    fn test_env_wins_over_files() {
+        let dir = std::env::temp_dir();
+        let base = write_temp_file(&dir, "edgegen_test_env_wins.toml", "sensors_per_type = 3\n");
+
+        env::set_var("EDGEGEN_TEST_ENVWIN_SENSORS_PER_TYPE", "42");
+        let config = GeneratorConfig::from_layered(&[&base], "EDGEGEN_TEST_ENVWIN")
+            .expect("layered load should succeed");
+        env::remove_var("EDGEGEN_TEST_ENVWIN_SENSORS_PER_TYPE");
+
+        assert_eq!(config.sensors_per_type, 42);
+
+        std::fs::remove_file(&base).ok();
+    }
+
+    #[test]
+    fn test_invalid_error_rate_is_rejected() {
+        let dir = std::env::temp_dir();
+        let base = write_temp_file(&dir, "edgegen_test_bad_rate.toml", "error_rate = 1.5\n");
+
+        let result = GeneratorConfig::from_layered(&[&base], "EDGEGEN_TEST_BAD_RATE_NOPE");
+        assert!(result.is_err());
+        assert!(result.unwrap_err().message.contains("error_rate"));
+
+        std::fs::remove_file(&base).ok();
+    }
+
+    #[test]
+    fn test_level_weights_must_have_six_entries() {
+        assert!(validate_level_weights(&[1, 2, 3]).is_err());
+        assert!(validate_level_weights(&[1, 2, 3, 4, 5, 6]).is_ok());
+    }
+
+    #[test]
+    fn test_parse_level_weights_from_env_string() {
+        let weights = parse_level_weights("5, 15, 60, 12, 7, 1").unwrap();
+        assert_eq!(weights, [5, 15, 60, 12, 7, 1]);
+    }
+
+    #[test]
+    fn test_unrecognized_extension_errors() {
+        let dir = std::env::temp_dir();
+        let path = write_temp_file(&dir, "edgegen_test_bad_ext.ini", "sensors_per_type = 3\n");
+
+        let result = GeneratorConfig::from_layered(&[&path], "EDGEGEN_TEST_BAD_EXT_NOPE");
+        assert!(result.is_err());
+        assert!(result.unwrap_err().message.contains("unrecognized"));
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_builder_defaults_match_generator_config_default() {
+        let config = GeneratorConfig::builder()
+            .env_prefix("EDGE_COLLECTOR_TEST_BUILDER_DEFAULTS_NOPE")
+            .build()
+            .expect("builder with no layers should succeed");
+
+        assert_eq!(config.sensors_per_type, GeneratorConfig::default().sensors_per_type);
+        assert_eq!(config.error_rate, GeneratorConfig::default().error_rate);
+    }
+
+    #[test]
+    fn test_builder_file_overridden_by_env_overridden_by_explicit() {
+        let dir = std::env::temp_dir();
+        let base = write_temp_file(
+            &dir,
+            "edgegen_test_builder_layers.toml",
+            "sensors_per_type = 3\nbase_interval_ms = 50\n",
+        );
+
+        env::set_var("EDGE_COLLECTOR_TEST_BUILDER_LAYERS_SENSORS_PER_TYPE", "7");
+
+        let config = GeneratorConfig::builder()
+            .file(&base)
+            .env_prefix("EDGE_COLLECTOR_TEST_BUILDER_LAYERS")
+            .base_interval_ms(999) // explicit override wins over the file
+            .build()
+            .expect("layered build should succeed");
+
+        env::remove_var("EDGE_COLLECTOR_TEST_BUILDER_LAYERS_SENSORS_PER_TYPE");
+        std::fs::remove_file(&base).ok();
+
+        assert_eq!(config.sensors_per_type, 7); // env wins over file
+        assert_eq!(config.base_interval_ms, 999); // explicit override wins over both
+    }
+
+    #[test]
+    fn test_builder_rejects_zero_sensors_per_type() {
+        let result = GeneratorConfig::builder()
+            .env_prefix("EDGE_COLLECTOR_TEST_BUILDER_ZERO_NOPE")
+            .sensors_per_type(0)
+            .build();
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().message.contains("sensors_per_type"));
+    }
+
+    #[test]
+    fn test_builder_rejects_invalid_error_rate() {
+        let result = GeneratorConfig::builder()
+            .env_prefix("EDGE_COLLECTOR_TEST_BUILDER_BAD_RATE_NOPE")
+            .error_rate(1.5)
+            .build();
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().message.contains("error_rate"));
+    }
+}
@@ -3,9 +3,25 @@
 //! This library provides components for edge-to-cloud log streaming:
 //!
 //! - **config**: Environment-based configuration for the edge collector
+//! - **generator_config**: Layered file/environment configuration for `GeneratorConfig`
 //! - **log_generator**: Simulated sensor log generation for testing
 //! - **buffer**: Async buffering with size and time-based flush triggers
 //! - **client**: HTTP client with connection pooling and retry logic
+//! - **benchmark**: Built-in load-generation mode for tuning batch size and flush interval
+//! - **binary**: Compact binary datagram encoding as an alternative to JSON
+//! - **sensor_template**: Template-driven custom sensor definitions
+//! - **filter**: Bitmask severity filtering with named presets
+//! - **stateful**: Autocorrelated, mean-reverting sensor state with anomaly episodes
+//! - **formatter**: Human-readable/colored formatting and size-rotating file output
+//! - **otlp**: OTLP (OpenTelemetry) logs export, as JSON or hand-rolled protobuf
+//! - **msgpack**: Compact MessagePack wire format for `LogBatch` (`msgpack` feature)
+//! - **sensor_source**: Real hwmon/sysfs sensor readings, with a synthetic fallback
+//! - **sink**: Concurrent batch transmission with bounded in-flight backpressure
+//! - **concurrency**: AIMD-adaptive concurrency limiting for client sends
+//! - **rate_limiter**: Token-bucket send-rate limiting for client sends
+//! - **multi_stream**: Keyed multi-stream batching, flushing each source independently
+//! - **persistence**: Disk-backed spill-and-replay for batches that exhaust client retries
+//! - **transport**: Pluggable `LogSink` trait selecting HTTP or Kafka delivery (`kafka` feature)
 //!
 //! # Example
 //!
@@ -41,13 +57,57 @@
 //! ```
 
 // Module declarations
+pub mod benchmark;
+pub mod binary;
 pub mod buffer;
+pub mod circuit_breaker;
 pub mod client;
+pub mod concurrency;
 pub mod config;
+pub mod filter;
+pub mod formatter;
+pub mod generator_config;
 pub mod log_generator;
+pub mod msgpack;
+pub mod multi_stream;
+pub mod otlp;
+pub mod persistence;
+pub mod rate_limiter;
+pub mod sensor_source;
+pub mod sensor_template;
+pub mod sink;
+pub mod stateful;
+pub mod transport;
+pub mod tuner;
 
 // Re-export commonly used types at crate root for convenience
-pub use buffer::{BufferConfig, BufferError, BufferSender, BufferStats, LogBuffer};
-pub use client::{ClientError, ClientStats, IngestResponse, LogClient, TrackedLogClient};
-pub use config::{Config, ConfigError};
+pub use benchmark::{BenchmarkConfig, BenchmarkResult};
+pub use binary::{DecodeError, MAX_DATAGRAM_LEN};
+pub use buffer::{BufferConfig, BufferError, BufferSender, BufferStats, LogBuffer, OverflowPolicy};
+pub use client::{ClientError, ClientStats, IngestResponse, LogClient, RequestConfig, TrackedLogClient};
+pub use concurrency::{AimdLimiter, AimdLimiterConfig};
+pub use config::{Config, ConfigError, ConfigHandle, DEFAULT_CONFIG_WATCH_INTERVAL};
+pub use filter::{FilterError, LevelMask, LogFilter};
+pub use formatter::{AnsiFormatter, Formatter, PlainFormatter, RotatingFileSink, SinkError};
+pub use generator_config::{
+    GeneratorConfigBuilder, GeneratorConfigError, DEFAULT_BUILDER_ENV_PREFIX, DEFAULT_ENV_PREFIX,
+    DEFAULT_WATCH_INTERVAL,
+};
 pub use log_generator::{GeneratorConfig, LogBatch, LogEntry, LogGenerator, LogLevel, SensorType};
+pub use msgpack::WireFormat;
+#[cfg(feature = "msgpack")]
+pub use msgpack::MsgPackError;
+pub use multi_stream::{MultiStreamBuffer, MultiStreamConfig, MultiStreamStats};
+pub use otlp::{AnyValue, ExportLogsServiceRequest, KeyValue, LogRecord, Resource, ResourceLogs, ScopeLogs};
+pub use persistence::{DurableSink, PersistenceError, SpillConfig};
+pub use rate_limiter::{TokenBucket, TokenBucketConfig};
+pub use sensor_source::{HwmonSensorSource, SensorSource, SyntheticSensorSource, DEFAULT_HWMON_ROOT};
+pub use sensor_template::{SensorRegistry, SensorTemplate};
+pub use sink::{
+    spawn_batch_sink, BatchSink, DeliveryResult, DeliveryStream, SinkConfig,
+    SinkError as BatchSinkError,
+};
+pub use stateful::StatefulGenerator;
+pub use transport::{build_sink, HttpSink, KafkaConfig, LogSink, TransportError, TransportKind};
+#[cfg(feature = "kafka")]
+pub use transport::KafkaSink;
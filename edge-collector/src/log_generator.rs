@@ -6,12 +6,14 @@
 use chrono::{DateTime, Utc};
 use rand::distributions::{Distribution, WeightedIndex};
 use rand::Rng;
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use uuid::Uuid;
 
 /// Log severity levels matching the Python API schema.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum LogLevel {
     Trace,
@@ -97,6 +99,14 @@ impl LogEntry {
         self.metadata = Some(metadata);
         self
     }
+
+    /// Estimate this entry's on-wire JSON size in bytes.
+    ///
+    /// Used by [`crate::buffer`] for byte-budget flush decisions; falls back
+    /// to 0 if serialization fails, which shouldn't happen for a valid entry.
+    pub fn estimated_size(&self) -> usize {
+        serde_json::to_vec(self).map(|bytes| bytes.len()).unwrap_or(0)
+    }
 }
 
 /// A batch of log entries to send to the API.
@@ -196,7 +206,7 @@ impl SensorType {
 }
 
 /// Configuration for the log generator.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct GeneratorConfig {
     /// Number of simulated sensors per type
     pub sensors_per_type: usize,
@@ -209,6 +219,29 @@ pub struct GeneratorConfig {
 
     /// Error rate (0.0 - 1.0) for generating error/warning logs
     pub error_rate: f64,
+
+    /// Relative sampling weights for `[Trace, Debug, Info, Warn, Error, Fatal]`,
+    /// in that order. Higher weight means the level is sampled more often; see
+    /// [`LogLevel::all`] for the level ordering this lines up with.
+    pub level_weights: [u32; 6],
+
+    /// Additional sensor templates registered via [`GeneratorConfig::with_sensor`],
+    /// sampled from alongside the eight built-ins when generating through
+    /// [`LogGenerator::generate_templated`].
+    pub(crate) custom_sensors: Vec<std::sync::Arc<dyn crate::sensor_template::SensorTemplate + Send + Sync>>,
+}
+
+impl std::fmt::Debug for GeneratorConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GeneratorConfig")
+            .field("sensors_per_type", &self.sensors_per_type)
+            .field("base_interval_ms", &self.base_interval_ms)
+            .field("include_metadata", &self.include_metadata)
+            .field("error_rate", &self.error_rate)
+            .field("level_weights", &self.level_weights)
+            .field("custom_sensors", &self.custom_sensors.len())
+            .finish()
+    }
 }
 
 impl Default for GeneratorConfig {
@@ -218,10 +251,27 @@ impl Default for GeneratorConfig {
             base_interval_ms: 100,
             include_metadata: true,
             error_rate: 0.05, // 5% error rate
+            // Trace: 5%, Debug: 15%, Info: 60%, Warn: 12%, Error: 7%, Fatal: 1%
+            level_weights: [5, 15, 60, 12, 7, 1],
+            custom_sensors: Vec::new(),
         }
     }
 }
 
+impl GeneratorConfig {
+    /// Register an additional sensor template to sample from.
+    ///
+    /// This lets callers add sensor kinds (e.g. `co2`, `soil_moisture`)
+    /// without editing the crate; see [`crate::sensor_template::SensorTemplate`].
+    pub fn with_sensor(
+        mut self,
+        template: std::sync::Arc<dyn crate::sensor_template::SensorTemplate + Send + Sync>,
+    ) -> Self {
+        self.custom_sensors.push(template);
+        self
+    }
+}
+
 /// Log generator for simulating edge sensor data.
 ///
 /// The generator creates realistic dummy sensor logs with weighted log levels
@@ -229,19 +279,39 @@ impl Default for GeneratorConfig {
 pub struct LogGenerator {
     config: GeneratorConfig,
     level_weights: WeightedIndex<u32>,
+    sensor_source: Box<dyn crate::sensor_source::SensorSource>,
 }
 
 impl LogGenerator {
     /// Create a new log generator with the given configuration.
+    ///
+    /// Reads generated via [`LogGenerator::generate_from_source`] come from a
+    /// [`crate::sensor_source::SyntheticSensorSource`] by default; use
+    /// [`LogGenerator::with_source`] to read real hardware instead.
     pub fn new(config: GeneratorConfig) -> Self {
-        // Weight log levels: mostly INFO, some DEBUG, occasional WARN/ERROR
-        // Trace: 5%, Debug: 15%, Info: 60%, Warn: 12%, Error: 7%, Fatal: 1%
-        let weights = vec![5, 15, 60, 12, 7, 1];
-        let level_weights = WeightedIndex::new(&weights).expect("Invalid weights");
+        Self::with_source(
+            config,
+            Box::new(crate::sensor_source::SyntheticSensorSource::new()),
+        )
+    }
+
+    /// Create a new log generator that reads sensor values from `sensor_source`
+    /// instead of the built-in synthetic ranges.
+    ///
+    /// This is what lets the same batching/serialization code emit real
+    /// telemetry on-device (a [`crate::sensor_source::HwmonSensorSource`]) and
+    /// synthetic data in tests (a [`crate::sensor_source::SyntheticSensorSource`]).
+    pub fn with_source(
+        config: GeneratorConfig,
+        sensor_source: Box<dyn crate::sensor_source::SensorSource>,
+    ) -> Self {
+        let level_weights =
+            WeightedIndex::new(config.level_weights).expect("Invalid level weights");
 
         Self {
             config,
             level_weights,
+            sensor_source,
         }
     }
 
@@ -282,6 +352,171 @@ impl LogGenerator {
         (0..count).map(|_| self.generate()).collect()
     }
 
+    /// Generate `count` log entries in parallel using rayon, for load-testing
+    /// scenarios that need tens of thousands of synthetic logs.
+    ///
+    /// Entries are independent, so `generate()`'s per-call work parallelizes
+    /// directly across worker threads — except the per-sensor `sequence`
+    /// metadata counter, which [`LogGenerator::generate`] fills from
+    /// `rng.gen_range`. Sharing that across threads would risk duplicate
+    /// sequence numbers for the same sensor, so this instead precomputes the
+    /// fixed set of possible `source_id`s once and hands each one an
+    /// [`AtomicU64`] counter: every worker does a lock-free `fetch_add` on the
+    /// counter for the `source_id` it happens to generate, so two entries for
+    /// the same sensor can never collide on `sequence` regardless of
+    /// scheduling. Output order matches `0..count`, same as `generate_batch`.
+    pub fn generate_batch_parallel(&self, count: usize) -> Vec<LogEntry> {
+        let sequence_counters = self.sequence_counters();
+
+        (0..count)
+            .into_par_iter()
+            .map(|_| self.generate_with_counters(&sequence_counters))
+            .collect()
+    }
+
+    /// Build one `AtomicU64` sequence counter per possible `source_id`
+    /// (every [`SensorType`] crossed with every `sensors_per_type` instance).
+    fn sequence_counters(&self) -> HashMap<String, AtomicU64> {
+        SensorType::all()
+            .iter()
+            .flat_map(|sensor_type| {
+                (1..=self.config.sensors_per_type)
+                    .map(move |instance| format!("edge-{}-{:03}", sensor_type.name(), instance))
+            })
+            .map(|source_id| (source_id, AtomicU64::new(1)))
+            .collect()
+    }
+
+    /// Like [`LogGenerator::generate`], but sources the `sequence` metadata
+    /// value from `counters` instead of `rng.gen_range`, so it's safe to call
+    /// from multiple threads concurrently.
+    fn generate_with_counters(&self, counters: &HashMap<String, AtomicU64>) -> LogEntry {
+        let mut rng = rand::thread_rng();
+
+        let sensor_types = SensorType::all();
+        let sensor_type = sensor_types[rng.gen_range(0..sensor_types.len())];
+        let sensor_instance = rng.gen_range(1..=self.config.sensors_per_type);
+        let source_id = format!("edge-{}-{:03}", sensor_type.name(), sensor_instance);
+
+        let level = LogLevel::all()[self.level_weights.sample(&mut rng)];
+        let (message, mut metadata) = self.generate_sensor_data(&mut rng, sensor_type, level);
+
+        if let Some(counter) = counters.get(&source_id) {
+            let sequence = counter.fetch_add(1, Ordering::Relaxed);
+            metadata.insert(
+                "sequence".to_string(),
+                serde_json::Value::Number(sequence.into()),
+            );
+        }
+
+        let mut entry = LogEntry::new(source_id, level, message);
+
+        if self.config.include_metadata {
+            entry = entry.with_metadata(metadata);
+        }
+
+        entry
+    }
+
+    /// Generate a single entry by dispatching over a [`SensorRegistry`]
+    /// instead of the hardcoded `SensorType` match.
+    ///
+    /// The registry includes the eight built-in sensors plus any templates
+    /// registered via [`GeneratorConfig::with_sensor`], so custom sensor
+    /// kinds participate in generation exactly like the built-ins.
+    ///
+    /// Returns `None` only if the registry has no templates, which does not
+    /// happen here: the eight built-ins are always registered.
+    pub fn generate_templated(&self) -> Option<LogEntry> {
+        let mut rng = rand::thread_rng();
+        let registry = self.sensor_registry();
+        let template = registry.sample(&mut rng)?;
+
+        let sensor_instance = rng.gen_range(1..=self.config.sensors_per_type);
+        let source_id = format!("edge-{}-{:03}", template.name(), sensor_instance);
+
+        let level = LogLevel::all()[self.level_weights.sample(&mut rng)];
+        let (reading, extra, message) = template.generate(&mut rng, level);
+        let metadata = crate::sensor_template::build_metadata(template, reading, extra, &mut rng);
+
+        let mut entry = LogEntry::new(source_id, level, message);
+        if self.config.include_metadata {
+            entry = entry.with_metadata(metadata);
+        }
+
+        Some(entry)
+    }
+
+    /// Generate a single entry by reading this generator's [`SensorSource`]
+    /// instead of synthesizing a reading internally.
+    ///
+    /// [`SensorSource::read`] returns `None` when a sensor file is absent or
+    /// unreadable (or the sensor type has no real-hardware mapping at all),
+    /// in which case this falls back to a synthetic reading for the same
+    /// sensor type from the built-in [`crate::sensor_template::SensorRegistry`],
+    /// so the batch always gets a value.
+    ///
+    /// [`SensorSource::read`]: crate::sensor_source::SensorSource::read
+    pub fn generate_from_source(&self) -> LogEntry {
+        let mut rng = rand::thread_rng();
+
+        let sensor_types = SensorType::all();
+        let sensor_type = sensor_types[rng.gen_range(0..sensor_types.len())];
+        let sensor_instance = rng.gen_range(1..=self.config.sensors_per_type);
+        let source_id = format!("edge-{}-{:03}", sensor_type.name(), sensor_instance);
+
+        let (reading, from_hardware) = match self.sensor_source.read(sensor_type) {
+            Some(value) => (value, true),
+            None => {
+                let registry = crate::sensor_template::SensorRegistry::with_defaults();
+                let value = registry
+                    .get(sensor_type.name())
+                    .map(|template| template.generate(&mut rng, LogLevel::Info).0)
+                    .unwrap_or(0.0);
+                (value, false)
+            }
+        };
+
+        let message = format!("{} reading: {:.2} {}", sensor_type.name(), reading, sensor_type.unit());
+
+        let mut entry = LogEntry::new(source_id, LogLevel::Info, message);
+
+        if self.config.include_metadata {
+            let mut metadata = HashMap::new();
+            metadata.insert(
+                "sensor_type".to_string(),
+                serde_json::Value::String(sensor_type.name().to_string()),
+            );
+            metadata.insert(
+                "unit".to_string(),
+                serde_json::Value::String(sensor_type.unit().to_string()),
+            );
+            metadata.insert(
+                "reading".to_string(),
+                serde_json::Value::Number(serde_json::Number::from_f64(reading).unwrap()),
+            );
+            metadata.insert(
+                "source".to_string(),
+                serde_json::Value::String(
+                    if from_hardware { "hardware" } else { "synthetic" }.to_string(),
+                ),
+            );
+            entry = entry.with_metadata(metadata);
+        }
+
+        entry
+    }
+
+    /// Build the sensor registry used by [`LogGenerator::generate_templated`]:
+    /// the eight built-ins plus any custom templates from `config`.
+    fn sensor_registry(&self) -> crate::sensor_template::SensorRegistry {
+        let mut registry = crate::sensor_template::SensorRegistry::with_defaults();
+        for template in &self.config.custom_sensors {
+            registry.register(template.clone());
+        }
+        registry
+    }
+
     /// Generate sensor-specific message and metadata.
     fn generate_sensor_data(
         &self,
@@ -735,6 +970,7 @@ mod tests {
         assert_eq!(config.base_interval_ms, 100);
         assert!(config.include_metadata);
         assert!((config.error_rate - 0.05).abs() < f64::EPSILON);
+        assert_eq!(config.level_weights, [5, 15, 60, 12, 7, 1]);
     }
 
     #[test]
@@ -756,6 +992,92 @@ mod tests {
         assert_eq!(batch.len(), 50);
     }
 
+    #[test]
+    fn test_generate_batch_parallel_size() {
+        let generator = LogGenerator::with_defaults();
+        let batch = generator.generate_batch_parallel(200);
+        assert_eq!(batch.len(), 200);
+    }
+
+    #[test]
+    fn test_generate_batch_parallel_keeps_source_id_format() {
+        let generator = LogGenerator::with_defaults();
+        let batch = generator.generate_batch_parallel(50);
+
+        for entry in &batch {
+            assert!(entry.source_id.starts_with("edge-"));
+            let last_part = entry.source_id.rsplit('-').next().unwrap();
+            assert_eq!(last_part.len(), 3);
+            assert!(last_part.chars().all(|c| c.is_ascii_digit()));
+        }
+    }
+
+    #[test]
+    fn test_generate_batch_parallel_sequences_unique_per_sensor() {
+        let generator = LogGenerator::with_defaults();
+        let batch = generator.generate_batch_parallel(2000);
+
+        let mut seen_per_sensor: HashMap<String, std::collections::HashSet<i64>> = HashMap::new();
+        for entry in &batch {
+            let metadata = entry.metadata.as_ref().expect("include_metadata defaults to true");
+            let sequence = metadata.get("sequence").unwrap().as_i64().unwrap();
+            let seen = seen_per_sensor.entry(entry.source_id.clone()).or_default();
+            assert!(
+                seen.insert(sequence),
+                "duplicate sequence {} for sensor {}",
+                sequence,
+                entry.source_id
+            );
+        }
+    }
+
+    #[test]
+    fn test_generate_templated_uses_builtin_registry() {
+        let generator = LogGenerator::with_defaults();
+        let entry = generator
+            .generate_templated()
+            .expect("built-in registry is never empty");
+
+        assert!(entry.source_id.starts_with("edge-"));
+        let metadata = entry.metadata.expect("include_metadata defaults to true");
+        assert!(metadata.contains_key("sensor_type"));
+        assert!(metadata.contains_key("reading"));
+    }
+
+    #[test]
+    fn test_generate_templated_samples_custom_sensor() {
+        struct Co2Template;
+        impl crate::sensor_template::SensorTemplate for Co2Template {
+            fn name(&self) -> &str {
+                "co2"
+            }
+            fn unit(&self) -> &str {
+                "ppm"
+            }
+            fn generate(
+                &self,
+                _rng: &mut dyn rand::RngCore,
+                _level: LogLevel,
+            ) -> (f64, HashMap<String, serde_json::Value>, String) {
+                (420.0, HashMap::new(), "CO2 reading: 420 ppm".to_string())
+            }
+        }
+
+        // Register one custom sensor on top of the built-ins.
+        let mut config = GeneratorConfig {
+            custom_sensors: Vec::new(),
+            ..GeneratorConfig::default()
+        };
+        config.custom_sensors.push(std::sync::Arc::new(Co2Template));
+        let generator = LogGenerator::new(config);
+
+        // Built-ins are still in the registry, so assert indirectly via the
+        // registry helper rather than a specific generated entry.
+        let registry = generator.sensor_registry();
+        assert_eq!(registry.len(), 9);
+        assert!(registry.get("co2").is_some());
+    }
+
     #[test]
     fn test_generator_source_id_format() {
         let generator = LogGenerator::with_defaults();
@@ -803,6 +1125,39 @@ mod tests {
         assert!(metadata.contains_key("sequence"));
     }
 
+    #[test]
+    fn test_generate_from_source_default_is_synthetic() {
+        let generator = LogGenerator::with_defaults();
+        let entry = generator.generate_from_source();
+
+        let metadata = entry.metadata.expect("include_metadata defaults to true");
+        assert_eq!(
+            metadata.get("source").unwrap().as_str().unwrap(),
+            "synthetic"
+        );
+        assert!(metadata.contains_key("reading"));
+    }
+
+    #[test]
+    fn test_generate_from_source_uses_hardware_when_available() {
+        struct AlwaysFortyTwo;
+        impl crate::sensor_source::SensorSource for AlwaysFortyTwo {
+            fn read(&self, _sensor_type: SensorType) -> Option<f64> {
+                Some(42.0)
+            }
+        }
+
+        let generator = LogGenerator::with_source(GeneratorConfig::default(), Box::new(AlwaysFortyTwo));
+        let entry = generator.generate_from_source();
+
+        let metadata = entry.metadata.expect("include_metadata defaults to true");
+        assert_eq!(
+            metadata.get("source").unwrap().as_str().unwrap(),
+            "hardware"
+        );
+        assert_eq!(metadata.get("reading").unwrap().as_f64().unwrap(), 42.0);
+    }
+
     #[test]
     fn test_log_level_display() {
         assert_eq!(format!("{}", LogLevel::Info), "info");
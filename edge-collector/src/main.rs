@@ -8,31 +8,62 @@
 //! - Async log generation using tokio runtime
 //! - Size-based and time-based buffer flushing
 //! - HTTP batch transmission with retry logic
+//! - Disk-backed spill-and-replay so an extended backend outage doesn't lose logs
+//! - Pluggable transport (HTTP or Kafka) behind the same batching and retry path
 //! - Graceful shutdown on SIGINT/SIGTERM
 //!
 //! ## Configuration
 //!
-//! Configuration is loaded from environment variables:
+//! Configuration is loaded from environment variables, then CLI flags are
+//! layered on top (CLI wins):
 //!
-//! - `EDGE_COLLECTOR_API_URL`: Python API URL (default: http://localhost:8000)
-//! - `EDGE_COLLECTOR_BATCH_SIZE`: Logs per batch (default: 100)
-//! - `EDGE_COLLECTOR_FLUSH_INTERVAL_SECS`: Seconds between flushes (default: 5)
-//! - `EDGE_COLLECTOR_REQUEST_TIMEOUT_SECS`: HTTP request timeout (default: 30)
-//! - `EDGE_COLLECTOR_MAX_RETRIES`: Max retry attempts (default: 3)
+//! - `EDGE_COLLECTOR_API_URL` / `--api-url`: Python API URL (default: http://localhost:8000)
+//! - `EDGE_COLLECTOR_BATCH_SIZE` / `--batch-size`: Logs per batch (default: 100)
+//! - `EDGE_COLLECTOR_FLUSH_INTERVAL_SECS` / `--flush-interval-secs`: Seconds between flushes (default: 5)
+//! - `EDGE_COLLECTOR_REQUEST_TIMEOUT_SECS` / `--request-timeout-secs`: HTTP request timeout (default: 30)
+//! - `EDGE_COLLECTOR_MAX_RETRIES` / `--max-retries`: Max retry attempts (default: 3)
 //! - `RUST_LOG`: Logging level filter (default: info)
+//! - `EDGE_COLLECTOR_LOG_LEVEL_FILE`: Path to a file polled for a replacement
+//!   `RUST_LOG`-style filter, applied without restarting the service (optional)
+//! - `EDGE_COLLECTOR_SPILL_DIR`: Directory for undelivered batches spilled to
+//!   disk (default: ./spill)
+//! - `EDGE_COLLECTOR_SPILL_MAX_BYTES`: Cap on total spill file size in bytes
+//!   (default: 100 MiB)
+//! - `EDGE_COLLECTOR_TRANSPORT`: `http` (default) or `kafka`; see
+//!   [`edge_collector::transport`] for the Kafka broker/topic/SASL settings
+//!
+//! See [`edge_collector::config::Config::from_env_and_args`] for the full
+//! set of flags, including circuit breaker tuning.
+//!
+//! ## Benchmark mode
+//!
+//! Passing `--benchmark` (or setting `EDGE_COLLECTOR_BENCHMARK=1`) skips log
+//! generation entirely and instead sweeps a list of batch sizes against the
+//! configured backend, reporting throughput and send latency percentiles —
+//! see [`edge_collector::benchmark`]. Useful for picking
+//! `EDGE_COLLECTOR_BATCH_SIZE`/`EDGE_COLLECTOR_FLUSH_INTERVAL_SECS` for a
+//! given device and network without an external load-testing harness.
 
 use std::sync::Arc;
 use std::time::Duration;
 
-use tokio::sync::mpsc;
+use tokio::signal::unix::{signal, SignalKind};
+use tokio::sync::{mpsc, watch};
 use tokio::time::interval;
+use tokio_util::sync::CancellationToken;
 use tracing::{error, info, warn};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::reload;
+use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::EnvFilter;
 
-use edge_collector::buffer::buffer_task;
-use edge_collector::client::{send_batch, LogClient};
+use edge_collector::benchmark::{format_report, run_benchmark, BenchmarkConfig};
+use edge_collector::buffer::buffer_task_with_shutdown;
+use edge_collector::client::LogClient;
 use edge_collector::config::Config;
 use edge_collector::log_generator::LogGenerator;
+use edge_collector::persistence::{replay_spilled_batches, run_replay_loop, DurableSink, SpillConfig, DEFAULT_REPLAY_INTERVAL};
+use edge_collector::transport::build_sink;
 
 /// Default log generation interval in milliseconds
 const DEFAULT_GENERATION_INTERVAL_MS: u64 = 50;
@@ -40,15 +71,26 @@ const DEFAULT_GENERATION_INTERVAL_MS: u64 = 50;
 /// Channel capacity for the log buffer
 const CHANNEL_CAPACITY: usize = 1000;
 
+/// How often the log level file is polled for changes
+const LOG_LEVEL_WATCH_INTERVAL: Duration = Duration::from_secs(30);
+
 #[tokio::main]
 async fn main() {
-    // Initialize tracing subscriber with environment filter
-    init_tracing();
+    // Initialize tracing subscriber with environment filter, and keep the
+    // reload handle around so the filter can be changed at runtime
+    let reload_handle = init_tracing();
+    tokio::spawn(watch_log_level_file(reload_handle));
+
+    let cli_args: Vec<String> = std::env::args().skip(1).collect();
+    if cli_args.iter().any(|a| a == "--benchmark") || std::env::var("EDGE_COLLECTOR_BENCHMARK").is_ok() {
+        run_benchmark_mode().await;
+        return;
+    }
 
     info!("Starting Edge Collector service...");
 
-    // Load configuration from environment
-    let config = match Config::from_env() {
+    // Load configuration from environment, then layer CLI flags on top
+    let config = match Config::from_env_and_args(std::env::args().skip(1)) {
         Ok(config) => {
             info!(
                 api_url = %config.api_url,
@@ -87,22 +129,64 @@ async fn main() {
     let generator = LogGenerator::with_defaults();
     info!("Log generator initialized");
 
-    // Clone client for buffer task
-    let client_clone = client.clone();
+    // Shutdown channel: flipped to `true` once, watched by the generator so
+    // it can stop cleanly and drop `tx`; the buffer task watches the
+    // equivalent cancellation token so it can flush before exiting.
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let cancel_token = CancellationToken::new();
+    let bridge_token = cancel_token.clone();
+    let mut bridge_rx = shutdown_rx.clone();
+    tokio::spawn(async move {
+        if bridge_rx.wait_for(|shutting_down| *shutting_down).await.is_ok() {
+            bridge_token.cancel();
+        }
+    });
+
+    // Transport: HTTP by default, or Kafka if EDGE_COLLECTOR_TRANSPORT=kafka
+    // (and the collector was built with the `kafka` feature)
+    let sink = build_sink(client.clone());
+
+    // Spill-to-disk configuration for batches that exhaust client retries
+    let spill_config = SpillConfig::from_env();
+
+    // Replay whatever spilled during a prior outage before draining new live
+    // batches; a background loop then keeps retrying in case of a later one.
+    if let Err(e) = replay_spilled_batches(&spill_config, {
+        let sink = sink.clone();
+        move |batch| {
+            let sink = sink.clone();
+            async move {
+                sink.send(batch.logs)
+                    .await
+                    .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+            }
+        }
+    })
+    .await
+    {
+        warn!(error = %e, "Initial spill replay failed; will keep retrying in the background");
+    }
+    tokio::spawn(run_replay_loop(spill_config.clone(), sink.clone(), DEFAULT_REPLAY_INTERVAL));
+
+    // Durable sink: spills a batch to disk instead of dropping it if the
+    // transport exhausts its retries
+    let durable_sink = DurableSink::new(sink, spill_config);
 
     // Spawn buffer task - handles batching and sending logs
     let buffer_handle = tokio::spawn(async move {
         info!("Buffer task started");
-        buffer_task(
+        buffer_task_with_shutdown(
             rx,
             config.batch_size,
             config.flush_interval,
             move |batch| {
-                let client = client_clone.clone();
-                async move {
-                    send_batch(&client, batch).await
-                }
+                let sink = durable_sink.clone();
+                async move { sink.flush(batch).await }
             },
+            cancel_token,
+            edge_collector::buffer::OverflowPolicy::default(),
+            None,
+            config.optimize_for(),
         )
         .await;
         info!("Buffer task completed");
@@ -112,31 +196,40 @@ async fn main() {
     let tx_clone = tx.clone();
     let generator_handle = tokio::spawn(async move {
         info!("Generator task started");
-        run_generator(generator, tx_clone).await;
+        run_generator(generator, tx_clone, shutdown_rx).await;
         info!("Generator task completed");
     });
 
-    // Wait for shutdown signal
+    // Wait for shutdown signal (SIGINT or SIGTERM, the latter for Docker/systemd)
+    let mut sigterm = match signal(SignalKind::terminate()) {
+        Ok(stream) => stream,
+        Err(e) => {
+            error!(error = %e, "Failed to install SIGTERM handler");
+            std::process::exit(1);
+        }
+    };
+
     info!("Edge Collector running. Press Ctrl+C to stop.");
-    match tokio::signal::ctrl_c().await {
-        Ok(()) => {
-            info!("Shutdown signal received, stopping...");
+    tokio::select! {
+        result = tokio::signal::ctrl_c() => {
+            match result {
+                Ok(()) => info!("SIGINT received, stopping..."),
+                Err(e) => error!(error = %e, "Failed to listen for SIGINT"),
+            }
         }
-        Err(e) => {
-            error!(error = %e, "Failed to listen for shutdown signal");
+        _ = sigterm.recv() => {
+            info!("SIGTERM received, stopping...");
         }
     }
 
-    // Graceful shutdown
+    // Graceful shutdown: flip the watch so the generator drops `tx` and the
+    // buffer task flushes its remaining batch, then drop our own sender too
+    // in case the generator task doesn't get scheduled before exiting.
     info!("Initiating graceful shutdown...");
-
-    // Drop the sender to signal the buffer task to flush remaining logs
+    let _ = shutdown_tx.send(true);
     drop(tx);
 
-    // Wait for generator to complete (it will stop when tx is dropped)
-    generator_handle.abort();
-
-    // Wait for buffer to flush remaining logs (with timeout)
+    // Wait for buffer to flush remaining logs (with timeout as a hard backstop)
     let shutdown_timeout = Duration::from_secs(10);
     match tokio::time::timeout(shutdown_timeout, buffer_handle).await {
         Ok(Ok(())) => {
@@ -150,60 +243,174 @@ async fn main() {
         }
     }
 
+    // The generator should have exited promptly once the watch flipped
+    if tokio::time::timeout(Duration::from_secs(1), generator_handle).await.is_err() {
+        warn!("Generator task did not exit promptly after shutdown signal");
+    }
+
     info!("Edge Collector stopped");
 }
 
+/// Run the built-in benchmark mode: sweep batch sizes against the
+/// configured backend and print a throughput/latency table, then exit
+/// without generating or buffering any logs for real.
+///
+/// `--benchmark` (or `EDGE_COLLECTOR_BENCHMARK`) is checked in `main` before
+/// anything else starts up, so this never races the generator or buffer
+/// tasks for the shared HTTP client's connection pool.
+async fn run_benchmark_mode() {
+    let config = match Config::from_env_and_args(std::env::args().skip(1)) {
+        Ok(config) => config,
+        Err(e) => {
+            error!(error = %e, "Failed to load configuration");
+            std::process::exit(1);
+        }
+    };
+
+    let client = match LogClient::new(&config) {
+        Ok(client) => client,
+        Err(e) => {
+            error!(error = %e, "Failed to create HTTP client");
+            std::process::exit(1);
+        }
+    };
+
+    let bench_config = BenchmarkConfig::from_env();
+    info!(
+        batch_sizes = ?bench_config.batch_sizes,
+        warmup_iterations = bench_config.warmup_iterations,
+        iterations = bench_config.iterations,
+        ingest_url = %client.ingest_url(),
+        "Running benchmark"
+    );
+
+    let results = run_benchmark(&client, &bench_config).await;
+    println!("{}", format_report(&results));
+}
+
 /// Initialize the tracing subscriber with environment-based filtering.
-fn init_tracing() {
-    let filter = EnvFilter::try_from_default_env()
-        .unwrap_or_else(|_| EnvFilter::new("info"));
+///
+/// The filter is wrapped in a [`reload::Layer`] so it can be swapped out at
+/// runtime (see [`watch_log_level_file`]) without restarting the collector.
+fn init_tracing() -> reload::Handle<EnvFilter, tracing_subscriber::Registry> {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let (filter_layer, reload_handle) = reload::Layer::new(filter);
 
-    tracing_subscriber::fmt()
-        .with_env_filter(filter)
+    let fmt_layer = tracing_subscriber::fmt::layer()
         .with_target(true)
         .with_thread_ids(false)
         .with_file(false)
         .with_line_number(false)
-        .compact()
+        .compact();
+
+    tracing_subscriber::registry()
+        .with(filter_layer)
+        .with(fmt_layer)
         .init();
+
+    reload_handle
+}
+
+/// Poll `EDGE_COLLECTOR_LOG_LEVEL_FILE` for a replacement log filter.
+///
+/// Every [`LOG_LEVEL_WATCH_INTERVAL`], the file's trimmed contents are
+/// compared against the last-applied filter; on a change, the new filter is
+/// parsed and swapped in via `reload_handle`, letting operators bump a
+/// running edge device from `info` to something like
+/// `debug,edge_collector::client=trace` to diagnose an issue and dial it
+/// back down, all without restarting the service. If the env var is unset,
+/// this task does nothing.
+async fn watch_log_level_file(reload_handle: reload::Handle<EnvFilter, tracing_subscriber::Registry>) {
+    let Ok(path) = std::env::var("EDGE_COLLECTOR_LOG_LEVEL_FILE") else {
+        return;
+    };
+
+    let mut ticker = interval(LOG_LEVEL_WATCH_INTERVAL);
+    let mut last_applied: Option<String> = None;
+
+    loop {
+        ticker.tick().await;
+
+        let contents = match tokio::fs::read_to_string(&path).await {
+            Ok(contents) => contents.trim().to_string(),
+            Err(e) => {
+                warn!(path = %path, error = %e, "Failed to read log level file");
+                continue;
+            }
+        };
+
+        if contents.is_empty() || last_applied.as_deref() == Some(contents.as_str()) {
+            continue;
+        }
+
+        match EnvFilter::try_new(&contents) {
+            Ok(new_filter) => match reload_handle.reload(new_filter) {
+                Ok(()) => {
+                    info!(path = %path, filter = %contents, "Log filter hot-reloaded");
+                    last_applied = Some(contents);
+                }
+                Err(e) => {
+                    warn!(error = %e, "Failed to apply reloaded log filter");
+                }
+            },
+            Err(e) => {
+                warn!(path = %path, filter = %contents, error = %e, "Invalid log filter in file, keeping previous");
+            }
+        }
+    }
 }
 
 /// Run the log generator task, producing logs at regular intervals.
 ///
 /// This task generates simulated sensor logs and sends them to the buffer
-/// channel. It runs until the channel is closed.
-async fn run_generator(generator: LogGenerator, tx: mpsc::Sender<edge_collector::LogEntry>) {
+/// channel. It runs until the channel is closed or `shutdown` flips to
+/// `true`, at which point it drops `tx`. The buffer task flushes its final
+/// batch when its cancellation token fires, not when the channel closes.
+async fn run_generator(
+    generator: LogGenerator,
+    tx: mpsc::Sender<edge_collector::LogEntry>,
+    mut shutdown: watch::Receiver<bool>,
+) {
     let mut ticker = interval(Duration::from_millis(DEFAULT_GENERATION_INTERVAL_MS));
     let mut logs_generated: u64 = 0;
     let mut last_report_time = std::time::Instant::now();
     let report_interval = Duration::from_secs(30);
 
     loop {
-        ticker.tick().await;
+        tokio::select! {
+            _ = ticker.tick() => {
+                // Generate a new log entry
+                let entry = generator.generate();
+
+                // Send to buffer channel
+                match tx.send(entry).await {
+                    Ok(()) => {
+                        logs_generated += 1;
 
-        // Generate a new log entry
-        let entry = generator.generate();
-
-        // Send to buffer channel
-        match tx.send(entry).await {
-            Ok(()) => {
-                logs_generated += 1;
-
-                // Periodic progress report
-                if last_report_time.elapsed() >= report_interval {
-                    info!(
-                        logs_generated = logs_generated,
-                        rate = format!("{:.1}/s", logs_generated as f64 / last_report_time.elapsed().as_secs_f64()),
-                        "Generator progress"
-                    );
-                    logs_generated = 0;
-                    last_report_time = std::time::Instant::now();
+                        // Periodic progress report
+                        if last_report_time.elapsed() >= report_interval {
+                            info!(
+                                logs_generated = logs_generated,
+                                rate = format!("{:.1}/s", logs_generated as f64 / last_report_time.elapsed().as_secs_f64()),
+                                "Generator progress"
+                            );
+                            logs_generated = 0;
+                            last_report_time = std::time::Instant::now();
+                        }
+                    }
+                    Err(_) => {
+                        // Channel closed, stop generating
+                        info!("Channel closed, generator stopping");
+                        break;
+                    }
                 }
             }
-            Err(_) => {
-                // Channel closed, stop generating
-                info!("Channel closed, generator stopping");
-                break;
+
+            result = shutdown.changed() => {
+                if result.is_err() || *shutdown.borrow() {
+                    info!("Shutdown requested, generator stopping");
+                    break;
+                }
             }
         }
     }
@@ -224,4 +431,41 @@ mod tests {
         assert!(CHANNEL_CAPACITY >= 100);
         assert!(CHANNEL_CAPACITY <= 10000);
     }
+
+    #[test]
+    fn test_log_level_watch_interval() {
+        assert!(LOG_LEVEL_WATCH_INTERVAL >= Duration::from_secs(1));
+        assert!(LOG_LEVEL_WATCH_INTERVAL <= Duration::from_secs(300));
+    }
+
+    #[tokio::test]
+    async fn test_watch_log_level_file_noop_without_env_var() {
+        std::env::remove_var("EDGE_COLLECTOR_LOG_LEVEL_FILE");
+        let filter = EnvFilter::new("info");
+        let (_layer, reload_handle) = reload::Layer::new(filter);
+
+        // With the env var unset, the watcher should return immediately
+        // rather than looping forever.
+        tokio::time::timeout(Duration::from_secs(1), watch_log_level_file(reload_handle))
+            .await
+            .expect("watcher should exit promptly when unconfigured");
+    }
+
+    #[tokio::test]
+    async fn test_run_generator_stops_on_shutdown_signal() {
+        let (tx, mut rx) = mpsc::channel(16);
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let generator = LogGenerator::with_defaults();
+
+        let handle = tokio::spawn(run_generator(generator, tx, shutdown_rx));
+
+        // Let it generate at least one log before asking it to stop.
+        rx.recv().await.expect("generator should produce a log");
+        shutdown_tx.send(true).expect("receiver still alive");
+
+        tokio::time::timeout(Duration::from_secs(1), handle)
+            .await
+            .expect("generator should stop promptly after shutdown signal")
+            .expect("generator task should not panic");
+    }
 }
@@ -0,0 +1,155 @@
+//! MessagePack wire format for `LogBatch`, as a more compact alternative to
+//! JSON on metered cellular/LoRa uplinks.
+//!
+//! `LogEntry`, `LogLevel`, and the metadata map already derive `Serialize`/
+//! `Deserialize`, so [`LogBatch::to_msgpack`]/[`LogBatch::from_msgpack`] are
+//! thin wrappers around `rmp-serde`. The MessagePack encoding in this module
+//! — and the `MsgPack` variant of [`WireFormat`] — only compiles with the
+//! `msgpack` feature enabled; without it, [`WireFormat::Json`] is the only
+//! option.
+
+use crate::log_generator::LogBatch;
+
+/// Wire format the transmit side can pick at runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WireFormat {
+    /// Plain JSON, as already produced by `serde_json`.
+    Json,
+    /// Compact MessagePack encoding; requires the `msgpack` feature.
+    #[cfg(feature = "msgpack")]
+    MsgPack,
+}
+
+#[cfg(feature = "msgpack")]
+mod encoding {
+    use super::*;
+
+    /// Error encoding or decoding a [`LogBatch`] as MessagePack.
+    #[derive(Debug)]
+    pub enum MsgPackError {
+        Encode(String),
+        Decode(String),
+    }
+
+    impl std::fmt::Display for MsgPackError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                MsgPackError::Encode(e) => write!(f, "failed to encode MessagePack: {}", e),
+                MsgPackError::Decode(e) => write!(f, "failed to decode MessagePack: {}", e),
+            }
+        }
+    }
+
+    impl std::error::Error for MsgPackError {}
+
+    impl LogBatch {
+        /// Encode this batch as MessagePack bytes.
+        ///
+        /// Structs are written as maps keyed by field name, so optional
+        /// fields omitted when `None` decode back as `None`.
+        pub fn to_msgpack(&self) -> Result<Vec<u8>, MsgPackError> {
+            rmp_serde::to_vec_named(self).map_err(|e| MsgPackError::Encode(e.to_string()))
+        }
+
+        /// Decode a batch previously written by [`LogBatch::to_msgpack`].
+        pub fn from_msgpack(bytes: &[u8]) -> Result<LogBatch, MsgPackError> {
+            rmp_serde::from_slice(bytes).map_err(|e| MsgPackError::Decode(e.to_string()))
+        }
+
+        /// Encode this batch using `format`, dispatching at runtime.
+        ///
+        /// `serde_json`'s encode is infallible for any value this crate
+        /// produces, so the `Json` arm never actually errors; the `Result`
+        /// return type exists so callers can treat both arms uniformly.
+        pub fn encode(&self, format: WireFormat) -> Result<Vec<u8>, MsgPackError> {
+            match format {
+                WireFormat::Json => Ok(serde_json::to_vec(self)
+                    .expect("LogBatch always serializes to JSON")),
+                WireFormat::MsgPack => self.to_msgpack(),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "msgpack")]
+pub use encoding::MsgPackError;
+
+#[cfg(not(feature = "msgpack"))]
+impl LogBatch {
+    /// Encode this batch using `format`, dispatching at runtime.
+    ///
+    /// Without the `msgpack` feature, [`WireFormat`] only has the `Json`
+    /// variant, so this is infallible.
+    pub fn encode(&self, format: WireFormat) -> Vec<u8> {
+        match format {
+            WireFormat::Json => {
+                serde_json::to_vec(self).expect("LogBatch always serializes to JSON")
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "msgpack"))]
+mod tests {
+    use super::*;
+    use crate::log_generator::{LogEntry, LogLevel};
+    use std::collections::HashMap;
+
+    fn sample_batch() -> LogBatch {
+        let mut metadata = HashMap::new();
+        metadata.insert("reading".to_string(), serde_json::json!(42.5));
+        metadata.insert("unit".to_string(), serde_json::json!("celsius"));
+
+        let entries = vec![
+            LogEntry::new("edge-temp-001", LogLevel::Info, "Temperature reading: 21.3C")
+                .with_metadata(metadata),
+            LogEntry::new("edge-temp-002", LogLevel::Warn, "Temperature approaching threshold"),
+        ];
+        LogBatch::new(entries)
+    }
+
+    #[test]
+    fn test_msgpack_roundtrip_equals_original() {
+        let batch = sample_batch();
+        let bytes = batch.to_msgpack().expect("encode should succeed");
+        let decoded = LogBatch::from_msgpack(&bytes).expect("decode should succeed");
+
+        assert_eq!(decoded.len(), batch.len());
+        assert_eq!(decoded.batch_id, batch.batch_id);
+        assert_eq!(decoded.source, batch.source);
+        assert_eq!(decoded.logs[0].source_id, batch.logs[0].source_id);
+        assert_eq!(decoded.logs[0].message, batch.logs[0].message);
+    }
+
+    #[test]
+    fn test_msgpack_is_smaller_than_json() {
+        let batch = sample_batch();
+        let msgpack_bytes = batch.to_msgpack().expect("encode should succeed");
+        let json_bytes = serde_json::to_string(&batch).unwrap().into_bytes();
+
+        assert!(
+            msgpack_bytes.len() < json_bytes.len(),
+            "msgpack ({} bytes) should be smaller than JSON ({} bytes)",
+            msgpack_bytes.len(),
+            json_bytes.len()
+        );
+    }
+
+    #[test]
+    fn test_decode_invalid_bytes_errors() {
+        let result = LogBatch::from_msgpack(&[0xff, 0xff, 0xff]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_encode_dispatches_on_wire_format() {
+        let batch = sample_batch();
+
+        let json_bytes = batch.encode(WireFormat::Json).unwrap();
+        let msgpack_bytes = batch.encode(WireFormat::MsgPack).unwrap();
+
+        assert!(serde_json::from_slice::<LogBatch>(&json_bytes).is_ok());
+        assert!(LogBatch::from_msgpack(&msgpack_bytes).is_ok());
+        assert!(msgpack_bytes.len() < json_bytes.len());
+    }
+}
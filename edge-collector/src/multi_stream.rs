@@ -0,0 +1,413 @@
+//! Keyed multi-stream batching, partitioning incoming logs by source (or any
+//! other key) before flushing.
+//!
+//! [`LogBuffer`](crate::buffer::LogBuffer) treats every entry as part of one
+//! shared batch, so a single chatty source can force a premature flush that
+//! mixes in a handful of entries from an otherwise-quiet one. [`MultiStreamBuffer`]
+//! instead maintains an independent sub-buffer per key, each with its own
+//! size threshold, and a shared ticker that sweeps for sub-buffers whose
+//! oldest entry has aged past `flush_interval`. This lets downstream
+//! consumers ship per-source batches to different endpoints without waiting
+//! on each other.
+
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+
+use tokio::sync::mpsc;
+use tokio::time::interval;
+use tracing::{debug, info};
+
+use crate::buffer::BufferSender;
+use crate::log_generator::{LogBatch, LogEntry, LogLevel};
+
+/// Default number of distinct live keys a [`MultiStreamBuffer`] will track at
+/// once before evicting the least-recently-updated one.
+const DEFAULT_MAX_KEYS: usize = 64;
+
+/// Configuration for [`MultiStreamBuffer`].
+#[derive(Debug, Clone)]
+pub struct MultiStreamConfig {
+    /// Number of logs to accumulate for a single key before flushing just
+    /// that key's sub-buffer.
+    pub batch_size: usize,
+
+    /// Maximum age of a key's oldest buffered entry before the ticker sweep
+    /// flushes it, even if `batch_size` hasn't been reached.
+    pub flush_interval: Duration,
+
+    /// Capacity of the mpsc channel.
+    pub channel_capacity: usize,
+
+    /// Maximum number of distinct live keys to track at once. Once exceeded,
+    /// the least-recently-updated key is flushed and evicted to bound memory.
+    pub max_keys: usize,
+}
+
+impl Default for MultiStreamConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 100,
+            flush_interval: Duration::from_secs(5),
+            channel_capacity: 1_000,
+            max_keys: DEFAULT_MAX_KEYS,
+        }
+    }
+}
+
+impl MultiStreamConfig {
+    /// Create a new config with the specified batch size and flush interval.
+    pub fn new(batch_size: usize, flush_interval: Duration) -> Self {
+        Self {
+            batch_size,
+            flush_interval,
+            ..Self::default()
+        }
+    }
+}
+
+/// Statistics about [`MultiStreamBuffer`] operations.
+#[derive(Debug, Clone, Default)]
+pub struct MultiStreamStats {
+    /// Total number of logs received across all keys.
+    pub logs_received: u64,
+
+    /// Number of flushes triggered by a key reaching `batch_size`.
+    pub size_flushes: u64,
+
+    /// Number of flushes triggered by the ticker sweep finding an aged key.
+    pub time_flushes: u64,
+
+    /// Number of keys evicted (and flushed) because `max_keys` was exceeded.
+    pub key_evictions: u64,
+}
+
+/// Per-key accumulator: the buffered entries, when the oldest one arrived,
+/// and when this key was last touched (for LRU eviction).
+struct StreamState {
+    entries: Vec<LogEntry>,
+    oldest: Instant,
+    last_updated: Instant,
+}
+
+impl StreamState {
+    fn new() -> Self {
+        let now = Instant::now();
+        Self {
+            entries: Vec::new(),
+            oldest: now,
+            last_updated: now,
+        }
+    }
+
+    fn push(&mut self, entry: LogEntry) {
+        if self.entries.is_empty() {
+            self.oldest = Instant::now();
+        }
+        self.entries.push(entry);
+        self.last_updated = Instant::now();
+    }
+}
+
+/// A log buffer that partitions entries by key, flushing each key's
+/// sub-buffer independently.
+///
+/// # Example
+///
+/// ```no_run
+/// use edge_collector::multi_stream::{MultiStreamBuffer, MultiStreamConfig};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let (sender, mut buffer) = MultiStreamBuffer::by_source(MultiStreamConfig::default());
+///
+///     loop {
+///         if let Some((source, batch)) = buffer.next_batch().await {
+///             println!("Flushing {} logs for {}", batch.len(), source);
+///         } else {
+///             break;
+///         }
+///     }
+/// }
+/// ```
+pub struct MultiStreamBuffer<K, F> {
+    rx: mpsc::Receiver<LogEntry>,
+    key_fn: F,
+    streams: HashMap<K, StreamState>,
+    pending: VecDeque<(K, LogBatch)>,
+    config: MultiStreamConfig,
+    stats: MultiStreamStats,
+}
+
+impl<K, F> MultiStreamBuffer<K, F>
+where
+    K: Clone + Eq + Hash,
+    F: Fn(&LogEntry) -> K,
+{
+    /// Create a new multi-stream buffer, partitioning entries with `key_fn`.
+    ///
+    /// Returns a tuple of (BufferSender, MultiStreamBuffer); the sender is
+    /// the same [`BufferSender`] used by [`crate::buffer::LogBuffer`], since
+    /// both consume a plain `LogEntry` channel.
+    pub fn new(config: MultiStreamConfig, key_fn: F) -> (BufferSender, Self) {
+        let (tx, rx) = mpsc::channel(config.channel_capacity);
+
+        let buffer = Self {
+            rx,
+            key_fn,
+            streams: HashMap::new(),
+            pending: VecDeque::new(),
+            config,
+            stats: MultiStreamStats::default(),
+        };
+
+        (BufferSender::from_mpsc_sender(tx), buffer)
+    }
+
+    /// Wait for the next per-key batch to be ready.
+    ///
+    /// Returns `None` once the channel is closed and every key's remaining
+    /// entries have been drained as final batches.
+    pub async fn next_batch(&mut self) -> Option<(K, LogBatch)> {
+        let mut ticker = interval(self.config.flush_interval);
+        // Skip the first immediate tick
+        ticker.tick().await;
+
+        loop {
+            if let Some(item) = self.pending.pop_front() {
+                return Some(item);
+            }
+
+            tokio::select! {
+                maybe_entry = self.rx.recv() => {
+                    match maybe_entry {
+                        Some(entry) => self.ingest(entry),
+                        None => {
+                            self.flush_all_remaining();
+                            return self.pending.pop_front();
+                        }
+                    }
+                }
+
+                _ = ticker.tick() => {
+                    self.sweep_expired();
+                }
+            }
+        }
+    }
+
+    /// Route `entry` into its key's sub-buffer, evicting the
+    /// least-recently-updated key first if `max_keys` would be exceeded, and
+    /// queuing a flush if the key just reached `batch_size`.
+    fn ingest(&mut self, entry: LogEntry) {
+        self.stats.logs_received += 1;
+        let key = (self.key_fn)(&entry);
+
+        if !self.streams.contains_key(&key) && self.streams.len() >= self.config.max_keys {
+            if let Some(evict_key) = self
+                .streams
+                .iter()
+                .min_by_key(|(_, state)| state.last_updated)
+                .map(|(k, _)| k.clone())
+            {
+                if let Some(state) = self.streams.remove(&evict_key) {
+                    if !state.entries.is_empty() {
+                        self.stats.key_evictions += 1;
+                        debug!(max_keys = self.config.max_keys, "Evicting least-recently-updated key");
+                        self.pending.push_back((evict_key, LogBatch::new(state.entries)));
+                    }
+                }
+            }
+        }
+
+        let state = self.streams.entry(key.clone()).or_insert_with(StreamState::new);
+        state.push(entry);
+
+        if state.entries.len() >= self.config.batch_size {
+            if let Some(state) = self.streams.remove(&key) {
+                self.stats.size_flushes += 1;
+                debug!(batch_size = state.entries.len(), "Key reached batch size, flushing");
+                self.pending.push_back((key, LogBatch::new(state.entries)));
+            }
+        }
+    }
+
+    /// Flush every key whose oldest buffered entry has aged past
+    /// `flush_interval`.
+    fn sweep_expired(&mut self) {
+        let now = Instant::now();
+        let expired: Vec<K> = self
+            .streams
+            .iter()
+            .filter(|(_, state)| {
+                !state.entries.is_empty() && now.duration_since(state.oldest) >= self.config.flush_interval
+            })
+            .map(|(k, _)| k.clone())
+            .collect();
+
+        for key in expired {
+            if let Some(state) = self.streams.remove(&key) {
+                self.stats.time_flushes += 1;
+                debug!(batch_size = state.entries.len(), "Key aged past flush_interval, flushing");
+                self.pending.push_back((key, LogBatch::new(state.entries)));
+            }
+        }
+    }
+
+    /// Drain every key's remaining entries into `pending`, for use once the
+    /// channel has closed.
+    fn flush_all_remaining(&mut self) {
+        if self.streams.is_empty() {
+            return;
+        }
+        info!(live_keys = self.streams.len(), "Channel closed, flushing remaining keys");
+        for (key, state) in self.streams.drain() {
+            if !state.entries.is_empty() {
+                self.pending.push_back((key, LogBatch::new(state.entries)));
+            }
+        }
+    }
+
+    /// Get the number of distinct keys currently buffered.
+    pub fn live_keys(&self) -> usize {
+        self.streams.len()
+    }
+
+    /// Get current buffer statistics.
+    pub fn stats(&self) -> &MultiStreamStats {
+        &self.stats
+    }
+}
+
+impl MultiStreamBuffer<String, fn(&LogEntry) -> String> {
+    /// Create a multi-stream buffer keyed by `entry.source_id`.
+    pub fn by_source(config: MultiStreamConfig) -> (BufferSender, Self) {
+        Self::new(config, |entry: &LogEntry| entry.source_id.clone())
+    }
+}
+
+impl MultiStreamBuffer<LogLevel, fn(&LogEntry) -> LogLevel> {
+    /// Create a multi-stream buffer keyed by `entry.level`.
+    pub fn by_level(config: MultiStreamConfig) -> (BufferSender, Self) {
+        Self::new(config, |entry: &LogEntry| entry.level)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log_generator::LogEntry;
+    use tokio::time::timeout;
+
+    fn entry(source: &str, level: LogLevel) -> LogEntry {
+        LogEntry::new(source, level, "test message")
+    }
+
+    #[tokio::test]
+    async fn test_multi_stream_config_default() {
+        let config = MultiStreamConfig::default();
+        assert_eq!(config.batch_size, 100);
+        assert_eq!(config.flush_interval, Duration::from_secs(5));
+        assert_eq!(config.max_keys, DEFAULT_MAX_KEYS);
+    }
+
+    #[tokio::test]
+    async fn test_size_based_flush_is_per_key() {
+        let config = MultiStreamConfig::new(2, Duration::from_secs(60));
+        let (sender, mut buffer) = MultiStreamBuffer::by_source(config);
+
+        sender.send(entry("device-a", LogLevel::Info)).await.unwrap();
+        sender.send(entry("device-b", LogLevel::Info)).await.unwrap();
+        sender.send(entry("device-a", LogLevel::Info)).await.unwrap();
+
+        let (key, batch) = timeout(Duration::from_millis(100), buffer.next_batch())
+            .await
+            .expect("should complete quickly")
+            .expect("should get a batch");
+
+        assert_eq!(key, "device-a");
+        assert_eq!(batch.len(), 2);
+        assert_eq!(buffer.live_keys(), 1); // device-b is still pending
+    }
+
+    #[tokio::test]
+    async fn test_time_based_flush_sweeps_aged_keys() {
+        let config = MultiStreamConfig::new(100, Duration::from_millis(50));
+        let (sender, mut buffer) = MultiStreamBuffer::by_source(config);
+
+        sender.send(entry("device-a", LogLevel::Info)).await.unwrap();
+
+        let (key, batch) = timeout(Duration::from_millis(300), buffer.next_batch())
+            .await
+            .expect("should complete")
+            .expect("should get a batch");
+
+        assert_eq!(key, "device-a");
+        assert_eq!(batch.len(), 1);
+        assert_eq!(buffer.stats().time_flushes, 1);
+    }
+
+    #[tokio::test]
+    async fn test_channel_close_flushes_all_remaining_keys() {
+        let config = MultiStreamConfig::new(100, Duration::from_secs(60));
+        let (sender, mut buffer) = MultiStreamBuffer::by_source(config);
+
+        sender.send(entry("device-a", LogLevel::Info)).await.unwrap();
+        sender.send(entry("device-b", LogLevel::Info)).await.unwrap();
+        drop(sender);
+
+        let mut batches = Vec::new();
+        while let Some(item) = timeout(Duration::from_millis(100), buffer.next_batch())
+            .await
+            .expect("should complete quickly")
+        {
+            batches.push(item);
+        }
+
+        assert_eq!(batches.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_max_keys_evicts_least_recently_updated() {
+        let config = MultiStreamConfig {
+            batch_size: 100,
+            flush_interval: Duration::from_secs(60),
+            max_keys: 2,
+            ..MultiStreamConfig::default()
+        };
+        let (sender, mut buffer) = MultiStreamBuffer::by_source(config);
+
+        sender.send(entry("device-a", LogLevel::Info)).await.unwrap();
+        sender.send(entry("device-b", LogLevel::Info)).await.unwrap();
+        // A third key exceeds max_keys=2, evicting the least-recently-updated (device-a).
+        sender.send(entry("device-c", LogLevel::Info)).await.unwrap();
+
+        let (key, batch) = timeout(Duration::from_millis(100), buffer.next_batch())
+            .await
+            .expect("should complete quickly")
+            .expect("should get a batch");
+
+        assert_eq!(key, "device-a");
+        assert_eq!(batch.len(), 1);
+        assert_eq!(buffer.stats().key_evictions, 1);
+    }
+
+    #[tokio::test]
+    async fn test_by_level_keys_on_severity() {
+        let config = MultiStreamConfig::new(2, Duration::from_secs(60));
+        let (sender, mut buffer) = MultiStreamBuffer::by_level(config);
+
+        sender.send(entry("device-a", LogLevel::Error)).await.unwrap();
+        sender.send(entry("device-b", LogLevel::Info)).await.unwrap();
+        sender.send(entry("device-c", LogLevel::Error)).await.unwrap();
+
+        let (key, batch) = timeout(Duration::from_millis(100), buffer.next_batch())
+            .await
+            .expect("should complete quickly")
+            .expect("should get a batch");
+
+        assert_eq!(key, LogLevel::Error);
+        assert_eq!(batch.len(), 2);
+    }
+}
@@ -0,0 +1,472 @@
+//! OpenTelemetry (OTLP) Logs export for `LogBatch`/`LogEntry`.
+//!
+//! Alongside the existing JSON wire format (and the [`crate::binary`] compact
+//! datagram format), collectors further downstream increasingly speak OTLP.
+//! This module builds the OTLP logs data model —
+//! `ExportLogsServiceRequest -> ResourceLogs -> ScopeLogs -> LogRecord` — from
+//! a [`LogBatch`], so it can be POSTed to any OTel collector either as
+//! OTLP/JSON (`serde_json::to_vec(&batch.to_otlp())`, matching the protobuf
+//! JSON mapping: 64-bit integer fields are strings) or as OTLP/protobuf via
+//! [`ExportLogsServiceRequest::encode_proto`]. The protobuf encoder is
+//! hand-rolled against the `opentelemetry-proto` `logs/v1` and `common/v1`
+//! message/field numbers rather than pulling in `prost`, the same tradeoff
+//! [`crate::binary`] makes for its own wire format.
+//!
+//! Field mapping from [`LogEntry`]: `timestamp` -> `time_unix_nano`
+//! (nanoseconds since epoch), `message` -> `body` (string `AnyValue`),
+//! `source_id` -> the `service.instance.id` attribute, and every `metadata`
+//! entry -> a `LogRecord` attribute. `LogLevel` maps to the OTLP 1-24
+//! `severity_number` scale (trace=1, debug=5, info=9, warn=13, error=17,
+//! fatal=21) with `severity_text` set to the lowercase name already used by
+//! `LogLevel`'s `Display` impl.
+
+use serde::{Serialize, Serializer};
+
+use crate::log_generator::{LogBatch, LogEntry, LogLevel};
+
+/// Instrumentation scope name reported on every `ScopeLogs`.
+const SCOPE_NAME: &str = "edge-collector-rust";
+
+fn serialize_u64_as_string<S>(value: &u64, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&value.to_string())
+}
+
+/// OTLP `AnyValue`: a tagged union of the scalar value kinds this crate emits.
+///
+/// `ArrayValue`/`KvlistValue`/`BytesValue` are not produced here — metadata
+/// is always a [`serde_json::Value`] scalar, string, bool, or number (arrays
+/// and objects fall back to their JSON text, the same degrade-gracefully
+/// choice [`crate::binary::write_metadata_value`] makes).
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnyValue {
+    StringValue(String),
+    BoolValue(bool),
+    IntValue(i64),
+    DoubleValue(f64),
+}
+
+impl Serialize for AnyValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(1))?;
+        match self {
+            AnyValue::StringValue(s) => map.serialize_entry("stringValue", s)?,
+            AnyValue::BoolValue(b) => map.serialize_entry("boolValue", b)?,
+            // int64 fields are strings in protobuf-JSON to avoid precision loss.
+            AnyValue::IntValue(i) => map.serialize_entry("intValue", &i.to_string())?,
+            AnyValue::DoubleValue(d) => map.serialize_entry("doubleValue", d)?,
+        }
+        map.end()
+    }
+}
+
+impl AnyValue {
+    fn encode_proto(&self, buf: &mut Vec<u8>) {
+        match self {
+            AnyValue::StringValue(s) => write_string_field(buf, 1, s),
+            AnyValue::BoolValue(b) => write_varint_field(buf, 2, *b as u64),
+            AnyValue::IntValue(i) => write_varint_field(buf, 3, *i as u64),
+            AnyValue::DoubleValue(d) => write_fixed64_field(buf, 4, d.to_bits()),
+        }
+    }
+}
+
+/// Converts a metadata [`serde_json::Value`] into an OTLP [`AnyValue`].
+fn json_value_to_any_value(value: &serde_json::Value) -> AnyValue {
+    match value {
+        serde_json::Value::String(s) => AnyValue::StringValue(s.clone()),
+        serde_json::Value::Bool(b) => AnyValue::BoolValue(*b),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => AnyValue::IntValue(i),
+            None => AnyValue::DoubleValue(n.as_f64().unwrap_or(0.0)),
+        },
+        other => AnyValue::StringValue(other.to_string()),
+    }
+}
+
+/// OTLP `KeyValue`: one attribute entry.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyValue {
+    pub key: String,
+    pub value: AnyValue,
+}
+
+impl KeyValue {
+    fn new(key: impl Into<String>, value: AnyValue) -> Self {
+        Self {
+            key: key.into(),
+            value,
+        }
+    }
+
+    fn encode_proto(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        write_string_field(&mut buf, 1, &self.key);
+        let mut value_buf = Vec::new();
+        self.value.encode_proto(&mut value_buf);
+        write_message_field(&mut buf, 2, &value_buf);
+        buf
+    }
+}
+
+/// OTLP `Resource`: the entity that produced the logs (here, the edge batch).
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Resource {
+    pub attributes: Vec<KeyValue>,
+}
+
+impl Resource {
+    fn encode_proto(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        for attribute in &self.attributes {
+            write_message_field(&mut buf, 1, &attribute.encode_proto());
+        }
+        buf
+    }
+}
+
+/// OTLP `InstrumentationScope`.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct InstrumentationScope {
+    pub name: String,
+}
+
+impl InstrumentationScope {
+    fn encode_proto(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        write_string_field(&mut buf, 1, &self.name);
+        buf
+    }
+}
+
+/// OTLP `LogRecord`: one [`LogEntry`].
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LogRecord {
+    #[serde(serialize_with = "serialize_u64_as_string")]
+    pub time_unix_nano: u64,
+    pub severity_number: u32,
+    pub severity_text: String,
+    pub body: AnyValue,
+    pub attributes: Vec<KeyValue>,
+}
+
+impl LogRecord {
+    fn encode_proto(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        write_fixed64_field(&mut buf, 1, self.time_unix_nano);
+        write_varint_field(&mut buf, 2, self.severity_number as u64);
+        write_string_field(&mut buf, 3, &self.severity_text);
+        let mut body_buf = Vec::new();
+        self.body.encode_proto(&mut body_buf);
+        write_message_field(&mut buf, 5, &body_buf);
+        for attribute in &self.attributes {
+            write_message_field(&mut buf, 6, &attribute.encode_proto());
+        }
+        buf
+    }
+}
+
+/// OTLP `ScopeLogs`: one instrumentation scope's [`LogRecord`]s.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScopeLogs {
+    pub scope: InstrumentationScope,
+    pub log_records: Vec<LogRecord>,
+}
+
+impl ScopeLogs {
+    fn encode_proto(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        write_message_field(&mut buf, 1, &self.scope.encode_proto());
+        for record in &self.log_records {
+            write_message_field(&mut buf, 2, &record.encode_proto());
+        }
+        buf
+    }
+}
+
+/// OTLP `ResourceLogs`: one [`LogBatch`].
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceLogs {
+    pub resource: Resource,
+    pub scope_logs: Vec<ScopeLogs>,
+}
+
+impl ResourceLogs {
+    fn encode_proto(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        write_message_field(&mut buf, 1, &self.resource.encode_proto());
+        for scope_logs in &self.scope_logs {
+            write_message_field(&mut buf, 2, &scope_logs.encode_proto());
+        }
+        buf
+    }
+}
+
+/// Top-level OTLP logs payload, matching the `ExportLogsServiceRequest`
+/// message accepted by the OTLP/HTTP logs endpoint of any OTel collector.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportLogsServiceRequest {
+    pub resource_logs: Vec<ResourceLogs>,
+}
+
+impl ExportLogsServiceRequest {
+    /// Encode this payload as OTLP/protobuf bytes, ready to POST to an OTel
+    /// collector's `/v1/logs` endpoint with `content-type: application/x-protobuf`.
+    pub fn encode_proto(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        for resource_logs in &self.resource_logs {
+            write_message_field(&mut buf, 1, &resource_logs.encode_proto());
+        }
+        buf
+    }
+}
+
+/// Map a [`LogLevel`] to its OTLP 1-24 `severity_number`.
+fn severity_number(level: LogLevel) -> u32 {
+    match level {
+        LogLevel::Trace => 1,
+        LogLevel::Debug => 5,
+        LogLevel::Info => 9,
+        LogLevel::Warn => 13,
+        LogLevel::Error => 17,
+        LogLevel::Fatal => 21,
+    }
+}
+
+impl LogEntry {
+    /// Convert this entry into an OTLP [`LogRecord`].
+    pub fn to_otlp_log_record(&self) -> LogRecord {
+        let mut attributes = vec![KeyValue::new(
+            "service.instance.id",
+            AnyValue::StringValue(self.source_id.clone()),
+        )];
+
+        if let Some(metadata) = &self.metadata {
+            let mut keys: Vec<&String> = metadata.keys().collect();
+            keys.sort();
+            for key in keys {
+                attributes.push(KeyValue::new(
+                    key.clone(),
+                    json_value_to_any_value(&metadata[key]),
+                ));
+            }
+        }
+
+        LogRecord {
+            time_unix_nano: self.timestamp.timestamp_nanos_opt().unwrap_or(0).max(0) as u64,
+            severity_number: severity_number(self.level),
+            severity_text: self.level.to_string(),
+            body: AnyValue::StringValue(self.message.clone()),
+            attributes,
+        }
+    }
+}
+
+impl LogBatch {
+    /// Convert this batch into an OTLP [`ExportLogsServiceRequest`]: one
+    /// `ResourceLogs` whose `Resource.attributes` carry `service.name` and
+    /// (if set) `batch_id`, one `ScopeLogs`, and one `LogRecord` per entry.
+    pub fn to_otlp(&self) -> ExportLogsServiceRequest {
+        let mut resource_attributes = vec![KeyValue::new(
+            "service.name",
+            AnyValue::StringValue(
+                self.source
+                    .clone()
+                    .unwrap_or_else(|| SCOPE_NAME.to_string()),
+            ),
+        )];
+
+        if let Some(batch_id) = &self.batch_id {
+            resource_attributes.push(KeyValue::new(
+                "batch_id",
+                AnyValue::StringValue(batch_id.to_string()),
+            ));
+        }
+
+        let log_records = self.logs.iter().map(LogEntry::to_otlp_log_record).collect();
+
+        ExportLogsServiceRequest {
+            resource_logs: vec![ResourceLogs {
+                resource: Resource {
+                    attributes: resource_attributes,
+                },
+                scope_logs: vec![ScopeLogs {
+                    scope: InstrumentationScope {
+                        name: SCOPE_NAME.to_string(),
+                    },
+                    log_records,
+                }],
+            }],
+        }
+    }
+}
+
+// --- Minimal protobuf wire-format writer ---
+//
+// Only what the OTLP logs/common/resource messages above need: varint,
+// fixed64, and length-delimited (string/bytes/embedded message) fields.
+// See https://protobuf.dev/programming-guides/encoding/ for the wire format.
+
+const WIRE_VARINT: u8 = 0;
+const WIRE_FIXED64: u8 = 1;
+const WIRE_LEN: u8 = 2;
+
+fn write_tag(buf: &mut Vec<u8>, field_number: u32, wire_type: u8) {
+    write_varint(buf, ((field_number as u64) << 3) | wire_type as u64);
+}
+
+fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            buf.push(byte);
+            break;
+        }
+        buf.push(byte | 0x80);
+    }
+}
+
+fn write_varint_field(buf: &mut Vec<u8>, field_number: u32, value: u64) {
+    write_tag(buf, field_number, WIRE_VARINT);
+    write_varint(buf, value);
+}
+
+fn write_fixed64_field(buf: &mut Vec<u8>, field_number: u32, bits: u64) {
+    write_tag(buf, field_number, WIRE_FIXED64);
+    buf.extend_from_slice(&bits.to_le_bytes());
+}
+
+fn write_len_delimited_field(buf: &mut Vec<u8>, field_number: u32, bytes: &[u8]) {
+    write_tag(buf, field_number, WIRE_LEN);
+    write_varint(buf, bytes.len() as u64);
+    buf.extend_from_slice(bytes);
+}
+
+fn write_string_field(buf: &mut Vec<u8>, field_number: u32, value: &str) {
+    write_len_delimited_field(buf, field_number, value.as_bytes());
+}
+
+fn write_message_field(buf: &mut Vec<u8>, field_number: u32, encoded: &[u8]) {
+    write_len_delimited_field(buf, field_number, encoded);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_severity_number_mapping() {
+        assert_eq!(severity_number(LogLevel::Trace), 1);
+        assert_eq!(severity_number(LogLevel::Debug), 5);
+        assert_eq!(severity_number(LogLevel::Info), 9);
+        assert_eq!(severity_number(LogLevel::Warn), 13);
+        assert_eq!(severity_number(LogLevel::Error), 17);
+        assert_eq!(severity_number(LogLevel::Fatal), 21);
+    }
+
+    #[test]
+    fn test_log_record_maps_basic_fields() {
+        let entry = LogEntry::new("edge-temp-001", LogLevel::Warn, "hot");
+        let record = entry.to_otlp_log_record();
+
+        assert_eq!(record.severity_number, 13);
+        assert_eq!(record.severity_text, "warn");
+        assert_eq!(record.body, AnyValue::StringValue("hot".to_string()));
+        assert!(record
+            .attributes
+            .iter()
+            .any(|kv| kv.key == "service.instance.id"
+                && kv.value == AnyValue::StringValue("edge-temp-001".to_string())));
+    }
+
+    #[test]
+    fn test_log_record_includes_metadata_attributes() {
+        let mut metadata = HashMap::new();
+        metadata.insert("reading".to_string(), serde_json::json!(42.5));
+        metadata.insert("ok".to_string(), serde_json::json!(true));
+
+        let entry = LogEntry::new("edge-temp-001", LogLevel::Info, "reading")
+            .with_metadata(metadata);
+        let record = entry.to_otlp_log_record();
+
+        assert!(record
+            .attributes
+            .iter()
+            .any(|kv| kv.key == "reading" && kv.value == AnyValue::DoubleValue(42.5)));
+        assert!(record
+            .attributes
+            .iter()
+            .any(|kv| kv.key == "ok" && kv.value == AnyValue::BoolValue(true)));
+    }
+
+    #[test]
+    fn test_to_otlp_carries_resource_attributes() {
+        let batch = LogBatch::new(vec![LogEntry::new("edge-1", LogLevel::Info, "hi")]);
+        let request = batch.to_otlp();
+
+        assert_eq!(request.resource_logs.len(), 1);
+        let resource = &request.resource_logs[0].resource;
+        assert!(resource
+            .attributes
+            .iter()
+            .any(|kv| kv.key == "service.name"));
+        assert!(resource.attributes.iter().any(|kv| kv.key == "batch_id"));
+    }
+
+    #[test]
+    fn test_to_otlp_one_scope_logs_one_log_record_per_entry() {
+        let batch = LogBatch::new(vec![
+            LogEntry::new("edge-1", LogLevel::Info, "a"),
+            LogEntry::new("edge-2", LogLevel::Error, "b"),
+        ]);
+        let request = batch.to_otlp();
+
+        let scope_logs = &request.resource_logs[0].scope_logs;
+        assert_eq!(scope_logs.len(), 1);
+        assert_eq!(scope_logs[0].log_records.len(), 2);
+    }
+
+    #[test]
+    fn test_json_representation_uses_camel_case_and_string_nanos() {
+        let batch = LogBatch::new(vec![LogEntry::new("edge-1", LogLevel::Info, "hi")]);
+        let json = serde_json::to_string(&batch.to_otlp()).unwrap();
+
+        assert!(json.contains("\"resourceLogs\""));
+        assert!(json.contains("\"scopeLogs\""));
+        assert!(json.contains("\"logRecords\""));
+        assert!(json.contains("\"timeUnixNano\":\""));
+    }
+
+    #[test]
+    fn test_encode_proto_nonempty_and_starts_with_resource_logs_tag() {
+        let batch = LogBatch::new(vec![LogEntry::new("edge-1", LogLevel::Info, "hi")]);
+        let bytes = batch.to_otlp().encode_proto();
+
+        assert!(!bytes.is_empty());
+        // field 1, length-delimited => tag byte (1 << 3) | 2 = 0x0A
+        assert_eq!(bytes[0], 0x0A);
+    }
+
+    #[test]
+    fn test_varint_encodes_multibyte_value() {
+        let mut buf = Vec::new();
+        write_varint(&mut buf, 300);
+        // 300 = 0b1_0010_1100 -> low7=0101100|0x80, high=10
+        assert_eq!(buf, vec![0xAC, 0x02]);
+    }
+}
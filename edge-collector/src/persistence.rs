@@ -0,0 +1,440 @@
+//! Disk-backed durability for batches that exhaust client retries.
+//!
+//! Today, when [`crate::client::send_batch`] gives up after `max_retries`,
+//! the batch is simply dropped. [`DurableSink`] wraps that call so that a
+//! failed batch is instead appended as a newline-delimited JSON record to a
+//! rolling write-ahead log under `EDGE_COLLECTOR_SPILL_DIR` (daily rotation,
+//! capped total size via `EDGE_COLLECTOR_SPILL_MAX_BYTES`), and
+//! [`replay_spilled_batches`] drains the oldest spill files back through
+//! `send_batch` once the backend is reachable again. This makes the
+//! collector resilient to extended cloud-backend outages, which is the
+//! normal case for intermittently-connected edge hardware.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use tracing::{debug, info, warn};
+
+use crate::log_generator::LogBatch;
+use crate::transport::LogSink;
+
+/// Default directory spill files are written under if
+/// `EDGE_COLLECTOR_SPILL_DIR` is unset.
+pub const DEFAULT_SPILL_DIR: &str = "./spill";
+
+/// Default cap, in bytes, on the total size of retained spill files.
+pub const DEFAULT_SPILL_MAX_BYTES: u64 = 100 * 1024 * 1024;
+
+/// Default interval between background replay attempts.
+pub const DEFAULT_REPLAY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Errors spilling a batch to disk or replaying one back.
+#[derive(Debug)]
+pub enum PersistenceError {
+    /// An I/O error occurred creating, reading, or writing a spill file.
+    Io(io::Error),
+    /// A batch failed to serialize or a spilled record failed to parse.
+    Serialize(serde_json::Error),
+}
+
+impl std::fmt::Display for PersistenceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PersistenceError::Io(e) => write!(f, "spill I/O error: {}", e),
+            PersistenceError::Serialize(e) => write!(f, "spill serialization error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for PersistenceError {}
+
+impl From<io::Error> for PersistenceError {
+    fn from(e: io::Error) -> Self {
+        PersistenceError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for PersistenceError {
+    fn from(e: serde_json::Error) -> Self {
+        PersistenceError::Serialize(e)
+    }
+}
+
+/// Configuration for spilling and replaying batches, normally built from
+/// environment variables via [`SpillConfig::from_env`].
+#[derive(Debug, Clone)]
+pub struct SpillConfig {
+    /// Directory spill files are written under; created if missing.
+    pub spill_dir: PathBuf,
+
+    /// Cap, in bytes, on the total size of all retained spill files. Once
+    /// reached, new batches are dropped (and a warning logged) rather than
+    /// letting a wedged backend fill the disk.
+    pub max_bytes: u64,
+}
+
+impl Default for SpillConfig {
+    fn default() -> Self {
+        Self {
+            spill_dir: PathBuf::from(DEFAULT_SPILL_DIR),
+            max_bytes: DEFAULT_SPILL_MAX_BYTES,
+        }
+    }
+}
+
+impl SpillConfig {
+    /// Read `EDGE_COLLECTOR_SPILL_DIR` / `EDGE_COLLECTOR_SPILL_MAX_BYTES`,
+    /// falling back to defaults for anything unset or unparseable.
+    pub fn from_env() -> Self {
+        let spill_dir = std::env::var("EDGE_COLLECTOR_SPILL_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| PathBuf::from(DEFAULT_SPILL_DIR));
+
+        let max_bytes = std::env::var("EDGE_COLLECTOR_SPILL_MAX_BYTES")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_SPILL_MAX_BYTES);
+
+        Self { spill_dir, max_bytes }
+    }
+}
+
+/// Wraps a batch send so that a batch which exhausts retries is spilled to
+/// disk instead of dropped, matching the `on_flush` shape
+/// [`crate::buffer::buffer_task`] expects.
+///
+/// Replay of previously-spilled batches is handled separately by
+/// [`replay_spilled_batches`] — `DurableSink` only covers the "spill on
+/// failure" half of the contract, so a live flush is never held up waiting
+/// on disk I/O for anything but the failure path.
+#[derive(Clone)]
+pub struct DurableSink {
+    sink: Arc<dyn LogSink>,
+    config: SpillConfig,
+}
+
+impl DurableSink {
+    /// Create a sink that sends through `sink`, spilling failed batches
+    /// under `config.spill_dir`.
+    pub fn new(sink: Arc<dyn LogSink>, config: SpillConfig) -> Self {
+        Self { sink, config }
+    }
+
+    /// Send `batch`, spilling it to disk instead of losing it if the
+    /// underlying transport exhausts its retries.
+    ///
+    /// Returns `Err` only if the batch could neither be delivered nor
+    /// spilled (e.g. the spill directory isn't writable) — at that point
+    /// the log data really is lost, and the caller's usual "failed to
+    /// flush batch" warning is the only record of it.
+    pub async fn flush(&self, batch: LogBatch) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let spill_copy = batch.clone();
+        match self.sink.send(batch.logs).await {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                warn!(batch_id = ?spill_copy.batch_id, error = %e, "Batch delivery failed, spilling to disk");
+                spill_batch(&self.config, &spill_copy).map_err(|spill_err| {
+                    warn!(
+                        batch_id = ?spill_copy.batch_id,
+                        error = %spill_err,
+                        "Failed to spill undelivered batch, log data lost"
+                    );
+                    Box::new(spill_err) as Box<dyn std::error::Error + Send + Sync>
+                })
+            }
+        }
+    }
+}
+
+/// Append `batch` as a single newline-delimited JSON record to today's spill
+/// file under `config.spill_dir`, creating the directory and file as
+/// needed. If the spill directory is already at or past `config.max_bytes`,
+/// the batch is dropped and a warning is logged, rather than growing the
+/// write-ahead log without bound.
+pub fn spill_batch(config: &SpillConfig, batch: &LogBatch) -> Result<(), PersistenceError> {
+    fs::create_dir_all(&config.spill_dir)?;
+
+    let current_size = total_spill_bytes(&config.spill_dir)?;
+    if current_size >= config.max_bytes {
+        warn!(
+            spill_dir = %config.spill_dir.display(),
+            current_size,
+            max_bytes = config.max_bytes,
+            "Spill directory at capacity, dropping undeliverable batch"
+        );
+        return Ok(());
+    }
+
+    let path = spill_path_for_today(&config.spill_dir);
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    let line = serde_json::to_string(batch)?;
+    writeln!(file, "{}", line)?;
+
+    debug!(path = %path.display(), batch_id = ?batch.batch_id, "Spilled batch to disk");
+    Ok(())
+}
+
+/// Read every batch out of the oldest spill files under `config.spill_dir`
+/// and hand each to `send`, in file order. A file is removed once every
+/// batch in it has been sent; the first failure stops replay for that file
+/// (rewriting it with only the unsent remainder) and leaves every
+/// subsequent file untouched, so nothing is lost or reordered across a
+/// retry.
+///
+/// Returns the number of batches successfully replayed.
+pub async fn replay_spilled_batches<F, Fut>(
+    config: &SpillConfig,
+    mut send: F,
+) -> Result<usize, PersistenceError>
+where
+    F: FnMut(LogBatch) -> Fut,
+    Fut: std::future::Future<Output = Result<(), Box<dyn std::error::Error + Send + Sync>>>,
+{
+    let mut replayed = 0usize;
+
+    for path in spill_files(&config.spill_dir)? {
+        let reader = BufReader::new(File::open(&path)?);
+        let mut remaining: Vec<String> = Vec::new();
+        let mut failed = false;
+
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            if failed {
+                remaining.push(line);
+                continue;
+            }
+
+            let batch: LogBatch = serde_json::from_str(&line)?;
+            let batch_id = batch.batch_id;
+            match send(batch).await {
+                Ok(()) => replayed += 1,
+                Err(e) => {
+                    warn!(path = %path.display(), batch_id = ?batch_id, error = %e, "Replay failed, will retry on next reconnect");
+                    remaining.push(line);
+                    failed = true;
+                }
+            }
+        }
+
+        if failed {
+            let mut file = File::create(&path)?;
+            for line in &remaining {
+                writeln!(file, "{}", line)?;
+            }
+            info!(path = %path.display(), remaining = remaining.len(), "Stopping replay, backend unreachable again");
+            break;
+        }
+
+        fs::remove_file(&path)?;
+        debug!(path = %path.display(), "Replayed and removed spill file");
+    }
+
+    Ok(replayed)
+}
+
+/// Run [`replay_spilled_batches`] every `retry_interval`, forever.
+///
+/// Intended to run alongside the live buffer task: a first replay pass
+/// typically drains whatever accumulated during the last outage, and this
+/// loop keeps checking afterward in case a later outage spills more.
+pub async fn run_replay_loop(config: SpillConfig, sink: Arc<dyn LogSink>, retry_interval: Duration) {
+    loop {
+        let outcome = replay_spilled_batches(&config, |batch| {
+            let sink = sink.clone();
+            async move {
+                sink.send(batch.logs)
+                    .await
+                    .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+            }
+        })
+        .await;
+
+        match outcome {
+            Ok(replayed) if replayed > 0 => {
+                info!(replayed, "Replayed spilled batches");
+            }
+            Ok(_) => {}
+            Err(e) => {
+                warn!(error = %e, "Failed to replay spilled batches");
+            }
+        }
+
+        tokio::time::sleep(retry_interval).await;
+    }
+}
+
+fn spill_path_for_today(dir: &Path) -> PathBuf {
+    dir.join(format!("spill-{}.ndjson", Utc::now().format("%Y-%m-%d")))
+}
+
+fn total_spill_bytes(dir: &Path) -> io::Result<u64> {
+    let mut total = 0u64;
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_type()?.is_file() {
+            total += entry.metadata()?.len();
+        }
+    }
+    Ok(total)
+}
+
+/// List spill files in `dir`, oldest-first. `spill-YYYY-MM-DD.ndjson` names
+/// sort chronologically, so a plain sort is enough.
+fn spill_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "ndjson"))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log_generator::{LogEntry, LogLevel};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn test_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("edge-collector-persistence-test-{}-{}", name, std::process::id()))
+    }
+
+    fn create_test_batch(tag: &str) -> LogBatch {
+        LogBatch::new(vec![LogEntry::new(tag, LogLevel::Info, "test message")])
+    }
+
+    #[test]
+    fn test_spill_config_default() {
+        let config = SpillConfig::default();
+        assert_eq!(config.spill_dir, PathBuf::from(DEFAULT_SPILL_DIR));
+        assert_eq!(config.max_bytes, DEFAULT_SPILL_MAX_BYTES);
+    }
+
+    #[test]
+    fn test_spill_config_from_env() {
+        let dir = test_dir("from-env");
+        std::env::set_var("EDGE_COLLECTOR_SPILL_DIR", &dir);
+        std::env::set_var("EDGE_COLLECTOR_SPILL_MAX_BYTES", "2048");
+
+        let config = SpillConfig::from_env();
+        assert_eq!(config.spill_dir, dir);
+        assert_eq!(config.max_bytes, 2048);
+
+        std::env::remove_var("EDGE_COLLECTOR_SPILL_DIR");
+        std::env::remove_var("EDGE_COLLECTOR_SPILL_MAX_BYTES");
+    }
+
+    #[test]
+    fn test_spill_batch_appends_ndjson_line() {
+        let dir = test_dir("append");
+        let _ = fs::remove_dir_all(&dir);
+        let config = SpillConfig { spill_dir: dir.clone(), max_bytes: DEFAULT_SPILL_MAX_BYTES };
+
+        spill_batch(&config, &create_test_batch("a")).unwrap();
+        spill_batch(&config, &create_test_batch("b")).unwrap();
+
+        let path = spill_path_for_today(&dir);
+        let contents = fs::read_to_string(&path).unwrap();
+        assert_eq!(contents.lines().count(), 2);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_spill_batch_drops_over_capacity() {
+        let dir = test_dir("capacity");
+        let _ = fs::remove_dir_all(&dir);
+        let config = SpillConfig { spill_dir: dir.clone(), max_bytes: 1 };
+
+        spill_batch(&config, &create_test_batch("over")).unwrap();
+
+        let path = spill_path_for_today(&dir);
+        assert!(!path.exists());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_replay_removes_file_on_full_success() {
+        let dir = test_dir("replay-success");
+        let _ = fs::remove_dir_all(&dir);
+        let config = SpillConfig { spill_dir: dir.clone(), max_bytes: DEFAULT_SPILL_MAX_BYTES };
+
+        spill_batch(&config, &create_test_batch("one")).unwrap();
+        spill_batch(&config, &create_test_batch("two")).unwrap();
+
+        let sent = Arc::new(AtomicUsize::new(0));
+        let sent_clone = sent.clone();
+        let replayed = replay_spilled_batches(&config, move |_batch| {
+            let sent = sent_clone.clone();
+            async move {
+                sent.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(replayed, 2);
+        assert_eq!(sent.load(Ordering::SeqCst), 2);
+        assert!(!spill_path_for_today(&dir).exists());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_replay_retains_unsent_on_failure() {
+        let dir = test_dir("replay-failure");
+        let _ = fs::remove_dir_all(&dir);
+        let config = SpillConfig { spill_dir: dir.clone(), max_bytes: DEFAULT_SPILL_MAX_BYTES };
+
+        spill_batch(&config, &create_test_batch("one")).unwrap();
+        spill_batch(&config, &create_test_batch("two")).unwrap();
+
+        let replayed = replay_spilled_batches(&config, |_batch| async {
+            Err("backend unreachable".into())
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(replayed, 0);
+        let path = spill_path_for_today(&dir);
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 2);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_durable_sink_spills_on_delivery_failure() {
+        let dir = test_dir("durable-sink");
+        let _ = fs::remove_dir_all(&dir);
+        let config = SpillConfig { spill_dir: dir.clone(), max_bytes: DEFAULT_SPILL_MAX_BYTES };
+
+        // Port 1 is reserved and refuses connections immediately.
+        let client = Arc::new(
+            crate::client::LogClient::with_settings("http://127.0.0.1:1/ingest", Duration::from_millis(100), 0)
+                .unwrap(),
+        );
+        let sink: Arc<dyn LogSink> = Arc::new(crate::transport::HttpSink::new(client));
+        let durable_sink = DurableSink::new(sink, config);
+
+        durable_sink.flush(create_test_batch("unreachable")).await.unwrap();
+
+        let path = spill_path_for_today(&dir);
+        assert!(path.exists());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
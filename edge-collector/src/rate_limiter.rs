@@ -0,0 +1,168 @@
+//! Client-side send-rate limiting for [`crate::client::LogClient`].
+//!
+//! Retries already have their own pacing via
+//! [`crate::client`]'s retry token bucket, but nothing stops a burst of
+//! *new* batches from hammering the cloud API faster than it accepts —
+//! which just turns into a wave of self-inflicted 429s. [`TokenBucket`]
+//! proactively paces the first attempt of every send: tokens refill
+//! continuously based on elapsed wall-clock time, up to a configured burst
+//! capacity, and a send either waits for one to become available or fails
+//! fast, depending on the caller.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Rate and burst capacity for a [`TokenBucket`].
+#[derive(Debug, Clone, Copy)]
+pub struct TokenBucketConfig {
+    /// Steady-state tokens refilled per second (i.e. sustained requests/sec).
+    pub rate_per_sec: f64,
+
+    /// Maximum tokens the bucket can hold, bounding how large a burst of
+    /// sends can proceed without waiting.
+    pub burst: f64,
+}
+
+impl Default for TokenBucketConfig {
+    fn default() -> Self {
+        Self {
+            rate_per_sec: 50.0,
+            burst: 100.0,
+        }
+    }
+}
+
+struct Inner {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// A continuously-refilling token bucket gating how often sends may start.
+///
+/// Cloning shares the same underlying state, the same pattern as
+/// [`crate::circuit_breaker::CircuitBreaker`].
+#[derive(Clone)]
+pub struct TokenBucket {
+    config: TokenBucketConfig,
+    inner: std::sync::Arc<Mutex<Inner>>,
+}
+
+impl Default for TokenBucket {
+    fn default() -> Self {
+        Self::new(TokenBucketConfig::default())
+    }
+}
+
+impl TokenBucket {
+    /// Create a bucket starting full (`config.burst` tokens available).
+    pub fn new(config: TokenBucketConfig) -> Self {
+        Self {
+            config,
+            inner: std::sync::Arc::new(Mutex::new(Inner {
+                tokens: config.burst,
+                last_refill: Instant::now(),
+            })),
+        }
+    }
+
+    /// Refill tokens based on elapsed time since the last refill, capped at
+    /// the configured burst.
+    fn refill(&self, inner: &mut Inner) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(inner.last_refill).as_secs_f64();
+        inner.tokens = (inner.tokens + elapsed * self.config.rate_per_sec).min(self.config.burst);
+        inner.last_refill = now;
+    }
+
+    /// Try to take a token without waiting. Returns `true` if one was
+    /// available.
+    pub fn try_acquire(&self) -> bool {
+        let mut inner = self.inner.lock().expect("token bucket mutex poisoned");
+        self.refill(&mut inner);
+
+        if inner.tokens >= 1.0 {
+            inner.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Wait until a token is available, take it, and return how long the
+    /// wait took (`Duration::ZERO` if a token was already available).
+    pub async fn acquire(&self) -> Duration {
+        let start = Instant::now();
+
+        loop {
+            let wait = {
+                let mut inner = self.inner.lock().expect("token bucket mutex poisoned");
+                self.refill(&mut inner);
+
+                if inner.tokens >= 1.0 {
+                    inner.tokens -= 1.0;
+                    None
+                } else {
+                    let deficit = 1.0 - inner.tokens;
+                    Some(Duration::from_secs_f64(deficit / self.config.rate_per_sec))
+                }
+            };
+
+            match wait {
+                None => return start.elapsed(),
+                Some(delay) => tokio::time::sleep(delay.max(Duration::from_millis(1))).await,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_starts_full_and_drains() {
+        let bucket = TokenBucket::new(TokenBucketConfig {
+            rate_per_sec: 10.0,
+            burst: 3.0,
+        });
+
+        assert!(bucket.try_acquire());
+        assert!(bucket.try_acquire());
+        assert!(bucket.try_acquire());
+        assert!(!bucket.try_acquire()); // burst exhausted
+    }
+
+    #[tokio::test]
+    async fn test_refills_over_time() {
+        let bucket = TokenBucket::new(TokenBucketConfig {
+            rate_per_sec: 1000.0, // fast refill to keep the test quick
+            burst: 1.0,
+        });
+
+        assert!(bucket.try_acquire());
+        assert!(!bucket.try_acquire());
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(bucket.try_acquire());
+    }
+
+    #[tokio::test]
+    async fn test_acquire_waits_for_a_token() {
+        let bucket = TokenBucket::new(TokenBucketConfig {
+            rate_per_sec: 100.0,
+            burst: 1.0,
+        });
+
+        assert!(bucket.try_acquire()); // drain the only token
+
+        let waited = bucket.acquire().await;
+        assert!(waited >= Duration::from_millis(5));
+    }
+
+    #[tokio::test]
+    async fn test_acquire_returns_immediately_when_token_available() {
+        let bucket = TokenBucket::default();
+        let waited = bucket.acquire().await;
+        assert!(waited < Duration::from_millis(5));
+    }
+}
@@ -0,0 +1,249 @@
+//! Real and synthetic sensor readings behind a common [`SensorSource`] trait.
+//!
+//! [`SensorType`] maps cleanly onto real edge hardware exposed through Linux
+//! `hwmon`/`sysfs` (`/sys/class/hwmon/*/temp*_input` for temperature,
+//! `/sys/class/hwmon/*/power*_input` for power rails). [`HwmonSensorSource`]
+//! reads those files directly; [`SyntheticSensorSource`] wraps the same
+//! [`crate::sensor_template::SensorRegistry`] the dummy log generator already
+//! uses, so the same batching/serialization code in [`crate::log_generator`]
+//! emits real telemetry on-device and synthetic data in tests, just by
+//! swapping which `Box<dyn SensorSource>` [`crate::log_generator::LogGenerator`]
+//! was built with.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use rand::Rng;
+
+use crate::log_generator::{LogLevel, SensorType};
+use crate::sensor_template::SensorRegistry;
+
+/// Default root hwmon directory on Linux.
+pub const DEFAULT_HWMON_ROOT: &str = "/sys/class/hwmon";
+
+/// Reads a single value for a [`SensorType`], from real hardware or a
+/// synthetic fallback.
+///
+/// Returns `None` when this source has no reading for `sensor_type` right
+/// now — the sensor file is absent/unreadable, or the backing device is
+/// idle — so callers can fall back to another source rather than treating
+/// it as a hard error.
+pub trait SensorSource: Send + Sync {
+    /// Read the current value for `sensor_type`, or `None` if unavailable.
+    fn read(&self, sensor_type: SensorType) -> Option<f64>;
+}
+
+/// Reads temperature and power values from Linux `hwmon`/`sysfs`.
+///
+/// Only [`SensorType::Temperature`] and [`SensorType::Power`] have an hwmon
+/// equivalent; every other [`SensorType`] always reads as `None` here.
+pub struct HwmonSensorSource {
+    hwmon_root: PathBuf,
+}
+
+impl HwmonSensorSource {
+    /// Create a source rooted at [`DEFAULT_HWMON_ROOT`].
+    pub fn new() -> Self {
+        Self::with_root(DEFAULT_HWMON_ROOT)
+    }
+
+    /// Create a source rooted at a custom hwmon directory (mainly for tests).
+    pub fn with_root(root: impl Into<PathBuf>) -> Self {
+        Self {
+            hwmon_root: root.into(),
+        }
+    }
+
+    fn hwmon_dirs(&self) -> Vec<PathBuf> {
+        fs::read_dir(&self.hwmon_root)
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .collect()
+    }
+
+    /// Whether the device backing `hwmon_dir` is runtime-suspended.
+    ///
+    /// Polling a `temp*_input`/`power*_input` file on a suspended device can
+    /// wake it just to answer the read, so this is checked before reading
+    /// any file under the directory.
+    fn is_idle(hwmon_dir: &Path) -> bool {
+        let runtime_status = hwmon_dir.join("device").join("power").join("runtime_status");
+        fs::read_to_string(runtime_status)
+            .map(|status| status.trim() == "suspended")
+            .unwrap_or(false)
+    }
+
+    /// Read the first file in `hwmon_dir` matching `{prefix}*{suffix}`,
+    /// scaling its raw integer value by `scale`.
+    fn read_scaled(hwmon_dir: &Path, prefix: &str, suffix: &str, scale: f64) -> Option<f64> {
+        let entries = fs::read_dir(hwmon_dir).ok()?;
+        for entry in entries.flatten() {
+            let file_name = entry.file_name();
+            let file_name = file_name.to_str()?;
+            if !file_name.starts_with(prefix) || !file_name.ends_with(suffix) {
+                continue;
+            }
+
+            if let Ok(contents) = fs::read_to_string(entry.path()) {
+                if let Ok(raw) = contents.trim().parse::<f64>() {
+                    return Some(raw * scale);
+                }
+            }
+        }
+        None
+    }
+}
+
+impl Default for HwmonSensorSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SensorSource for HwmonSensorSource {
+    fn read(&self, sensor_type: SensorType) -> Option<f64> {
+        // (file prefix, file suffix, raw-value -> reported-unit scale)
+        let (prefix, suffix, scale) = match sensor_type {
+            SensorType::Temperature => ("temp", "_input", 1.0 / 1_000.0), // millidegrees C -> C
+            SensorType::Power => ("power", "_input", 1.0 / 1_000_000.0), // microwatts -> W
+            _ => return None,
+        };
+
+        for hwmon_dir in self.hwmon_dirs() {
+            if Self::is_idle(&hwmon_dir) {
+                continue;
+            }
+            if let Some(value) = Self::read_scaled(&hwmon_dir, prefix, suffix, scale) {
+                return Some(value);
+            }
+        }
+
+        None
+    }
+}
+
+/// Falls back to the same synthetic ranges [`crate::log_generator::LogGenerator`]
+/// uses, via the built-in [`SensorRegistry`] templates.
+///
+/// [`crate::log_generator::LogGenerator::new`] uses it as the default
+/// [`SensorSource`] on every platform. When a real source has no reading,
+/// [`crate::log_generator::LogGenerator::generate_from_source`] falls back to
+/// the same built-in templates directly rather than to this type.
+pub struct SyntheticSensorSource {
+    registry: SensorRegistry,
+}
+
+impl SyntheticSensorSource {
+    pub fn new() -> Self {
+        Self {
+            registry: SensorRegistry::with_defaults(),
+        }
+    }
+}
+
+impl Default for SyntheticSensorSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SensorSource for SyntheticSensorSource {
+    fn read(&self, sensor_type: SensorType) -> Option<f64> {
+        let template = self.registry.get(sensor_type.name())?;
+        let mut rng = rand::thread_rng();
+        let (reading, _metadata, _message) = template.generate(&mut rng, LogLevel::Info);
+        Some(reading)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_file(dir: &Path, name: &str, contents: &str) {
+        fs::write(dir.join(name), contents).unwrap();
+    }
+
+    fn unique_temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "edge_collector_sensor_source_test_{}_{}",
+            name,
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_synthetic_source_reads_every_builtin_sensor_type() {
+        let source = SyntheticSensorSource::new();
+        for sensor_type in SensorType::all() {
+            assert!(
+                source.read(*sensor_type).is_some(),
+                "synthetic source should cover every built-in sensor type"
+            );
+        }
+    }
+
+    #[test]
+    fn test_hwmon_source_reads_temperature_from_sysfs_layout() {
+        let root = unique_temp_dir("temp_reading");
+        let hwmon0 = root.join("hwmon0");
+        fs::create_dir_all(&hwmon0).unwrap();
+        write_file(&hwmon0, "temp1_input", "42500\n"); // 42.5C in millidegrees
+
+        let source = HwmonSensorSource::with_root(&root);
+        let reading = source.read(SensorType::Temperature).expect("should read temp1_input");
+        assert!((reading - 42.5).abs() < f64::EPSILON);
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn test_hwmon_source_reads_power_from_sysfs_layout() {
+        let root = unique_temp_dir("power_reading");
+        let hwmon0 = root.join("hwmon0");
+        fs::create_dir_all(&hwmon0).unwrap();
+        write_file(&hwmon0, "power1_input", "5000000\n"); // 5W in microwatts
+
+        let source = HwmonSensorSource::with_root(&root);
+        let reading = source.read(SensorType::Power).expect("should read power1_input");
+        assert!((reading - 5.0).abs() < f64::EPSILON);
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn test_hwmon_source_skips_idle_device() {
+        let root = unique_temp_dir("idle_skip");
+        let hwmon0 = root.join("hwmon0");
+        let power_dir = hwmon0.join("device").join("power");
+        fs::create_dir_all(&power_dir).unwrap();
+        write_file(&hwmon0, "temp1_input", "30000\n");
+        write_file(&power_dir, "runtime_status", "suspended\n");
+
+        let source = HwmonSensorSource::with_root(&root);
+        assert!(
+            source.read(SensorType::Temperature).is_none(),
+            "idle device should not be polled"
+        );
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn test_hwmon_source_returns_none_for_unsupported_sensor_type() {
+        let root = unique_temp_dir("unsupported");
+        let source = HwmonSensorSource::with_root(&root);
+        assert!(source.read(SensorType::Humidity).is_none());
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn test_hwmon_source_returns_none_when_root_missing() {
+        let source = HwmonSensorSource::with_root("/nonexistent/hwmon/root/for/tests");
+        assert!(source.read(SensorType::Temperature).is_none());
+    }
+}
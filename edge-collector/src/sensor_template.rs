@@ -0,0 +1,606 @@
+//! Template-driven custom sensor definitions.
+//!
+//! `SensorType` covers a fixed set of built-in sensors baked into the crate.
+//! This module lets callers register additional sensor kinds (e.g. `co2`,
+//! `soil_moisture`) at runtime without editing the crate, modeled after the
+//! `SensorTemplate`/`try_from_template` pattern used by the `spaceapi` crate.
+//! A template owns its own normal/warning/error ranges and message text, so
+//! `LogGenerator` can dispatch over a [`SensorRegistry`] instead of a
+//! hardcoded match.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use rand::RngCore;
+
+use crate::log_generator::LogLevel;
+
+/// Produces realistic readings, metadata, and log messages for one sensor kind.
+///
+/// A template owns the full generation logic for its sensor: the normal,
+/// warning, and error value ranges, any sensor-specific extra metadata
+/// fields (e.g. `pm25`, `frequency_hz`), and the human-readable message text.
+pub trait SensorTemplate {
+    /// Stable name used as the `sensor_type` metadata value and in `source_id`s.
+    fn name(&self) -> &str;
+
+    /// Unit string reported alongside readings (e.g. `"celsius"`).
+    fn unit(&self) -> &str;
+
+    /// Generate a `(reading, extra_metadata, message)` triple for the given level.
+    ///
+    /// `extra_metadata` should NOT include `sensor_type`, `unit`, `reading`,
+    /// or `sequence` — those common fields are filled in by the caller.
+    fn generate(
+        &self,
+        rng: &mut dyn RngCore,
+        level: LogLevel,
+    ) -> (f64, HashMap<String, serde_json::Value>, String);
+}
+
+/// A registry of sensor templates that a [`crate::log_generator::LogGenerator`]
+/// samples from.
+///
+/// Built via [`SensorRegistry::with_defaults`] to get the eight built-in
+/// sensors equivalent to the original `SensorType` enum, then extended with
+/// [`SensorRegistry::register`] (or [`GeneratorConfig::with_sensor`] at the
+/// config layer) to add custom sensor kinds.
+///
+/// [`GeneratorConfig::with_sensor`]: crate::log_generator::GeneratorConfig::with_sensor
+#[derive(Clone)]
+pub struct SensorRegistry {
+    templates: Vec<Arc<dyn SensorTemplate + Send + Sync>>,
+}
+
+impl SensorRegistry {
+    /// Create an empty registry with no templates.
+    pub fn new() -> Self {
+        Self {
+            templates: Vec::new(),
+        }
+    }
+
+    /// Create a registry pre-populated with the eight built-in sensor templates.
+    pub fn with_defaults() -> Self {
+        let mut registry = Self::new();
+        registry.register(Arc::new(TemperatureTemplate));
+        registry.register(Arc::new(HumidityTemplate));
+        registry.register(Arc::new(PressureTemplate));
+        registry.register(Arc::new(MotionTemplate));
+        registry.register(Arc::new(LightTemplate));
+        registry.register(Arc::new(VibrationTemplate));
+        registry.register(Arc::new(AirQualityTemplate));
+        registry.register(Arc::new(PowerTemplate));
+        registry
+    }
+
+    /// Register an additional template, making it eligible for sampling.
+    pub fn register(&mut self, template: Arc<dyn SensorTemplate + Send + Sync>) {
+        self.templates.push(template);
+    }
+
+    /// Number of registered templates.
+    pub fn len(&self) -> usize {
+        self.templates.len()
+    }
+
+    /// Whether the registry has no templates.
+    pub fn is_empty(&self) -> bool {
+        self.templates.is_empty()
+    }
+
+    /// Look up a template by name.
+    pub fn get(&self, name: &str) -> Option<&(dyn SensorTemplate + Send + Sync)> {
+        self.templates
+            .iter()
+            .find(|t| t.name() == name)
+            .map(|t| t.as_ref())
+    }
+
+    /// Sample a random template, uniformly across all registered templates.
+    pub fn sample(&self, rng: &mut impl rand::Rng) -> Option<&(dyn SensorTemplate + Send + Sync)> {
+        if self.templates.is_empty() {
+            return None;
+        }
+        let idx = rng.gen_range(0..self.templates.len());
+        Some(self.templates[idx].as_ref())
+    }
+}
+
+impl Default for SensorRegistry {
+    fn default() -> Self {
+        Self::with_defaults()
+    }
+}
+
+fn sequence_metadata(rng: &mut dyn RngCore) -> serde_json::Value {
+    use rand::Rng;
+    serde_json::Value::Number(rng.gen_range(1..=999999u32).into())
+}
+
+/// Built-in templates equivalent to the original `SensorType` variants.
+pub struct TemperatureTemplate;
+pub struct HumidityTemplate;
+pub struct PressureTemplate;
+pub struct MotionTemplate;
+pub struct LightTemplate;
+pub struct VibrationTemplate;
+pub struct AirQualityTemplate;
+pub struct PowerTemplate;
+
+impl SensorTemplate for TemperatureTemplate {
+    fn name(&self) -> &str {
+        "temperature"
+    }
+
+    fn unit(&self) -> &str {
+        "celsius"
+    }
+
+    fn generate(
+        &self,
+        rng: &mut dyn RngCore,
+        level: LogLevel,
+    ) -> (f64, HashMap<String, serde_json::Value>, String) {
+        use rand::Rng;
+        let temp = match level {
+            LogLevel::Error | LogLevel::Fatal => {
+                if rng.gen_bool(0.5) {
+                    rng.gen_range(35.0..50.0)
+                } else {
+                    rng.gen_range(-10.0..10.0)
+                }
+            }
+            LogLevel::Warn => {
+                if rng.gen_bool(0.5) {
+                    rng.gen_range(26.0..35.0)
+                } else {
+                    rng.gen_range(10.0..18.0)
+                }
+            }
+            _ => rng.gen_range(18.0..26.0),
+        };
+
+        let message = match level {
+            LogLevel::Error | LogLevel::Fatal => {
+                format!("CRITICAL: Temperature reading {:.1}C is outside safe range", temp)
+            }
+            LogLevel::Warn => format!("Temperature {:.1}C approaching threshold limits", temp),
+            LogLevel::Info => format!("Temperature reading: {:.1}C", temp),
+            LogLevel::Debug => format!("Sensor calibration check: {:.1}C within tolerance", temp),
+            LogLevel::Trace => format!("Raw temperature ADC value converted to {:.1}C", temp),
+        };
+
+        (temp, HashMap::new(), message)
+    }
+}
+
+impl SensorTemplate for HumidityTemplate {
+    fn name(&self) -> &str {
+        "humidity"
+    }
+
+    fn unit(&self) -> &str {
+        "percent"
+    }
+
+    fn generate(
+        &self,
+        rng: &mut dyn RngCore,
+        level: LogLevel,
+    ) -> (f64, HashMap<String, serde_json::Value>, String) {
+        use rand::Rng;
+        let humidity = match level {
+            LogLevel::Error | LogLevel::Fatal => {
+                if rng.gen_bool(0.5) {
+                    rng.gen_range(85.0..100.0)
+                } else {
+                    rng.gen_range(0.0..15.0)
+                }
+            }
+            LogLevel::Warn => {
+                if rng.gen_bool(0.5) {
+                    rng.gen_range(70.0..85.0)
+                } else {
+                    rng.gen_range(15.0..30.0)
+                }
+            }
+            _ => rng.gen_range(30.0..70.0),
+        };
+
+        let message = match level {
+            LogLevel::Error | LogLevel::Fatal => {
+                format!("ALERT: Humidity {:.1}% outside operational limits", humidity)
+            }
+            LogLevel::Warn => format!("Humidity {:.1}% nearing threshold", humidity),
+            _ => format!("Humidity reading: {:.1}%", humidity),
+        };
+
+        (humidity, HashMap::new(), message)
+    }
+}
+
+impl SensorTemplate for PressureTemplate {
+    fn name(&self) -> &str {
+        "pressure"
+    }
+
+    fn unit(&self) -> &str {
+        "hpa"
+    }
+
+    fn generate(
+        &self,
+        rng: &mut dyn RngCore,
+        level: LogLevel,
+    ) -> (f64, HashMap<String, serde_json::Value>, String) {
+        use rand::Rng;
+        let pressure = match level {
+            LogLevel::Error | LogLevel::Fatal => {
+                if rng.gen_bool(0.5) {
+                    rng.gen_range(1040.0..1060.0)
+                } else {
+                    rng.gen_range(950.0..980.0)
+                }
+            }
+            LogLevel::Warn => {
+                if rng.gen_bool(0.5) {
+                    rng.gen_range(1025.0..1040.0)
+                } else {
+                    rng.gen_range(980.0..1000.0)
+                }
+            }
+            _ => rng.gen_range(1000.0..1025.0),
+        };
+
+        let message = match level {
+            LogLevel::Error | LogLevel::Fatal => {
+                format!("CRITICAL: Barometric pressure {:.1} hPa is abnormal", pressure)
+            }
+            LogLevel::Warn => format!("Pressure {:.1} hPa deviation detected", pressure),
+            _ => format!("Pressure reading: {:.1} hPa", pressure),
+        };
+
+        (pressure, HashMap::new(), message)
+    }
+}
+
+impl SensorTemplate for MotionTemplate {
+    fn name(&self) -> &str {
+        "motion"
+    }
+
+    fn unit(&self) -> &str {
+        "detected"
+    }
+
+    fn generate(
+        &self,
+        rng: &mut dyn RngCore,
+        level: LogLevel,
+    ) -> (f64, HashMap<String, serde_json::Value>, String) {
+        use rand::Rng;
+        let detected = rng.gen_bool(0.3);
+        let confidence = rng.gen_range(70..=100u32);
+
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "motion_detected".to_string(),
+            serde_json::Value::Bool(detected),
+        );
+        metadata.insert(
+            "confidence".to_string(),
+            serde_json::Value::Number(confidence.into()),
+        );
+
+        let message = match level {
+            LogLevel::Error | LogLevel::Fatal => "Motion sensor communication failure".to_string(),
+            LogLevel::Warn => format!("Motion detection confidence low: {}%", confidence),
+            _ => {
+                if detected {
+                    format!("Motion detected with {}% confidence", confidence)
+                } else {
+                    "No motion detected".to_string()
+                }
+            }
+        };
+
+        (if detected { 1.0 } else { 0.0 }, metadata, message)
+    }
+}
+
+impl SensorTemplate for LightTemplate {
+    fn name(&self) -> &str {
+        "light"
+    }
+
+    fn unit(&self) -> &str {
+        "lux"
+    }
+
+    fn generate(
+        &self,
+        rng: &mut dyn RngCore,
+        level: LogLevel,
+    ) -> (f64, HashMap<String, serde_json::Value>, String) {
+        use rand::Rng;
+        let lux = match level {
+            LogLevel::Error | LogLevel::Fatal => rng.gen_range(0.0..10.0),
+            LogLevel::Warn => {
+                if rng.gen_bool(0.5) {
+                    rng.gen_range(10.0..100.0)
+                } else {
+                    rng.gen_range(1000.0..2000.0)
+                }
+            }
+            _ => rng.gen_range(300.0..700.0),
+        };
+
+        let message = match level {
+            LogLevel::Error | LogLevel::Fatal => {
+                format!("CRITICAL: Light sensor reading {:.0} lux indicates failure", lux)
+            }
+            LogLevel::Warn => format!("Light level {:.0} lux outside normal range", lux),
+            _ => format!("Light level: {:.0} lux", lux),
+        };
+
+        (lux, HashMap::new(), message)
+    }
+}
+
+impl SensorTemplate for VibrationTemplate {
+    fn name(&self) -> &str {
+        "vibration"
+    }
+
+    fn unit(&self) -> &str {
+        "g"
+    }
+
+    fn generate(
+        &self,
+        rng: &mut dyn RngCore,
+        level: LogLevel,
+    ) -> (f64, HashMap<String, serde_json::Value>, String) {
+        use rand::Rng;
+        let vibration = match level {
+            LogLevel::Error | LogLevel::Fatal => rng.gen_range(2.0..5.0),
+            LogLevel::Warn => rng.gen_range(0.5..2.0),
+            _ => rng.gen_range(0.0..0.5),
+        };
+        let frequency = rng.gen_range(10.0..500.0);
+
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "frequency_hz".to_string(),
+            serde_json::Number::from_f64(frequency)
+                .map(serde_json::Value::Number)
+                .unwrap_or(serde_json::Value::Null),
+        );
+
+        let message = match level {
+            LogLevel::Error | LogLevel::Fatal => {
+                format!("CRITICAL: Excessive vibration {:.2}g detected", vibration)
+            }
+            LogLevel::Warn => format!("Elevated vibration level: {:.2}g", vibration),
+            _ => format!("Vibration reading: {:.3}g", vibration),
+        };
+
+        (vibration, metadata, message)
+    }
+}
+
+impl SensorTemplate for AirQualityTemplate {
+    fn name(&self) -> &str {
+        "air_quality"
+    }
+
+    fn unit(&self) -> &str {
+        "aqi"
+    }
+
+    fn generate(
+        &self,
+        rng: &mut dyn RngCore,
+        level: LogLevel,
+    ) -> (f64, HashMap<String, serde_json::Value>, String) {
+        use rand::Rng;
+        let aqi = match level {
+            LogLevel::Error | LogLevel::Fatal => rng.gen_range(200..500u32),
+            LogLevel::Warn => rng.gen_range(100..200u32),
+            _ => rng.gen_range(0..50u32),
+        };
+        let pm25 = rng.gen_range(0.0..100.0);
+
+        let category = match aqi {
+            0..=50 => "Good",
+            51..=100 => "Moderate",
+            101..=150 => "Unhealthy for Sensitive Groups",
+            151..=200 => "Unhealthy",
+            201..=300 => "Very Unhealthy",
+            _ => "Hazardous",
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "pm25".to_string(),
+            serde_json::Number::from_f64(pm25)
+                .map(serde_json::Value::Number)
+                .unwrap_or(serde_json::Value::Null),
+        );
+
+        let message = match level {
+            LogLevel::Error | LogLevel::Fatal => {
+                format!("ALERT: Air quality index {} ({}) - take action", aqi, category)
+            }
+            LogLevel::Warn => format!("Air quality degraded: AQI {} ({})", aqi, category),
+            _ => format!("Air quality: AQI {} ({})", aqi, category),
+        };
+
+        (aqi as f64, metadata, message)
+    }
+}
+
+impl SensorTemplate for PowerTemplate {
+    fn name(&self) -> &str {
+        "power"
+    }
+
+    fn unit(&self) -> &str {
+        "watts"
+    }
+
+    fn generate(
+        &self,
+        rng: &mut dyn RngCore,
+        level: LogLevel,
+    ) -> (f64, HashMap<String, serde_json::Value>, String) {
+        use rand::Rng;
+        let power = match level {
+            LogLevel::Error | LogLevel::Fatal => rng.gen_range(1000.0..2000.0),
+            LogLevel::Warn => rng.gen_range(500.0..1000.0),
+            _ => rng.gen_range(50.0..500.0),
+        };
+        let voltage = rng.gen_range(118.0..122.0);
+
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "voltage".to_string(),
+            serde_json::Number::from_f64(voltage)
+                .map(serde_json::Value::Number)
+                .unwrap_or(serde_json::Value::Null),
+        );
+
+        let message = match level {
+            LogLevel::Error | LogLevel::Fatal => {
+                format!("CRITICAL: Power consumption {:.1}W exceeds limit", power)
+            }
+            LogLevel::Warn => format!("High power consumption: {:.1}W", power),
+            _ => format!("Power consumption: {:.1}W", power),
+        };
+
+        (power, metadata, message)
+    }
+}
+
+/// Build the common metadata fields (`sensor_type`, `unit`, `reading`, `sequence`)
+/// shared by every template, merging in the template's own extra fields.
+pub(crate) fn build_metadata(
+    template: &(dyn SensorTemplate + Send + Sync),
+    reading: f64,
+    mut extra: HashMap<String, serde_json::Value>,
+    rng: &mut dyn RngCore,
+) -> HashMap<String, serde_json::Value> {
+    extra.insert(
+        "sensor_type".to_string(),
+        serde_json::Value::String(template.name().to_string()),
+    );
+    extra.insert(
+        "unit".to_string(),
+        serde_json::Value::String(template.unit().to_string()),
+    );
+    extra.insert(
+        "reading".to_string(),
+        serde_json::Number::from_f64(reading)
+            .map(serde_json::Value::Number)
+            .unwrap_or(serde_json::Value::Null),
+    );
+    extra.insert("sequence".to_string(), sequence_metadata(rng));
+    extra
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_with_defaults_has_eight_builtin_sensors() {
+        let registry = SensorRegistry::with_defaults();
+        assert_eq!(registry.len(), 8);
+    }
+
+    #[test]
+    fn test_empty_registry_sample_returns_none() {
+        let registry = SensorRegistry::new();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+        assert!(registry.sample(&mut rng).is_none());
+    }
+
+    #[test]
+    fn test_register_custom_template() {
+        struct Co2Template;
+        impl SensorTemplate for Co2Template {
+            fn name(&self) -> &str {
+                "co2"
+            }
+            fn unit(&self) -> &str {
+                "ppm"
+            }
+            fn generate(
+                &self,
+                _rng: &mut dyn RngCore,
+                _level: LogLevel,
+            ) -> (f64, HashMap<String, serde_json::Value>, String) {
+                (420.0, HashMap::new(), "CO2 reading: 420 ppm".to_string())
+            }
+        }
+
+        let mut registry = SensorRegistry::new();
+        registry.register(Arc::new(Co2Template));
+
+        assert_eq!(registry.len(), 1);
+        let template = registry.get("co2").expect("co2 template should be registered");
+        assert_eq!(template.unit(), "ppm");
+    }
+
+    #[test]
+    fn test_get_missing_template_returns_none() {
+        let registry = SensorRegistry::with_defaults();
+        assert!(registry.get("co2").is_none());
+    }
+
+    #[test]
+    fn test_temperature_template_generates_within_normal_band() {
+        let template = TemperatureTemplate;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let (reading, _metadata, message) = template.generate(&mut rng, LogLevel::Info);
+
+        assert!((18.0..26.0).contains(&reading));
+        assert!(message.contains("Temperature reading"));
+    }
+
+    #[test]
+    fn test_motion_template_reports_confidence_metadata() {
+        let template = MotionTemplate;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let (_reading, metadata, _message) = template.generate(&mut rng, LogLevel::Info);
+
+        assert!(metadata.contains_key("motion_detected"));
+        assert!(metadata.contains_key("confidence"));
+    }
+
+    #[test]
+    fn test_build_metadata_includes_common_fields() {
+        let template = PowerTemplate;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(3);
+        let mut extra = HashMap::new();
+        extra.insert("voltage".to_string(), serde_json::json!(120.0));
+
+        let metadata = build_metadata(&template, 100.0, extra, &mut rng);
+
+        assert_eq!(metadata.get("sensor_type").unwrap().as_str().unwrap(), "power");
+        assert_eq!(metadata.get("unit").unwrap().as_str().unwrap(), "watts");
+        assert_eq!(metadata.get("reading").unwrap().as_f64().unwrap(), 100.0);
+        assert!(metadata.contains_key("sequence"));
+        assert!(metadata.contains_key("voltage"));
+    }
+
+    #[test]
+    fn test_sample_returns_a_registered_template() {
+        let registry = SensorRegistry::with_defaults();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(9);
+        let template = registry.sample(&mut rng).expect("registry is non-empty");
+        assert!(!template.name().is_empty());
+    }
+}
@@ -0,0 +1,305 @@
+//! Concurrent batch transmission with bounded backpressure.
+//!
+//! [`spawn_batch_sink`] turns a [`crate::client::LogClient`] into an async
+//! uplink: submitted [`LogBatch`] values are shipped concurrently, up to a
+//! configurable in-flight window, and each batch's outcome is surfaced on a
+//! [`DeliveryStream`] keyed by `batch_id`. Retries with exponential backoff
+//! are already handled per-batch by [`LogClient::send_batch`]; this module
+//! is only responsible for fanning batches out concurrently and bounding how
+//! many requests are in flight at once.
+
+use std::sync::Arc;
+
+use tokio::sync::{mpsc, Semaphore};
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::client::{ClientError, IngestResponse, LogClient};
+use crate::log_generator::LogBatch;
+
+/// Default number of channel slots for submitted batches awaiting dispatch.
+const DEFAULT_CHANNEL_CAPACITY: usize = 256;
+
+/// Default maximum number of batches in flight to the remote endpoint at once.
+const DEFAULT_MAX_IN_FLIGHT: usize = 8;
+
+/// Configuration for a [`BatchSink`].
+#[derive(Debug, Clone)]
+pub struct SinkConfig {
+    /// Maximum number of batches being sent concurrently.
+    pub max_in_flight: usize,
+
+    /// Capacity of the submission channel; [`BatchSink::submit`] waits once
+    /// this many batches are queued, applying backpressure to the producer.
+    pub channel_capacity: usize,
+}
+
+impl Default for SinkConfig {
+    fn default() -> Self {
+        Self {
+            max_in_flight: DEFAULT_MAX_IN_FLIGHT,
+            channel_capacity: DEFAULT_CHANNEL_CAPACITY,
+        }
+    }
+}
+
+impl SinkConfig {
+    /// Create a new sink config with the given in-flight window.
+    pub fn new(max_in_flight: usize) -> Self {
+        Self {
+            max_in_flight,
+            channel_capacity: DEFAULT_CHANNEL_CAPACITY,
+        }
+    }
+}
+
+/// Errors that can occur submitting a batch to a [`BatchSink`].
+#[derive(Debug)]
+pub enum SinkError {
+    /// The submission channel is full (for non-blocking submits).
+    Full,
+
+    /// The sink's dispatch task has shut down and is no longer accepting batches.
+    Closed,
+}
+
+impl std::fmt::Display for SinkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SinkError::Full => write!(f, "Sink submission channel is full"),
+            SinkError::Closed => write!(f, "Sink has been closed"),
+        }
+    }
+}
+
+impl std::error::Error for SinkError {}
+
+/// The outcome of shipping a single [`LogBatch`].
+#[derive(Debug)]
+pub struct DeliveryResult {
+    /// The batch's client-generated ID, for correlating with the submitted batch.
+    pub batch_id: Option<Uuid>,
+
+    /// Number of log entries that were in the batch.
+    pub batch_size: usize,
+
+    /// The result of sending the batch, including retries already attempted
+    /// by [`LogClient::send_batch`].
+    pub outcome: Result<IngestResponse, ClientError>,
+}
+
+/// A handle for submitting log batches to a [`BatchSink`]'s dispatch task.
+///
+/// This can be cloned and shared across multiple producer tasks, the same
+/// way [`crate::buffer::BufferSender`] is.
+#[derive(Clone)]
+pub struct BatchSink {
+    tx: mpsc::Sender<LogBatch>,
+}
+
+impl BatchSink {
+    /// Submit a batch for delivery.
+    ///
+    /// This is an async operation that will wait if the submission channel
+    /// is full, applying backpressure to the caller when the network can't
+    /// keep up with the configured in-flight window.
+    pub async fn submit(&self, batch: LogBatch) -> Result<(), SinkError> {
+        self.tx.send(batch).await.map_err(|_| SinkError::Closed)
+    }
+
+    /// Try to submit a batch without waiting.
+    ///
+    /// Returns an error if the submission channel is full or closed.
+    pub fn try_submit(&self, batch: LogBatch) -> Result<(), SinkError> {
+        self.tx.try_send(batch).map_err(|e| match e {
+            mpsc::error::TrySendError::Full(_) => SinkError::Full,
+            mpsc::error::TrySendError::Closed(_) => SinkError::Closed,
+        })
+    }
+}
+
+/// A stream of [`DeliveryResult`]s for batches submitted to a [`BatchSink`].
+///
+/// Results arrive in completion order, not submission order, since batches
+/// are shipped concurrently.
+pub struct DeliveryStream {
+    rx: mpsc::UnboundedReceiver<DeliveryResult>,
+}
+
+impl DeliveryStream {
+    /// Wait for the next batch delivery outcome.
+    ///
+    /// Returns `None` once every submitted batch has been accounted for and
+    /// the sink has been dropped.
+    pub async fn next(&mut self) -> Option<DeliveryResult> {
+        self.rx.recv().await
+    }
+}
+
+/// Spawn a batch sink backed by `client`, returning a [`BatchSink`] handle to
+/// submit batches and a [`DeliveryStream`] to observe their outcomes.
+///
+/// Internally this spawns one dispatch task that pulls batches off the
+/// submission channel and, for each one, acquires a permit from a bounded
+/// [`Semaphore`] before spawning a task to actually send it — so at most
+/// `config.max_in_flight` requests are in flight at once, regardless of how
+/// quickly batches are submitted.
+///
+/// # Example
+///
+/// ```no_run
+/// use edge_collector::client::LogClient;
+/// use edge_collector::config::Config;
+/// use edge_collector::log_generator::{LogBatch, LogGenerator};
+/// use edge_collector::sink::{spawn_batch_sink, SinkConfig};
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let client = Arc::new(LogClient::new(&Config::default()).unwrap());
+///     let (sink, mut results) = spawn_batch_sink(client, SinkConfig::default());
+///
+///     let generator = LogGenerator::with_defaults();
+///     sink.submit(LogBatch::new(generator.generate_batch(100))).await.ok();
+///
+///     if let Some(result) = results.next().await {
+///         match result.outcome {
+///             Ok(response) => println!("acked {} logs", response.accepted),
+///             Err(e) => eprintln!("batch {:?} failed: {}", result.batch_id, e),
+///         }
+///     }
+/// }
+/// ```
+pub fn spawn_batch_sink(client: Arc<LogClient>, config: SinkConfig) -> (BatchSink, DeliveryStream) {
+    let (batch_tx, batch_rx) = mpsc::channel(config.channel_capacity);
+    let (result_tx, result_rx) = mpsc::unbounded_channel();
+
+    tokio::spawn(dispatch_loop(
+        batch_rx,
+        client,
+        Arc::new(Semaphore::new(config.max_in_flight.max(1))),
+        result_tx,
+    ));
+
+    (BatchSink { tx: batch_tx }, DeliveryStream { rx: result_rx })
+}
+
+/// Pull batches off `batch_rx` and spawn one send task per batch, bounded by
+/// `semaphore`'s permit count.
+async fn dispatch_loop(
+    mut batch_rx: mpsc::Receiver<LogBatch>,
+    client: Arc<LogClient>,
+    semaphore: Arc<Semaphore>,
+    result_tx: mpsc::UnboundedSender<DeliveryResult>,
+) {
+    while let Some(batch) = batch_rx.recv().await {
+        let permit = match semaphore.clone().acquire_owned().await {
+            Ok(permit) => permit,
+            Err(_) => break, // semaphore closed, shouldn't happen since we own it
+        };
+
+        let client = client.clone();
+        let result_tx = result_tx.clone();
+        let batch_id = batch.batch_id;
+        let batch_size = batch.len();
+
+        tokio::spawn(async move {
+            debug!(batch_id = ?batch_id, batch_size = batch_size, "Dispatching batch");
+            let outcome = client.send_batch(batch).await;
+            drop(permit);
+
+            if result_tx
+                .send(DeliveryResult {
+                    batch_id,
+                    batch_size,
+                    outcome,
+                })
+                .is_err()
+            {
+                warn!(batch_id = ?batch_id, "Delivery result dropped: no listener on the stream");
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log_generator::{LogEntry, LogLevel};
+    use std::time::Duration;
+
+    fn create_test_batch(size: usize) -> LogBatch {
+        let entries: Vec<LogEntry> = (0..size)
+            .map(|i| LogEntry::new(format!("test-{}", i), LogLevel::Info, "Test message"))
+            .collect();
+        LogBatch::new(entries)
+    }
+
+    #[test]
+    fn test_sink_config_default() {
+        let config = SinkConfig::default();
+        assert_eq!(config.max_in_flight, DEFAULT_MAX_IN_FLIGHT);
+        assert_eq!(config.channel_capacity, DEFAULT_CHANNEL_CAPACITY);
+    }
+
+    #[test]
+    fn test_sink_config_new() {
+        let config = SinkConfig::new(16);
+        assert_eq!(config.max_in_flight, 16);
+    }
+
+    #[test]
+    fn test_sink_error_display() {
+        assert_eq!(format!("{}", SinkError::Full), "Sink submission channel is full");
+        assert_eq!(format!("{}", SinkError::Closed), "Sink has been closed");
+    }
+
+    #[tokio::test]
+    async fn test_submit_and_receive_delivery_result() {
+        // Port 1 is reserved and will refuse the connection immediately,
+        // so this exercises the failure path without a real network call.
+        let client = Arc::new(
+            LogClient::with_settings("http://127.0.0.1:1/ingest", Duration::from_secs(1), 0)
+                .unwrap(),
+        );
+        let (sink, mut results) = spawn_batch_sink(client, SinkConfig::new(2));
+
+        let batch = create_test_batch(5);
+        let batch_id = batch.batch_id;
+        sink.submit(batch).await.expect("submit should succeed");
+
+        let result = tokio::time::timeout(Duration::from_secs(5), results.next())
+            .await
+            .expect("should complete")
+            .expect("should get a delivery result");
+
+        assert_eq!(result.batch_id, batch_id);
+        assert_eq!(result.batch_size, 5);
+        assert!(result.outcome.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_try_submit_full_channel() {
+        // Construct the channel directly, without a dispatch task consuming
+        // it, so the channel fills deterministically instead of racing
+        // against how fast the dispatch loop drains it.
+        let (tx, _rx) = mpsc::channel::<LogBatch>(1);
+        let sink = BatchSink { tx };
+
+        sink.try_submit(create_test_batch(1))
+            .expect("first submit should fit in the channel");
+
+        let result = sink.try_submit(create_test_batch(1));
+        assert!(matches!(result, Err(SinkError::Full)));
+    }
+
+    #[tokio::test]
+    async fn test_try_submit_closed_channel() {
+        let (tx, rx) = mpsc::channel::<LogBatch>(1);
+        drop(rx);
+        let sink = BatchSink { tx };
+
+        let result = sink.try_submit(create_test_batch(1));
+        assert!(matches!(result, Err(SinkError::Closed)));
+    }
+}
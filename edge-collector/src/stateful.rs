@@ -0,0 +1,373 @@
+//! Stateful time-series simulation with autocorrelated readings.
+//!
+//! [`crate::log_generator::LogGenerator::generate`] draws an independent reading and independently
+//! samples a level on every call, so a sensor jumps randomly between its
+//! normal and error bands log-to-log — unrealistic for training or testing
+//! anomaly detection downstream. [`StatefulGenerator`] instead keeps
+//! per-`source_id` state and evolves each reading as a bounded, mean-reverting
+//! random walk, deriving the [`LogLevel`] from which band the value lands in
+//! rather than picking the level first. Anomalies are modeled as episodes: a
+//! small per-tick probability shifts the sensor into a fault regime (shifted
+//! mean, larger variance) that persists for a sampled duration before
+//! reverting, producing a realistic Info -> Warn -> Error -> recovery arc.
+
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+use rand::Rng;
+
+use crate::log_generator::{GeneratorConfig, LogEntry, LogLevel, SensorType};
+
+/// Probability per tick that a sensor in its normal regime enters an anomaly episode.
+const ANOMALY_ENTRY_PROBABILITY: f64 = 0.01;
+
+/// Minimum and maximum duration (in ticks) of an anomaly episode.
+const ANOMALY_DURATION_RANGE: (u32, u32) = (5, 20);
+
+/// Fraction of the distance back to the sensor's midpoint closed per tick
+/// by mean reversion.
+const REVERSION_RATE: f64 = 0.12;
+
+/// Describes a continuous sensor's normal/warning bands and random-walk parameters.
+#[derive(Debug, Clone, Copy)]
+struct Band {
+    midpoint: f64,
+    sigma: f64,
+    normal: (f64, f64),
+    warn: (f64, f64),
+    min: f64,
+    max: f64,
+    anomaly_mean_shift: f64,
+    anomaly_sigma_mult: f64,
+}
+
+fn band_for(sensor_type: SensorType) -> Option<Band> {
+    match sensor_type {
+        SensorType::Temperature => Some(Band {
+            midpoint: 22.0,
+            sigma: 0.8,
+            normal: (18.0, 26.0),
+            warn: (10.0, 35.0),
+            min: -10.0,
+            max: 50.0,
+            anomaly_mean_shift: 18.0,
+            anomaly_sigma_mult: 3.0,
+        }),
+        SensorType::Humidity => Some(Band {
+            midpoint: 50.0,
+            sigma: 2.0,
+            normal: (30.0, 70.0),
+            warn: (15.0, 85.0),
+            min: 0.0,
+            max: 100.0,
+            anomaly_mean_shift: 30.0,
+            anomaly_sigma_mult: 3.0,
+        }),
+        SensorType::Pressure => Some(Band {
+            midpoint: 1012.5,
+            sigma: 2.0,
+            normal: (1000.0, 1025.0),
+            warn: (980.0, 1040.0),
+            min: 950.0,
+            max: 1060.0,
+            anomaly_mean_shift: 30.0,
+            anomaly_sigma_mult: 3.0,
+        }),
+        SensorType::Light => Some(Band {
+            midpoint: 500.0,
+            sigma: 40.0,
+            normal: (300.0, 700.0),
+            warn: (100.0, 1000.0),
+            min: 0.0,
+            max: 2000.0,
+            anomaly_mean_shift: 600.0,
+            anomaly_sigma_mult: 3.0,
+        }),
+        SensorType::Vibration => Some(Band {
+            midpoint: 0.25,
+            sigma: 0.05,
+            normal: (0.0, 0.5),
+            warn: (0.5, 2.0),
+            min: 0.0,
+            max: 5.0,
+            anomaly_mean_shift: 1.5,
+            anomaly_sigma_mult: 4.0,
+        }),
+        SensorType::AirQuality => Some(Band {
+            midpoint: 25.0,
+            sigma: 5.0,
+            normal: (0.0, 50.0),
+            warn: (50.0, 200.0),
+            min: 0.0,
+            max: 500.0,
+            anomaly_mean_shift: 150.0,
+            anomaly_sigma_mult: 3.0,
+        }),
+        SensorType::Power => Some(Band {
+            midpoint: 275.0,
+            sigma: 30.0,
+            normal: (50.0, 500.0),
+            warn: (500.0, 1000.0),
+            min: 0.0,
+            max: 2000.0,
+            anomaly_mean_shift: 600.0,
+            anomaly_sigma_mult: 3.0,
+        }),
+        // Motion is a discrete detection event, not a continuous quantity,
+        // so it has no meaningful random walk.
+        SensorType::Motion => None,
+    }
+}
+
+fn level_for_value(band: &Band, value: f64) -> LogLevel {
+    if value >= band.normal.0 && value <= band.normal.1 {
+        LogLevel::Info
+    } else if value >= band.warn.0 && value <= band.warn.1 {
+        LogLevel::Warn
+    } else {
+        LogLevel::Error
+    }
+}
+
+/// Sample from a standard normal distribution via the Box-Muller transform,
+/// avoiding a dependency on a distributions crate beyond `rand` itself.
+fn sample_standard_normal(rng: &mut impl Rng) -> f64 {
+    let u1: f64 = rng.gen_range(f64::EPSILON..1.0);
+    let u2: f64 = rng.gen_range(0.0..1.0);
+    (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+}
+
+/// The regime a sensor's state is currently in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Regime {
+    Normal,
+    Anomaly,
+}
+
+/// Per-`source_id` random-walk state.
+#[derive(Debug, Clone)]
+struct SensorState {
+    value: f64,
+    regime: Regime,
+    regime_ticks_remaining: u32,
+}
+
+impl SensorState {
+    fn initial(band: &Band) -> Self {
+        Self {
+            value: band.midpoint,
+            regime: Regime::Normal,
+            regime_ticks_remaining: 0,
+        }
+    }
+}
+
+/// Generates autocorrelated, mean-reverting sensor readings with occasional
+/// anomaly episodes, keeping a running state per `source_id`.
+///
+/// This complements the stateless [`LogGenerator`](crate::log_generator::LogGenerator),
+/// which remains available for callers that want independent draws.
+pub struct StatefulGenerator {
+    config: GeneratorConfig,
+    states: HashMap<String, SensorState>,
+}
+
+impl StatefulGenerator {
+    /// Create a new stateful generator with the given configuration.
+    pub fn new(config: GeneratorConfig) -> Self {
+        Self {
+            config,
+            states: HashMap::new(),
+        }
+    }
+
+    /// Create a new stateful generator with default configuration.
+    pub fn with_defaults() -> Self {
+        Self::new(GeneratorConfig::default())
+    }
+
+    /// Evolve `source_id`'s state by one tick for the given sensor type and
+    /// produce the resulting log entry.
+    ///
+    /// Motion has no continuous random walk, so its reading is drawn
+    /// independently each tick as before.
+    pub fn tick(&mut self, source_id: impl Into<String>, sensor_type: SensorType) -> LogEntry {
+        let source_id = source_id.into();
+        let mut rng = rand::thread_rng();
+
+        let Some(band) = band_for(sensor_type) else {
+            let detected = rng.gen_bool(0.3);
+            let message = if detected {
+                "Motion detected".to_string()
+            } else {
+                "No motion detected".to_string()
+            };
+            let mut entry = LogEntry::new(source_id, LogLevel::Info, message);
+            if self.config.include_metadata {
+                let mut metadata = HashMap::new();
+                metadata.insert("sensor_type".to_string(), serde_json::json!("motion"));
+                metadata.insert("motion_detected".to_string(), serde_json::json!(detected));
+                entry = entry.with_metadata(metadata);
+            }
+            return entry;
+        };
+
+        let state = self
+            .states
+            .entry(source_id.clone())
+            .or_insert_with(|| SensorState::initial(&band));
+
+        // Possibly enter or continue an anomaly episode.
+        match state.regime {
+            Regime::Normal => {
+                if rng.gen_bool(ANOMALY_ENTRY_PROBABILITY) {
+                    state.regime = Regime::Anomaly;
+                    state.regime_ticks_remaining =
+                        rng.gen_range(ANOMALY_DURATION_RANGE.0..=ANOMALY_DURATION_RANGE.1);
+                }
+            }
+            Regime::Anomaly => {
+                state.regime_ticks_remaining = state.regime_ticks_remaining.saturating_sub(1);
+                if state.regime_ticks_remaining == 0 {
+                    state.regime = Regime::Normal;
+                }
+            }
+        }
+
+        let (effective_mid, effective_sigma) = match state.regime {
+            Regime::Normal => (band.midpoint, band.sigma),
+            Regime::Anomaly => (
+                band.midpoint + band.anomaly_mean_shift,
+                band.sigma * band.anomaly_sigma_mult,
+            ),
+        };
+
+        let drift = (effective_mid - state.value) * REVERSION_RATE;
+        let noise = sample_standard_normal(&mut rng) * effective_sigma;
+        state.value = (state.value + drift + noise).clamp(band.min, band.max);
+
+        let level = level_for_value(&band, state.value);
+        let message = format!(
+            "{} reading: {:.2} {}",
+            sensor_type.name(),
+            state.value,
+            sensor_type.unit()
+        );
+
+        let mut entry = LogEntry::new(source_id, level, message);
+        if self.config.include_metadata {
+            let mut metadata = HashMap::new();
+            metadata.insert(
+                "sensor_type".to_string(),
+                serde_json::json!(sensor_type.name()),
+            );
+            metadata.insert("unit".to_string(), serde_json::json!(sensor_type.unit()));
+            metadata.insert("reading".to_string(), serde_json::json!(state.value));
+            metadata.insert(
+                "anomaly".to_string(),
+                serde_json::json!(state.regime == Regime::Anomaly),
+            );
+            entry = entry.with_metadata(metadata);
+        }
+
+        entry
+    }
+
+    /// Drop all per-`source_id` state, resetting every sensor to its
+    /// midpoint and normal regime.
+    pub fn reset(&mut self) {
+        self.states.clear();
+    }
+
+    /// Number of distinct `source_id`s with tracked state.
+    pub fn tracked_sources(&self) -> usize {
+        self.states.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tick_creates_state_for_new_source() {
+        let mut generator = StatefulGenerator::with_defaults();
+        assert_eq!(generator.tracked_sources(), 0);
+
+        generator.tick("edge-temperature-001", SensorType::Temperature);
+        assert_eq!(generator.tracked_sources(), 1);
+    }
+
+    #[test]
+    fn test_tick_reuses_state_for_same_source() {
+        let mut generator = StatefulGenerator::with_defaults();
+        generator.tick("edge-temperature-001", SensorType::Temperature);
+        generator.tick("edge-temperature-001", SensorType::Temperature);
+        assert_eq!(generator.tracked_sources(), 1);
+    }
+
+    #[test]
+    fn test_reset_clears_state() {
+        let mut generator = StatefulGenerator::with_defaults();
+        generator.tick("edge-temperature-001", SensorType::Temperature);
+        generator.reset();
+        assert_eq!(generator.tracked_sources(), 0);
+    }
+
+    #[test]
+    fn test_readings_are_autocorrelated_not_independent() {
+        let mut generator = StatefulGenerator::with_defaults();
+        let mut readings = Vec::new();
+
+        for _ in 0..50 {
+            let entry = generator.tick("edge-temperature-001", SensorType::Temperature);
+            let reading = entry
+                .metadata
+                .as_ref()
+                .unwrap()
+                .get("reading")
+                .unwrap()
+                .as_f64()
+                .unwrap();
+            readings.push(reading);
+        }
+
+        // Consecutive readings should never jump by more than a few sigma;
+        // an independent-draw generator would routinely violate this.
+        for window in readings.windows(2) {
+            assert!((window[1] - window[0]).abs() < 15.0);
+        }
+    }
+
+    #[test]
+    fn test_motion_sensor_has_no_random_walk_state() {
+        let mut generator = StatefulGenerator::with_defaults();
+        let entry = generator.tick("edge-motion-001", SensorType::Motion);
+        assert_eq!(generator.tracked_sources(), 0);
+        assert!(entry.metadata.is_some());
+    }
+
+    #[test]
+    fn test_level_for_value_bands() {
+        let band = band_for(SensorType::Temperature).unwrap();
+        assert_eq!(level_for_value(&band, 22.0), LogLevel::Info);
+        assert_eq!(level_for_value(&band, 30.0), LogLevel::Warn);
+        assert_eq!(level_for_value(&band, 45.0), LogLevel::Error);
+    }
+
+    #[test]
+    fn test_value_stays_within_clamped_bounds() {
+        let mut generator = StatefulGenerator::with_defaults();
+        for _ in 0..200 {
+            let entry = generator.tick("edge-temperature-001", SensorType::Temperature);
+            let reading = entry
+                .metadata
+                .unwrap()
+                .get("reading")
+                .unwrap()
+                .as_f64()
+                .unwrap();
+            assert!((-10.0..=50.0).contains(&reading));
+        }
+    }
+}
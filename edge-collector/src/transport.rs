@@ -0,0 +1,354 @@
+//! Pluggable uplink transport behind a [`LogSink`] trait.
+//!
+//! [`crate::client::LogClient`] + [`crate::client::send_batch`] already do
+//! all the batching-adjacent work (retries, circuit breaking, compression,
+//! rate limiting) for the HTTP case; [`HttpSink`] just puts that behind
+//! [`LogSink`] so [`crate::buffer::buffer_task`]'s `on_flush` callback — and
+//! anything else that moves a batch — doesn't need to know which wire
+//! protocol is on the other end. `EDGE_COLLECTOR_TRANSPORT=http|kafka`
+//! selects the implementation at startup; [`build_sink`] does the actual
+//! selection. Many edge-to-cloud pipelines ingest through a Kafka/broker
+//! layer rather than a FastAPI endpoint directly, and a trait object also
+//! makes the collector testable against a fake sink without a live server.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use tracing::warn;
+
+use crate::client::{send_batch, ClientError, LogClient};
+use crate::log_generator::LogEntry;
+
+/// Errors sending a batch through any [`LogSink`] implementation.
+#[derive(Debug)]
+pub enum TransportError {
+    /// The HTTP transport failed; see [`ClientError`] for the cause.
+    Http(ClientError),
+    /// The Kafka transport failed to produce the batch to its topic.
+    Kafka(String),
+}
+
+impl std::fmt::Display for TransportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TransportError::Http(e) => write!(f, "HTTP transport error: {}", e),
+            TransportError::Kafka(e) => write!(f, "Kafka transport error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for TransportError {}
+
+/// A future-returning, object-safe stand-in for `async fn send`.
+type SendFuture<'a> = Pin<Box<dyn Future<Output = Result<(), TransportError>> + Send + 'a>>;
+
+/// A destination a flushed batch of [`LogEntry`] can be shipped to.
+///
+/// Implementations own their own retry/backoff policy; `buffer_task` treats
+/// any sink failure as final for that batch (see
+/// [`crate::persistence::DurableSink`] for what happens next).
+pub trait LogSink: Send + Sync {
+    /// Send `batch` to this sink's backend.
+    fn send<'a>(&'a self, batch: Vec<LogEntry>) -> SendFuture<'a>;
+}
+
+/// Ships batches over HTTP via [`LogClient`], reusing its retry/circuit
+/// breaker/rate limiting as-is.
+pub struct HttpSink {
+    client: Arc<LogClient>,
+}
+
+impl HttpSink {
+    /// Wrap `client` as a [`LogSink`].
+    pub fn new(client: Arc<LogClient>) -> Self {
+        Self { client }
+    }
+}
+
+impl LogSink for HttpSink {
+    fn send<'a>(&'a self, batch: Vec<LogEntry>) -> SendFuture<'a> {
+        Box::pin(async move {
+            send_batch(&self.client, crate::log_generator::LogBatch::new(batch))
+                .await
+                .map_err(|e| TransportError::Http(client_error_from_boxed(e)))
+        })
+    }
+}
+
+/// `send_batch` returns a boxed `dyn Error`, but it only ever boxes a
+/// [`ClientError`] — recover it rather than losing the variant to a string.
+fn client_error_from_boxed(e: Box<dyn std::error::Error + Send + Sync>) -> ClientError {
+    match e.downcast::<ClientError>() {
+        Ok(client_error) => *client_error,
+        Err(other) => ClientError::Config(other.to_string()),
+    }
+}
+
+/// Which transport backend to use, selected by `EDGE_COLLECTOR_TRANSPORT`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransportKind {
+    /// Ship batches over HTTP to the Python FastAPI backend (the default).
+    Http,
+    /// Ship batches to a Kafka topic; requires the `kafka` feature.
+    Kafka,
+}
+
+impl TransportKind {
+    /// Read `EDGE_COLLECTOR_TRANSPORT`, defaulting to [`TransportKind::Http`]
+    /// for anything unset or unrecognized.
+    pub fn from_env() -> Self {
+        match std::env::var("EDGE_COLLECTOR_TRANSPORT") {
+            Ok(value) if value.eq_ignore_ascii_case("kafka") => TransportKind::Kafka,
+            Ok(value) if value.eq_ignore_ascii_case("http") => TransportKind::Http,
+            Ok(value) => {
+                warn!(value = %value, "Unrecognized EDGE_COLLECTOR_TRANSPORT, defaulting to http");
+                TransportKind::Http
+            }
+            Err(_) => TransportKind::Http,
+        }
+    }
+}
+
+/// Kafka producer settings, read from environment variables.
+///
+/// Present regardless of the `kafka` feature so `EDGE_COLLECTOR_TRANSPORT`
+/// misconfiguration can be reported even in builds that can't act on it;
+/// only [`kafka::KafkaSink`] itself needs `rdkafka` and the feature flag.
+#[derive(Debug, Clone)]
+pub struct KafkaConfig {
+    /// Comma-separated `host:port` broker list (`bootstrap.servers`).
+    pub brokers: String,
+    /// Topic each batch's logs are produced to.
+    pub topic: String,
+    /// SASL username, if SASL auth is in use.
+    pub sasl_username: Option<String>,
+    /// SASL password, if SASL auth is in use.
+    pub sasl_password: Option<String>,
+    /// SASL mechanism, e.g. `PLAIN` or `SCRAM-SHA-512`.
+    pub sasl_mechanism: Option<String>,
+    /// `security.protocol`, e.g. `SASL_SSL` or `SSL`; defaults to
+    /// `PLAINTEXT` when unset.
+    pub security_protocol: String,
+}
+
+/// Default Kafka broker list if `EDGE_COLLECTOR_KAFKA_BROKERS` is unset.
+pub const DEFAULT_KAFKA_BROKERS: &str = "localhost:9092";
+
+/// Default Kafka topic if `EDGE_COLLECTOR_KAFKA_TOPIC` is unset.
+pub const DEFAULT_KAFKA_TOPIC: &str = "edge-collector-logs";
+
+/// Default `security.protocol` if `EDGE_COLLECTOR_KAFKA_SECURITY_PROTOCOL`
+/// is unset.
+const DEFAULT_KAFKA_SECURITY_PROTOCOL: &str = "PLAINTEXT";
+
+impl KafkaConfig {
+    /// Read Kafka settings from `EDGE_COLLECTOR_KAFKA_*` environment
+    /// variables, falling back to defaults (or `None`, for auth) where unset.
+    pub fn from_env() -> Self {
+        Self {
+            brokers: std::env::var("EDGE_COLLECTOR_KAFKA_BROKERS")
+                .unwrap_or_else(|_| DEFAULT_KAFKA_BROKERS.to_string()),
+            topic: std::env::var("EDGE_COLLECTOR_KAFKA_TOPIC")
+                .unwrap_or_else(|_| DEFAULT_KAFKA_TOPIC.to_string()),
+            sasl_username: std::env::var("EDGE_COLLECTOR_KAFKA_SASL_USERNAME").ok(),
+            sasl_password: std::env::var("EDGE_COLLECTOR_KAFKA_SASL_PASSWORD").ok(),
+            sasl_mechanism: std::env::var("EDGE_COLLECTOR_KAFKA_SASL_MECHANISM").ok(),
+            security_protocol: std::env::var("EDGE_COLLECTOR_KAFKA_SECURITY_PROTOCOL")
+                .unwrap_or_else(|_| DEFAULT_KAFKA_SECURITY_PROTOCOL.to_string()),
+        }
+    }
+}
+
+#[cfg(feature = "kafka")]
+mod kafka {
+    use super::{KafkaConfig, LogSink, SendFuture, TransportError};
+    use crate::log_generator::{LogBatch, LogEntry};
+    use rdkafka::config::ClientConfig;
+    use rdkafka::producer::{FutureProducer, FutureRecord};
+    use rdkafka::util::Timeout;
+    use tracing::debug;
+
+    /// Ships batches to a Kafka topic via `rdkafka`'s async producer,
+    /// reusing the same `on_flush` contract [`super::HttpSink`] does — one
+    /// produce call per flushed batch.
+    pub struct KafkaSink {
+        producer: FutureProducer,
+        topic: String,
+    }
+
+    impl KafkaSink {
+        /// Build a producer from `config`, applying SASL/TLS settings if present.
+        pub fn new(config: &KafkaConfig) -> Result<Self, TransportError> {
+            let mut client_config = ClientConfig::new();
+            client_config
+                .set("bootstrap.servers", &config.brokers)
+                .set("security.protocol", &config.security_protocol);
+
+            if let Some(mechanism) = &config.sasl_mechanism {
+                client_config.set("sasl.mechanisms", mechanism);
+            }
+            if let Some(username) = &config.sasl_username {
+                client_config.set("sasl.username", username);
+            }
+            if let Some(password) = &config.sasl_password {
+                client_config.set("sasl.password", password);
+            }
+
+            let producer: FutureProducer = client_config
+                .create()
+                .map_err(|e| TransportError::Kafka(e.to_string()))?;
+
+            Ok(Self { producer, topic: config.topic.clone() })
+        }
+    }
+
+    impl LogSink for KafkaSink {
+        fn send<'a>(&'a self, batch: Vec<LogEntry>) -> SendFuture<'a> {
+            Box::pin(async move {
+                let payload = serde_json::to_vec(&LogBatch::new(batch))
+                    .map_err(|e| TransportError::Kafka(e.to_string()))?;
+
+                let record: FutureRecord<'_, (), Vec<u8>> =
+                    FutureRecord::to(&self.topic).payload(&payload);
+
+                self.producer
+                    .send(record, Timeout::Never)
+                    .await
+                    .map_err(|(e, _)| TransportError::Kafka(e.to_string()))?;
+
+                debug!(topic = %self.topic, bytes = payload.len(), "Produced batch to Kafka");
+                Ok(())
+            })
+        }
+    }
+}
+
+#[cfg(feature = "kafka")]
+pub use kafka::KafkaSink;
+
+/// Build the configured [`LogSink`] for this process, selecting between
+/// [`HttpSink`] and (with the `kafka` feature) [`kafka::KafkaSink`] based on
+/// [`TransportKind::from_env`].
+///
+/// Falls back to [`HttpSink`] with a warning if Kafka is selected but the
+/// `kafka` feature wasn't compiled in, or the producer fails to initialize.
+pub fn build_sink(client: Arc<LogClient>) -> Arc<dyn LogSink> {
+    match TransportKind::from_env() {
+        TransportKind::Http => Arc::new(HttpSink::new(client)),
+        TransportKind::Kafka => build_kafka_sink(client),
+    }
+}
+
+#[cfg(feature = "kafka")]
+fn build_kafka_sink(client: Arc<LogClient>) -> Arc<dyn LogSink> {
+    let config = KafkaConfig::from_env();
+    match KafkaSink::new(&config) {
+        Ok(sink) => Arc::new(sink),
+        Err(e) => {
+            warn!(error = %e, "Failed to initialize Kafka transport, falling back to HTTP");
+            Arc::new(HttpSink::new(client))
+        }
+    }
+}
+
+#[cfg(not(feature = "kafka"))]
+fn build_kafka_sink(client: Arc<LogClient>) -> Arc<dyn LogSink> {
+    warn!("EDGE_COLLECTOR_TRANSPORT=kafka requested but built without the `kafka` feature, falling back to HTTP");
+    Arc::new(HttpSink::new(client))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use crate::log_generator::LogLevel;
+
+    fn test_entries() -> Vec<LogEntry> {
+        vec![LogEntry::new("test-source", LogLevel::Info, "test message")]
+    }
+
+    #[test]
+    fn test_transport_kind_defaults_to_http() {
+        std::env::remove_var("EDGE_COLLECTOR_TRANSPORT");
+        assert_eq!(TransportKind::from_env(), TransportKind::Http);
+    }
+
+    #[test]
+    fn test_transport_kind_parses_kafka_case_insensitively() {
+        std::env::set_var("EDGE_COLLECTOR_TRANSPORT", "Kafka");
+        assert_eq!(TransportKind::from_env(), TransportKind::Kafka);
+        std::env::remove_var("EDGE_COLLECTOR_TRANSPORT");
+    }
+
+    #[test]
+    fn test_transport_kind_falls_back_on_unrecognized_value() {
+        std::env::set_var("EDGE_COLLECTOR_TRANSPORT", "carrier-pigeon");
+        assert_eq!(TransportKind::from_env(), TransportKind::Http);
+        std::env::remove_var("EDGE_COLLECTOR_TRANSPORT");
+    }
+
+    #[test]
+    fn test_kafka_config_from_env_defaults() {
+        std::env::remove_var("EDGE_COLLECTOR_KAFKA_BROKERS");
+        std::env::remove_var("EDGE_COLLECTOR_KAFKA_TOPIC");
+        let config = KafkaConfig::from_env();
+        assert_eq!(config.brokers, DEFAULT_KAFKA_BROKERS);
+        assert_eq!(config.topic, DEFAULT_KAFKA_TOPIC);
+        assert_eq!(config.security_protocol, DEFAULT_KAFKA_SECURITY_PROTOCOL);
+        assert!(config.sasl_username.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_http_sink_surfaces_client_error() {
+        // Port 1 is reserved and refuses connections immediately.
+        let client = Arc::new(
+            LogClient::with_settings("http://127.0.0.1:1/ingest", Duration::from_millis(100), 0).unwrap(),
+        );
+        let sink = HttpSink::new(client);
+
+        let result = sink.send(test_entries()).await;
+        assert!(matches!(result, Err(TransportError::Http(_))));
+    }
+
+    #[tokio::test]
+    async fn test_build_sink_defaults_to_http() {
+        std::env::remove_var("EDGE_COLLECTOR_TRANSPORT");
+        let client = Arc::new(
+            LogClient::with_settings("http://127.0.0.1:1/ingest", Duration::from_millis(100), 0).unwrap(),
+        );
+        let sink = build_sink(client);
+
+        // Can't downcast a trait object back to HttpSink without adding
+        // `Any`, so just confirm it behaves like the HTTP path does.
+        let result = sink.send(test_entries()).await;
+        assert!(matches!(result, Err(TransportError::Http(_))));
+    }
+
+    // Exercises a fake in-memory sink, the way a test double for the whole
+    // collector would use `LogSink` without a live HTTP server or broker.
+    struct CountingSink {
+        sent: AtomicUsize,
+    }
+
+    impl LogSink for CountingSink {
+        fn send<'a>(&'a self, batch: Vec<LogEntry>) -> SendFuture<'a> {
+            self.sent.fetch_add(batch.len(), Ordering::SeqCst);
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_log_sink_trait_object_dispatch() {
+        let sink: Arc<dyn LogSink> = Arc::new(CountingSink { sent: AtomicUsize::new(0) });
+        sink.send(test_entries()).await.unwrap();
+        sink.send(test_entries()).await.unwrap();
+
+        // Downcast back through the concrete type isn't available without
+        // `Any`, so this just confirms two sends against the trait object
+        // both complete without panicking or double-counting via the Arc.
+        assert_eq!(Arc::strong_count(&sink), 1);
+    }
+}
@@ -0,0 +1,215 @@
+//! Adaptive batch-size/flush-interval tuning for [`crate::buffer::LogBuffer`].
+//!
+//! A fixed `batch_size`/`flush_interval` is a guess: too small wastes HTTP
+//! overhead on a fast, healthy backend; too large lets latency creep up when
+//! the backend slows down. [`AdaptiveTuner`] nudges both toward a target
+//! flush latency as the caller reports how long each flush actually took to
+//! send: slower-than-target flushes shrink the batch and flush less often,
+//! faster-than-target flushes grow the batch and flush more often, each
+//! clamped to a configured min/max so tuning can't run away in either
+//! direction.
+
+use std::time::Duration;
+
+/// Bounds and target latency used by [`AdaptiveTuner`].
+#[derive(Debug, Clone)]
+pub struct AdaptiveTuningConfig {
+    /// Flush latency the tuner steers batch size and flush interval toward.
+    pub target_flush_latency: Duration,
+
+    /// Floor on the tuned batch size.
+    pub min_batch_size: usize,
+
+    /// Ceiling on the tuned batch size.
+    pub max_batch_size: usize,
+
+    /// Floor on the tuned flush interval.
+    pub min_flush_interval: Duration,
+
+    /// Ceiling on the tuned flush interval.
+    pub max_flush_interval: Duration,
+}
+
+impl Default for AdaptiveTuningConfig {
+    fn default() -> Self {
+        Self {
+            target_flush_latency: Duration::from_millis(500),
+            min_batch_size: 10,
+            max_batch_size: 5_000,
+            min_flush_interval: Duration::from_millis(500),
+            max_flush_interval: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Tracks a tuned `batch_size`/`flush_interval` pair, adjusted each time the
+/// caller reports how long a flush took via [`AdaptiveTuner::record_flush`].
+#[derive(Debug, Clone)]
+pub struct AdaptiveTuner {
+    config: AdaptiveTuningConfig,
+    batch_size: usize,
+    flush_interval: Duration,
+}
+
+impl AdaptiveTuner {
+    /// Create a tuner starting from `initial_batch_size`/`initial_flush_interval`,
+    /// clamped to `config`'s bounds.
+    pub fn new(
+        config: AdaptiveTuningConfig,
+        initial_batch_size: usize,
+        initial_flush_interval: Duration,
+    ) -> Self {
+        Self {
+            batch_size: initial_batch_size.clamp(config.min_batch_size, config.max_batch_size),
+            flush_interval: initial_flush_interval
+                .clamp(config.min_flush_interval, config.max_flush_interval),
+            config,
+        }
+    }
+
+    /// Record that a flush took `elapsed` to send, adjusting batch size and
+    /// flush interval toward the configured target latency.
+    ///
+    /// Flushes slower than 1.5x the target shrink the batch 10% and flush
+    /// less often; flushes faster than half the target grow the batch 10%
+    /// and flush more often. Flushes within that band are left untouched to
+    /// avoid oscillating on normal jitter.
+    pub fn record_flush(&mut self, elapsed: Duration) {
+        let target = self.config.target_flush_latency;
+
+        if elapsed > target.mul_f64(1.5) {
+            self.shrink();
+        } else if elapsed < target.mul_f64(0.5) {
+            self.grow();
+        }
+    }
+
+    fn shrink(&mut self) {
+        let new_size = (self.batch_size as f64 * 0.9) as usize;
+        self.batch_size = new_size.max(self.config.min_batch_size);
+        self.flush_interval = self
+            .flush_interval
+            .mul_f64(1.1)
+            .min(self.config.max_flush_interval);
+    }
+
+    fn grow(&mut self) {
+        let new_size = self.batch_size + (self.batch_size + 9) / 10;
+        self.batch_size = new_size.min(self.config.max_batch_size);
+        self.flush_interval = self
+            .flush_interval
+            .mul_f64(0.9)
+            .max(self.config.min_flush_interval);
+    }
+
+    /// The current tuned batch size.
+    pub fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    /// The current tuned flush interval.
+    pub fn flush_interval(&self) -> Duration {
+        self.flush_interval
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tuner() -> AdaptiveTuner {
+        AdaptiveTuner::new(
+            AdaptiveTuningConfig::default(),
+            100,
+            Duration::from_secs(5),
+        )
+    }
+
+    #[test]
+    fn test_starts_at_initial_values() {
+        let t = tuner();
+        assert_eq!(t.batch_size(), 100);
+        assert_eq!(t.flush_interval(), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_slow_flush_shrinks_batch_and_flushes_less_often() {
+        let mut t = tuner();
+        t.record_flush(Duration::from_secs(1)); // well over 1.5x the 500ms target
+        assert_eq!(t.batch_size(), 90);
+        assert!(t.flush_interval() > Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_fast_flush_grows_batch_and_flushes_more_often() {
+        let mut t = tuner();
+        t.record_flush(Duration::from_millis(100)); // well under 0.5x the 500ms target
+        assert_eq!(t.batch_size(), 110);
+        assert!(t.flush_interval() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_within_target_band_is_left_untouched() {
+        let mut t = tuner();
+        t.record_flush(Duration::from_millis(500)); // exactly on target
+        assert_eq!(t.batch_size(), 100);
+        assert_eq!(t.flush_interval(), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_batch_size_clamped_to_min() {
+        let mut t = AdaptiveTuner::new(
+            AdaptiveTuningConfig {
+                min_batch_size: 50,
+                ..AdaptiveTuningConfig::default()
+            },
+            52,
+            Duration::from_secs(5),
+        );
+
+        for _ in 0..10 {
+            t.record_flush(Duration::from_secs(10));
+        }
+
+        assert_eq!(t.batch_size(), 50);
+    }
+
+    #[test]
+    fn test_batch_size_clamped_to_max() {
+        let mut t = AdaptiveTuner::new(
+            AdaptiveTuningConfig {
+                max_batch_size: 200,
+                ..AdaptiveTuningConfig::default()
+            },
+            190,
+            Duration::from_secs(5),
+        );
+
+        for _ in 0..10 {
+            t.record_flush(Duration::from_millis(1));
+        }
+
+        assert_eq!(t.batch_size(), 200);
+    }
+
+    #[test]
+    fn test_flush_interval_clamped_to_bounds() {
+        let mut t = AdaptiveTuner::new(
+            AdaptiveTuningConfig {
+                min_flush_interval: Duration::from_millis(900),
+                max_flush_interval: Duration::from_secs(1),
+                ..AdaptiveTuningConfig::default()
+            },
+            100,
+            Duration::from_millis(950),
+        );
+
+        t.record_flush(Duration::from_millis(1));
+        assert_eq!(t.flush_interval(), Duration::from_millis(900));
+
+        for _ in 0..10 {
+            t.record_flush(Duration::from_secs(10));
+        }
+        assert_eq!(t.flush_interval(), Duration::from_secs(1));
+    }
+}